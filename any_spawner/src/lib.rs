@@ -11,6 +11,11 @@
 //! - no "join handle" or other result is returned from the spawn
 //! - the `Future` must output `()`
 //!
+//! It also provides executor-agnostic timers: [`Executor::sleep`], [`Executor::interval`], and
+//! [`Executor::timeout`] use the timer of the current executor where one is available (`tokio`'s
+//! timer, `setTimeout` in the browser, or `glib` timeouts), and can be customized for a
+//! [`CustomExecutor`] by implementing [`CustomExecutor::sleep`].
+//!
 //! ```no_run
 //! use any_spawner::Executor;
 //!
@@ -27,9 +32,18 @@
 #![deny(missing_docs)]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
-use std::{future::Future, pin::Pin, sync::OnceLock};
+use futures::Stream;
+use std::{future::Future, pin::Pin, sync::OnceLock, time::Duration};
 use thiserror::Error;
 
+#[cfg(feature = "test-executor")]
+mod test_executor;
+#[cfg(not(target_arch = "wasm32"))]
+mod thread_timer;
+#[cfg(feature = "test-executor")]
+#[cfg_attr(docsrs, doc(cfg(feature = "test-executor")))]
+pub use test_executor::TestExecutor;
+
 /// A future that has been pinned.
 pub type PinnedFuture<T> = Pin<Box<dyn Future<Output = T> + Send>>;
 /// A future that has been pinned.
@@ -41,6 +55,8 @@ type SpawnFn = fn(PinnedFuture<()>);
 type SpawnLocalFn = fn(PinnedLocalFuture<()>);
 // Type alias for the poll_local function pointer.
 type PollLocalFn = fn();
+// Type alias for the sleep function pointer.
+type SleepFn = fn(Duration) -> PinnedFuture<()>;
 
 /// Holds the function pointers for the current global executor.
 #[derive(Clone, Copy)]
@@ -48,6 +64,7 @@ struct ExecutorFns {
     spawn: SpawnFn,
     spawn_local: SpawnLocalFn,
     poll_local: PollLocalFn,
+    sleep: SleepFn,
 }
 
 // Use a single OnceLock to ensure atomic initialization of all functions.
@@ -91,6 +108,29 @@ fn no_op_spawn_local(_: PinnedLocalFuture<()>) {
     );
 }
 
+// Executors without a timer of their own wait on a background thread shared by every sleep, or
+// on `setTimeout` on wasm32, where threads cannot be spawned.
+#[cold]
+#[inline(never)]
+fn fallback_sleep(duration: Duration) -> PinnedFuture<()> {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        thread_timer::sleep(duration)
+    }
+    #[cfg(all(target_arch = "wasm32", feature = "wasm-bindgen"))]
+    {
+        wasm_sleep(duration)
+    }
+    #[cfg(all(target_arch = "wasm32", not(feature = "wasm-bindgen")))]
+    {
+        _ = duration;
+        panic!(
+            "Executor::sleep called on wasm32 without the `wasm-bindgen` \
+             feature, and the executor has no timer of its own."
+        );
+    }
+}
+
 /// Errors that can occur when using the executor.
 #[derive(Error, Debug)]
 pub enum ExecutorError {
@@ -99,6 +139,11 @@ pub enum ExecutorError {
     AlreadySet,
 }
 
+/// The error returned by [`Executor::timeout`] when the future did not complete in time.
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[error("Deadline has elapsed.")]
+pub struct Elapsed;
+
 /// A global async executor that can spawn tasks.
 pub struct Executor;
 
@@ -165,6 +210,50 @@ impl Executor {
         }
         // If not initialized or doesn't support polling, do nothing gracefully.
     }
+
+    /// Waits until the given duration has elapsed.
+    ///
+    /// Uses the timer of the globally configured executor. If the executor does not provide a
+    /// timer, or no executor has been initialized, this waits on a background thread that is
+    /// shared by all such sleeps, or on wasm32, with `setTimeout`.
+    ///
+    /// # Panics
+    /// Panics on wasm32 if the executor does not provide a timer and the `wasm-bindgen` feature
+    /// is not enabled, because no thread can be spawned to wait on.
+    #[inline(always)]
+    pub fn sleep(duration: Duration) -> impl Future<Output = ()> + Send {
+        match EXECUTOR_FNS.get() {
+            Some(fns) => (fns.sleep)(duration),
+            None => fallback_sleep(duration),
+        }
+    }
+
+    /// Creates a stream that yields `()` once every `period`, beginning after the first `period`
+    /// has elapsed.
+    ///
+    /// Each tick waits for `period` after the previous tick has been received, so slow consumers
+    /// will see ticks spaced further apart rather than a burst of missed ticks.
+    pub fn interval(period: Duration) -> impl Stream<Item = ()> + Send {
+        futures::stream::unfold((), move |_| async move {
+            Executor::sleep(period).await;
+            Some(((), ()))
+        })
+    }
+
+    /// Runs the given future, returning `Err(Elapsed)` if it has not completed before the given
+    /// duration has elapsed.
+    pub async fn timeout<T>(
+        duration: Duration,
+        fut: impl Future<Output = T>,
+    ) -> Result<T, Elapsed> {
+        use futures::future::{select, Either};
+
+        let fut = std::pin::pin!(fut);
+        match select(fut, Executor::sleep(duration)).await {
+            Either::Left((value, _)) => Ok(value),
+            Either::Right(_) => Err(Elapsed),
+        }
+    }
 }
 
 impl Executor {
@@ -185,6 +274,11 @@ impl Executor {
             },
             // Tokio doesn't have an explicit global poll function like LocalPool::run_until_stalled
             poll_local: no_op_poll,
+            // tokio's timer can only be used from within a runtime
+            sleep: |duration| match tokio::runtime::Handle::try_current() {
+                Ok(_) => Box::pin(tokio::time::sleep(duration)),
+                Err(_) => fallback_sleep(duration),
+            },
         };
         EXECUTOR_FNS
             .set(executor_impl)
@@ -206,6 +300,7 @@ impl Executor {
                 wasm_bindgen_futures::spawn_local(fut);
             },
             poll_local: no_op_poll,
+            sleep: wasm_sleep,
         };
         EXECUTOR_FNS
             .set(executor_impl)
@@ -231,6 +326,7 @@ impl Executor {
             },
             // Glib needs event loop integration, explicit polling isn't the standard model here.
             poll_local: no_op_poll,
+            sleep: glib::timeout_future,
         };
         EXECUTOR_FNS
             .set(executor_impl)
@@ -291,6 +387,8 @@ impl Executor {
                     // If already borrowed, we're likely in a nested poll, so do nothing.
                 });
             },
+            // The futures executor has no timer of its own.
+            sleep: fallback_sleep,
         };
 
         EXECUTOR_FNS
@@ -334,6 +432,35 @@ impl Executor {
                     pool.try_tick();
                 });
             },
+            // async-executor has no timer of its own.
+            sleep: fallback_sleep,
+        };
+        EXECUTOR_FNS
+            .set(executor_impl)
+            .map_err(|_| ExecutorError::AlreadySet)
+    }
+
+    /// Globally sets a deterministic, single-threaded [`TestExecutor`] as the executor used to
+    /// spawn tasks.
+    ///
+    /// Spawned tasks only run when [`TestExecutor::run_until_stalled`] or
+    /// [`TestExecutor::advance`] is called on the thread that spawned them, and
+    /// [`Executor::sleep`] waits on a virtual clock that is only moved forward by
+    /// [`TestExecutor::advance`]. This is intended for testing code that depends on timing.
+    ///
+    /// Returns `Err(_)` if a global executor has already been set.
+    ///
+    /// Requires the `test-executor` feature to be activated on this crate.
+    #[cfg(feature = "test-executor")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "test-executor")))]
+    pub fn init_test_executor() -> Result<(), ExecutorError> {
+        let executor_impl = ExecutorFns {
+            spawn: |fut| TestExecutor::spawn_local(fut),
+            spawn_local: TestExecutor::spawn_local,
+            poll_local: || {
+                TestExecutor::run_until_stalled();
+            },
+            sleep: TestExecutor::sleep,
         };
         EXECUTOR_FNS
             .set(executor_impl)
@@ -370,6 +497,9 @@ impl Executor {
             poll_local: || {
                 CUSTOM_EXECUTOR_INSTANCE.get().unwrap().poll_local();
             },
+            sleep: |duration| {
+                CUSTOM_EXECUTOR_INSTANCE.get().unwrap().sleep(duration)
+            },
         };
 
         EXECUTOR_FNS
@@ -422,6 +552,10 @@ impl Executor {
                 CUSTOM_EXECUTOR_INSTANCE
                     .with(|this| this.get().unwrap().poll_local());
             },
+            sleep: |duration| {
+                CUSTOM_EXECUTOR_INSTANCE
+                    .with(|this| this.get().unwrap().sleep(duration))
+            },
         };
 
         EXECUTOR_FNS
@@ -447,12 +581,49 @@ pub trait CustomExecutor {
     /// non-blocking or use mechanisms like `try_tick` or `try_borrow_mut` to handle
     /// re-entrant calls safely.
     fn poll_local(&self);
+    /// Returns a future that resolves once the given duration has elapsed.
+    ///
+    /// By default, this waits on a background thread, or on wasm32, with `setTimeout`, which
+    /// requires the `wasm-bindgen` feature. Executors that have a timer of their own should
+    /// override this to use it instead.
+    fn sleep(&self, duration: Duration) -> PinnedFuture<()> {
+        fallback_sleep(duration)
+    }
 }
 
 // Ensure CustomExecutor is object-safe
 #[allow(dead_code)]
 fn test_object_safety(_: Box<dyn CustomExecutor + Send + Sync>) {} // Added Send + Sync constraint here for global usage
 
+/// Schedules a wakeup with the JavaScript `setTimeout` function.
+///
+/// The callback only sends on a channel, so that the returned future is `Send`.
+#[cfg(feature = "wasm-bindgen")]
+fn wasm_sleep(duration: Duration) -> PinnedFuture<()> {
+    use futures::channel::oneshot;
+    use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+
+    let (tx, rx) = oneshot::channel();
+    let callback = Closure::once_into_js(move || {
+        _ = tx.send(());
+    });
+    let global = js_sys::global();
+    let set_timeout =
+        js_sys::Reflect::get(&global, &JsValue::from_str("setTimeout"))
+            .expect("setTimeout should exist in the global scope")
+            .unchecked_into::<js_sys::Function>();
+    set_timeout
+        .call2(
+            &global,
+            &callback,
+            &JsValue::from_f64(duration.as_millis() as f64),
+        )
+        .expect("setTimeout should not throw");
+    Box::pin(async move {
+        _ = rx.await;
+    })
+}
+
 /// Handles the case where `Executor::spawn` is called without an initialized executor.
 #[cold] // Less likely path
 #[inline(never)]
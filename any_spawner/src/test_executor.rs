@@ -0,0 +1,300 @@
+//! A deterministic executor for tests, with a virtual clock.
+//!
+//! Once installed with [`Executor::init_test_executor`](crate::Executor::init_test_executor),
+//! spawned tasks are not run in the background. Instead, they are queued on the thread that
+//! spawned them, and only make progress when that thread calls
+//! [`TestExecutor::run_until_stalled`] or [`TestExecutor::advance`]. Timers created with
+//! [`Executor::sleep`](crate::Executor::sleep) use a virtual clock, which only moves forward when
+//! it is advanced, so that timeouts and debounces can be tested instantly and deterministically.
+//!
+//! Each thread has its own task queue and clock, so tests that run in parallel on different
+//! threads do not interfere with one another.
+//!
+//! ```
+//! use any_spawner::{Executor, TestExecutor};
+//! use std::{
+//!     sync::{
+//!         atomic::{AtomicBool, Ordering},
+//!         Arc,
+//!     },
+//!     time::Duration,
+//! };
+//!
+//! _ = Executor::init_test_executor();
+//!
+//! let done = Arc::new(AtomicBool::new(false));
+//! Executor::spawn({
+//!     let done = Arc::clone(&done);
+//!     async move {
+//!         Executor::sleep(Duration::from_secs(10)).await;
+//!         done.store(true, Ordering::Relaxed);
+//!     }
+//! });
+//!
+//! // the task runs until it starts waiting for the timer
+//! TestExecutor::run_until_stalled();
+//! assert!(!done.load(Ordering::Relaxed));
+//!
+//! // advancing the virtual clock fires the timer and runs the task to completion
+//! TestExecutor::advance(Duration::from_secs(10));
+//! assert!(done.load(Ordering::Relaxed));
+//! assert_eq!(TestExecutor::now(), Duration::from_secs(10));
+//! ```
+
+use crate::{PinnedFuture, PinnedLocalFuture};
+use std::{
+    cell::{Cell, RefCell},
+    collections::{BTreeMap, HashMap, VecDeque},
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    task::{Context, Poll, Wake, Waker},
+    time::Duration,
+};
+
+thread_local! {
+    static STATE: State = State::default();
+}
+
+// Pending timers, ordered by deadline and then by creation order.
+type Timers = BTreeMap<(Duration, u64), Arc<Mutex<TimerState>>>;
+
+#[derive(Default)]
+struct State {
+    ready: Arc<Mutex<VecDeque<u64>>>,
+    tasks: RefCell<HashMap<u64, PinnedLocalFuture<()>>>,
+    next_task: Cell<u64>,
+    now: Cell<Duration>,
+    timers: RefCell<Timers>,
+    next_timer: Cell<u64>,
+    running: Cell<bool>,
+}
+
+#[derive(Default)]
+struct TimerState {
+    fired: bool,
+    waker: Option<Waker>,
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+struct TaskWaker {
+    id: u64,
+    ready: Arc<Mutex<VecDeque<u64>>>,
+}
+
+impl Wake for TaskWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        lock(&self.ready).push_back(self.id);
+    }
+}
+
+struct Sleep {
+    key: (Duration, u64),
+    timer: Arc<Mutex<TimerState>>,
+}
+
+impl Future for Sleep {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut timer = lock(&self.timer);
+        if timer.fired {
+            Poll::Ready(())
+        } else {
+            timer.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+impl Drop for Sleep {
+    fn drop(&mut self) {
+        // a timer that is no longer awaited does not count as pending, so it is removed, unless
+        // it has already fired (or this is not the thread that created it)
+        _ = STATE.try_with(|state| {
+            let Ok(mut timers) = state.timers.try_borrow_mut() else {
+                return;
+            };
+            if timers
+                .get(&self.key)
+                .is_some_and(|timer| Arc::ptr_eq(timer, &self.timer))
+            {
+                timers.remove(&self.key);
+            }
+        });
+    }
+}
+
+/// Controls the deterministic test executor installed by
+/// [`Executor::init_test_executor`](crate::Executor::init_test_executor).
+///
+/// All of these functions act on the task queue and virtual clock of the current thread.
+#[derive(Debug)]
+pub struct TestExecutor;
+
+impl TestExecutor {
+    pub(crate) fn spawn_local(fut: PinnedLocalFuture<()>) {
+        STATE.with(|state| {
+            let id = state.next_task.get();
+            state.next_task.set(id + 1);
+            state.tasks.borrow_mut().insert(id, fut);
+            lock(&state.ready).push_back(id);
+        });
+    }
+
+    pub(crate) fn sleep(duration: Duration) -> PinnedFuture<()> {
+        let timer = Arc::new(Mutex::new(TimerState::default()));
+        let key = STATE.with(|state| {
+            let seq = state.next_timer.get();
+            state.next_timer.set(seq + 1);
+            let deadline = state.now.get().saturating_add(duration);
+            state
+                .timers
+                .borrow_mut()
+                .insert((deadline, seq), Arc::clone(&timer));
+            (deadline, seq)
+        });
+        Box::pin(Sleep { key, timer })
+    }
+
+    /// Polls every task that is ready to make progress, including any tasks they spawn or wake,
+    /// until no task can make further progress without the clock being advanced (or some other
+    /// external event).
+    ///
+    /// Returns the number of times a task was polled. Calls made from inside a task that is
+    /// being run by this function do nothing, and return `0`.
+    pub fn run_until_stalled() -> usize {
+        STATE.with(|state| {
+            if state.running.replace(true) {
+                return 0;
+            }
+            let mut polls = 0;
+            loop {
+                Self::fire_timers(state, state.now.get());
+                let next = lock(&state.ready).pop_front();
+                let Some(id) = next else {
+                    break;
+                };
+                // the task may already have completed, or have been woken more than once
+                let Some(mut task) = state.tasks.borrow_mut().remove(&id)
+                else {
+                    continue;
+                };
+                let waker = Waker::from(Arc::new(TaskWaker {
+                    id,
+                    ready: Arc::clone(&state.ready),
+                }));
+                polls += 1;
+                if task
+                    .as_mut()
+                    .poll(&mut Context::from_waker(&waker))
+                    .is_pending()
+                {
+                    state.tasks.borrow_mut().insert(id, task);
+                }
+            }
+            state.running.set(false);
+            polls
+        })
+    }
+
+    /// Moves the virtual clock forward by the given duration.
+    ///
+    /// Timers fire in order of their deadlines. Each time a timer fires, the clock is set to its
+    /// deadline and all tasks are run until stalled, so that any timers they create in turn will
+    /// also fire if they fall within the duration.
+    pub fn advance(duration: Duration) {
+        let target = Self::now().saturating_add(duration);
+        Self::run_until_stalled();
+        while let Some(deadline) = Self::next_timer().filter(|d| *d <= target) {
+            STATE.with(|state| {
+                state.now.set(deadline);
+            });
+            Self::run_until_stalled();
+        }
+        STATE.with(|state| state.now.set(target));
+        Self::run_until_stalled();
+    }
+
+    /// Moves the virtual clock forward to the deadline of the next pending timer, and runs all
+    /// tasks until stalled.
+    ///
+    /// Returns `false` if there are no pending timers.
+    pub fn advance_to_next_timer() -> bool {
+        Self::run_until_stalled();
+        match Self::next_timer() {
+            Some(deadline) => {
+                Self::advance(deadline.saturating_sub(Self::now()));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the current time on the virtual clock, measured from when the clock started.
+    pub fn now() -> Duration {
+        STATE.with(|state| state.now.get())
+    }
+
+    /// Returns the deadline of the next timer that has not yet fired, if any. Timers that have
+    /// been dropped are not counted.
+    pub fn next_timer() -> Option<Duration> {
+        STATE.with(|state| {
+            state
+                .timers
+                .borrow()
+                .keys()
+                .next()
+                .map(|(deadline, _)| *deadline)
+        })
+    }
+
+    /// Returns the number of tasks that have been spawned, but have not yet completed.
+    pub fn pending_tasks() -> usize {
+        STATE.with(|state| state.tasks.borrow().len())
+    }
+
+    /// Drops all pending tasks and timers, and resets the virtual clock to zero.
+    pub fn reset() {
+        let (tasks, timers) = STATE.with(|state| {
+            lock(&state.ready).clear();
+            state.now.set(Duration::ZERO);
+            (state.tasks.take(), state.timers.take())
+        });
+        // dropped outside the borrow, in case dropping a task spawns another
+        drop(tasks);
+        drop(timers);
+    }
+
+    fn fire_timers(state: &State, now: Duration) {
+        loop {
+            let timer = {
+                let mut timers = state.timers.borrow_mut();
+                match timers.first_key_value() {
+                    Some(((deadline, _), _)) if *deadline <= now => {
+                        timers.pop_first().map(|(_, timer)| timer)
+                    }
+                    _ => None,
+                }
+            };
+            let Some(timer) = timer else {
+                break;
+            };
+            let waker = {
+                let mut timer = lock(&timer);
+                timer.fired = true;
+                timer.waker.take()
+            };
+            if let Some(waker) = waker {
+                waker.wake();
+            }
+        }
+    }
+}
@@ -0,0 +1,118 @@
+//! The timer used by executors that do not have one of their own.
+//!
+//! Every sleep is registered with a single background thread, which waits until the earliest
+//! deadline and wakes each sleep whose deadline has passed.
+
+use crate::PinnedFuture;
+use futures::channel::oneshot;
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::BinaryHeap,
+    sync::{Condvar, Mutex, OnceLock, PoisonError},
+    time::{Duration, Instant},
+};
+
+static TIMER: OnceLock<Timer> = OnceLock::new();
+
+#[derive(Default)]
+struct Timer {
+    deadlines: Mutex<BinaryHeap<Reverse<Deadline>>>,
+    // notified when a deadline is added that is earlier than all the others
+    earlier_deadline: Condvar,
+}
+
+struct Deadline {
+    at: Instant,
+    tx: oneshot::Sender<()>,
+}
+
+impl PartialEq for Deadline {
+    fn eq(&self, other: &Self) -> bool {
+        self.at == other.at
+    }
+}
+
+impl Eq for Deadline {}
+
+impl PartialOrd for Deadline {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Deadline {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.at.cmp(&other.at)
+    }
+}
+
+/// Returns a future that resolves once `duration` has elapsed, as measured by the shared timer
+/// thread, which is started the first time this is called.
+pub(crate) fn sleep(duration: Duration) -> PinnedFuture<()> {
+    // a deadline that cannot be represented is never reached
+    let Some(at) = Instant::now().checked_add(duration) else {
+        return Box::pin(futures::future::pending());
+    };
+    let (tx, rx) = oneshot::channel();
+
+    let timer = TIMER.get_or_init(|| {
+        std::thread::Builder::new()
+            .name("any_spawner-timer".into())
+            .spawn(|| TIMER.wait().run())
+            .expect("failed to spawn the timer thread");
+        Timer::default()
+    });
+    let mut deadlines = timer
+        .deadlines
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    let is_earliest = deadlines
+        .peek()
+        .is_none_or(|Reverse(earliest)| at < earliest.at);
+    deadlines.push(Reverse(Deadline { at, tx }));
+    drop(deadlines);
+    if is_earliest {
+        timer.earlier_deadline.notify_one();
+    }
+
+    Box::pin(async move {
+        _ = rx.await;
+    })
+}
+
+impl Timer {
+    fn run(&self) -> ! {
+        let mut deadlines = self
+            .deadlines
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        loop {
+            // sleeps that were dropped before their deadline no longer need to be woken
+            deadlines.retain(|Reverse(deadline)| !deadline.tx.is_canceled());
+
+            let now = Instant::now();
+            while deadlines
+                .peek()
+                .is_some_and(|Reverse(earliest)| earliest.at <= now)
+            {
+                if let Some(Reverse(deadline)) = deadlines.pop() {
+                    _ = deadline.tx.send(());
+                }
+            }
+
+            deadlines = match deadlines.peek() {
+                Some(Reverse(earliest)) => {
+                    let timeout = earliest.at - now;
+                    self.earlier_deadline
+                        .wait_timeout(deadlines, timeout)
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .0
+                }
+                None => self
+                    .earlier_deadline
+                    .wait(deadlines)
+                    .unwrap_or_else(PoisonError::into_inner),
+            };
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use any_spawner::{CustomExecutor, Executor, PinnedFuture, PinnedLocalFuture};
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+#[test]
+fn test_custom_executor_sleep() {
+    // A custom executor whose timer resolves immediately, recording the requested durations
+    struct TestExecutor {
+        slept_ms: Arc<AtomicU64>,
+    }
+
+    impl CustomExecutor for TestExecutor {
+        fn spawn(&self, fut: PinnedFuture<()>) {
+            futures::executor::block_on(fut);
+        }
+
+        fn spawn_local(&self, fut: PinnedLocalFuture<()>) {
+            futures::executor::block_on(fut);
+        }
+
+        fn poll_local(&self) {}
+
+        fn sleep(&self, duration: Duration) -> PinnedFuture<()> {
+            self.slept_ms
+                .fetch_add(duration.as_millis() as u64, Ordering::SeqCst);
+            Box::pin(async {})
+        }
+    }
+
+    let slept_ms = Arc::new(AtomicU64::new(0));
+    Executor::init_custom_executor(TestExecutor {
+        slept_ms: slept_ms.clone(),
+    })
+    .expect("Failed to initialize custom executor");
+
+    futures::executor::block_on(async {
+        Executor::sleep(Duration::from_secs(3600)).await;
+    });
+    assert_eq!(slept_ms.load(Ordering::SeqCst), 3_600_000);
+}
@@ -0,0 +1,115 @@
+#![cfg(feature = "test-executor")]
+
+use any_spawner::{Executor, TestExecutor};
+use futures::StreamExt;
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+fn init() {
+    _ = Executor::init_test_executor();
+}
+
+#[test]
+fn tasks_only_run_when_driven() {
+    init();
+
+    let log = Arc::new(Mutex::new(Vec::new()));
+    for n in 0..3 {
+        let log = Arc::clone(&log);
+        Executor::spawn(async move {
+            log.lock().unwrap().push(n);
+        });
+    }
+    assert!(log.lock().unwrap().is_empty());
+    assert_eq!(TestExecutor::pending_tasks(), 3);
+
+    TestExecutor::run_until_stalled();
+    assert_eq!(*log.lock().unwrap(), vec![0, 1, 2]);
+    assert_eq!(TestExecutor::pending_tasks(), 0);
+}
+
+#[test]
+fn timers_fire_in_virtual_time() {
+    init();
+
+    let log = Arc::new(Mutex::new(Vec::new()));
+    for ms in [30, 10, 20] {
+        let log = Arc::clone(&log);
+        Executor::spawn_local(async move {
+            Executor::sleep(Duration::from_millis(ms)).await;
+            log.lock().unwrap().push((ms, TestExecutor::now()));
+        });
+    }
+
+    TestExecutor::advance(Duration::from_millis(15));
+    assert_eq!(*log.lock().unwrap(), vec![(10, Duration::from_millis(10))]);
+    assert_eq!(TestExecutor::next_timer(), Some(Duration::from_millis(20)));
+
+    assert!(TestExecutor::advance_to_next_timer());
+    assert!(TestExecutor::advance_to_next_timer());
+    assert!(!TestExecutor::advance_to_next_timer());
+    assert_eq!(
+        *log.lock().unwrap(),
+        vec![
+            (10, Duration::from_millis(10)),
+            (20, Duration::from_millis(20)),
+            (30, Duration::from_millis(30))
+        ]
+    );
+}
+
+#[test]
+fn dropped_timers_are_not_pending() {
+    init();
+
+    let sleep = Executor::sleep(Duration::from_secs(1));
+    assert_eq!(TestExecutor::next_timer(), Some(Duration::from_secs(1)));
+    drop(sleep);
+    assert_eq!(TestExecutor::next_timer(), None);
+
+    // e.g., the timer of a timeout whose future finished first
+    Executor::spawn(async {
+        _ = Executor::timeout(Duration::from_secs(5), async {}).await;
+    });
+    TestExecutor::run_until_stalled();
+    assert_eq!(TestExecutor::pending_tasks(), 0);
+    assert_eq!(TestExecutor::next_timer(), None);
+    assert!(!TestExecutor::advance_to_next_timer());
+}
+
+#[test]
+fn timeout_and_interval_use_virtual_time() {
+    init();
+
+    let result = Arc::new(Mutex::new(None));
+    Executor::spawn({
+        let result = Arc::clone(&result);
+        async move {
+            let slow = Executor::sleep(Duration::from_secs(60));
+            let outcome = Executor::timeout(Duration::from_secs(5), slow).await;
+            *result.lock().unwrap() = Some(outcome.is_err());
+        }
+    });
+
+    let ticks = Arc::new(Mutex::new(0));
+    Executor::spawn({
+        let ticks = Arc::clone(&ticks);
+        async move {
+            let mut interval =
+                std::pin::pin!(Executor::interval(Duration::from_secs(1)));
+            while interval.next().await.is_some() {
+                *ticks.lock().unwrap() += 1;
+            }
+        }
+    });
+
+    TestExecutor::advance(Duration::from_secs(5));
+    assert_eq!(*result.lock().unwrap(), Some(true));
+    assert_eq!(*ticks.lock().unwrap(), 5);
+
+    TestExecutor::reset();
+    assert_eq!(TestExecutor::pending_tasks(), 0);
+    assert_eq!(TestExecutor::now(), Duration::ZERO);
+}
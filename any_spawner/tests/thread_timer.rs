@@ -0,0 +1,37 @@
+use any_spawner::Executor;
+use futures::future::join_all;
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+// without an executor, sleeps wait on the shared timer thread
+#[test]
+fn sleeps_wake_in_order_of_their_deadlines() {
+    let woken = Arc::new(Mutex::new(Vec::new()));
+    let start = Instant::now();
+    let sleeps = [60, 20, 40].map(|ms| {
+        let woken = Arc::clone(&woken);
+        async move {
+            Executor::sleep(Duration::from_millis(ms)).await;
+            woken.lock().unwrap().push(ms);
+        }
+    });
+    futures::executor::block_on(join_all(sleeps));
+
+    assert_eq!(*woken.lock().unwrap(), [20, 40, 60]);
+    assert!(start.elapsed() >= Duration::from_millis(60));
+}
+
+#[test]
+fn dropped_sleeps_do_not_hold_up_later_ones() {
+    let start = Instant::now();
+    drop(Executor::sleep(Duration::from_secs(3600)));
+    let value = futures::executor::block_on(Executor::timeout(
+        Duration::from_millis(10),
+        Executor::sleep(Duration::from_secs(3600)),
+    ));
+    assert!(value.is_err());
+    futures::executor::block_on(Executor::sleep(Duration::from_millis(10)));
+    assert!(start.elapsed() < Duration::from_secs(60));
+}
@@ -0,0 +1,36 @@
+#![cfg(feature = "tokio")]
+
+use any_spawner::{Elapsed, Executor};
+use futures::StreamExt;
+use std::time::Duration;
+
+#[tokio::test]
+async fn test_tokio_timers() {
+    Executor::init_tokio().expect("Failed to initialize tokio executor");
+
+    let start = tokio::time::Instant::now();
+    Executor::sleep(Duration::from_millis(20)).await;
+    assert!(start.elapsed() >= Duration::from_millis(20));
+
+    // interval ticks once per period
+    let start = tokio::time::Instant::now();
+    let ticks = Executor::interval(Duration::from_millis(10))
+        .take(3)
+        .collect::<Vec<_>>()
+        .await;
+    assert_eq!(ticks.len(), 3);
+    assert!(start.elapsed() >= Duration::from_millis(30));
+
+    // timeout resolves with the value if the future finishes in time...
+    let value =
+        Executor::timeout(Duration::from_millis(10), async { 42 }).await;
+    assert_eq!(value, Ok(42));
+
+    // ...and with an error if it does not
+    let value = Executor::timeout(
+        Duration::from_millis(10),
+        Executor::sleep(Duration::from_secs(5)),
+    )
+    .await;
+    assert_eq!(value, Err(Elapsed));
+}
@@ -0,0 +1,13 @@
+#![cfg(feature = "tokio")]
+
+use any_spawner::Executor;
+use std::time::{Duration, Instant};
+
+#[test]
+fn sleeping_outside_a_runtime_falls_back_to_the_timer_thread() {
+    Executor::init_tokio().expect("Failed to initialize tokio executor");
+
+    let start = Instant::now();
+    futures::executor::block_on(Executor::sleep(Duration::from_millis(20)));
+    assert!(start.elapsed() >= Duration::from_millis(20));
+}
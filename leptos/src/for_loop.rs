@@ -2,12 +2,12 @@ use crate::into_view::IntoView;
 use leptos_macro::component;
 use reactive_graph::{
     owner::Owner,
-    signal::{ArcRwSignal, ReadSignal},
+    signal::{ArcRwSignal, ArcSignalVec, ReadSignal},
     traits::Set,
 };
 use std::hash::Hash;
 use tachys::{
-    reactive_graph::OwnedView,
+    reactive_graph::{signal_vec::signal_vec, OwnedView},
     view::keyed::{keyed, SerializableKey},
 };
 
@@ -222,6 +222,57 @@ where
     move || keyed(each(), key.clone(), children.clone())
 }
 
+/// Iterates over the items in a [`SignalVec`](reactive_graph::signal::SignalVec) or
+/// [`ArcSignalVec`], and displays them.
+///
+/// Unlike [`For`], this does not need a key function, and never compares the old and new lists.
+/// Instead, each change made to the list (like an insert, removal, or move) is applied directly
+/// to the rendered rows, so changing one item only creates, removes, or moves one row. Replacing
+/// the value at an index re-renders the row at that index.
+///
+/// ```
+/// # use leptos::prelude::*;
+///
+/// #[component]
+/// fn Todos() -> impl IntoView {
+///   let todos = SignalVec::new(vec!["Wake up".to_string()]);
+///
+///   view! {
+///     <button on:click=move |_| todos.insert(0, "Make coffee".to_string())>
+///       "Add"
+///     </button>
+///     <ul>
+///       <ForSignalVec each=todos let(todo)>
+///         <li>{todo}</li>
+///       </ForSignalVec>
+///     </ul>
+///   }
+/// }
+/// ```
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip_all))]
+#[component]
+pub fn ForSignalVec<T, EF, N>(
+    /// The list over which the component should iterate.
+    #[prop(into)]
+    each: ArcSignalVec<T>,
+    /// A function that takes the item, and returns the view that will be displayed for each item.
+    children: EF,
+) -> impl IntoView
+where
+    EF: Fn(T) -> N + Send + 'static,
+    N: IntoView + 'static,
+    T: Clone + Send + Sync + 'static,
+{
+    // as in `For`, each row gets its own owner, which is a child of the owner of the component,
+    // so that it is only disposed when that row is removed or replaced
+    let parent = Owner::current().expect("no reactive owner");
+    signal_vec(each, move |child| {
+        let owner = parent.with(Owner::new);
+        let view = owner.with(|| children(child));
+        OwnedView::new_with_owner(view, owner)
+    })
+}
+
 /*
 #[cfg(test)]
 mod tests {
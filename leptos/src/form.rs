@@ -1,6 +1,8 @@
 use crate::{children::Children, component, prelude::*, IntoView};
 use leptos_dom::helpers::window;
 use leptos_server::{ServerAction, ServerMultiAction};
+#[cfg(feature = "stores")]
+use reactive_stores::StoreForm;
 use serde::de::DeserializeOwned;
 use server_fn::{
     client::Client,
@@ -9,6 +11,8 @@ use server_fn::{
     request::ClientReq,
     Http, ServerFn,
 };
+#[cfg(feature = "stores")]
+use std::fmt::Display;
 use tachys::{
     either::Either,
     html::{
@@ -215,6 +219,105 @@ where
     }
 }
 
+/// Returns a `submit` handler that validates a [`StoreForm`] before an [`ActionForm`] is
+/// submitted.
+///
+/// Add it to the form with `on:submit:capture`, so that it runs before the action is dispatched.
+/// If any field of the form is invalid, the submission is cancelled, and every field is marked
+/// as touched so that its error can be shown. The submission is also cancelled while the
+/// asynchronous validators of a field have not finished for its current value.
+///
+/// This requires the `stores` feature.
+///
+/// ```rust
+/// # use leptos::prelude::*;
+/// use leptos::form::{track_server_action, validate_on_submit, ActionForm};
+/// use reactive_stores::{Store, StoreForm};
+///
+/// #[derive(Store, Default)]
+/// struct Signup {
+///     email: String,
+/// }
+///
+/// #[component]
+/// fn SignupForm() -> impl IntoView {
+///     let form = StoreForm::new(Signup::default());
+///     let email = form.field(form.store().email()).validate(|email| {
+///         if email.contains('@') {
+///             Ok(())
+///         } else {
+///             Err("Please enter a valid email address.".to_string())
+///         }
+///     });
+///     let error = email.error();
+///     let submit_error = form.submit_error();
+///
+///     let action = ServerAction::<SignUp>::new();
+///     track_server_action(form.clone(), action);
+///
+///     view! {
+///       <ActionForm action on:submit:capture=validate_on_submit(form.clone())>
+///         <input
+///           type="email"
+///           name="email"
+///           bind:value=email.clone()
+///           on:blur=move |_| email.touch()
+///         />
+///         <p>{move || error.get()}</p>
+///         <p>{move || submit_error.get()}</p>
+///         <input type="submit"/>
+///       </ActionForm>
+///     }
+/// }
+///
+/// #[server]
+/// async fn sign_up(email: String) -> Result<(), ServerFnError> {
+///     Ok(())
+/// }
+/// ```
+#[cfg(feature = "stores")]
+pub fn validate_on_submit<T>(
+    form: StoreForm<T>,
+) -> impl Fn(SubmitEvent) + Clone + 'static
+where
+    T: Send + Sync + 'static,
+{
+    move |ev: SubmitEvent| {
+        if !form.validate() {
+            ev.prevent_default();
+        }
+    }
+}
+
+/// Keeps the submission state of a [`StoreForm`] in sync with a [`ServerAction`].
+///
+/// The form is [submitting](StoreForm::is_submitting) while the action is pending, and an error
+/// returned by the server function becomes the [submit error](StoreForm::submit_error) of the
+/// form.
+///
+/// This requires the `stores` feature.
+#[cfg(feature = "stores")]
+pub fn track_server_action<T, ServFn>(
+    form: StoreForm<T>,
+    action: ServerAction<ServFn>,
+) where
+    T: Send + Sync + 'static,
+    ServFn: ServerFn + Clone + Send + Sync + 'static,
+    ServFn::Output: Send + Sync + 'static,
+    ServFn::Error: Display + Send + Sync + 'static,
+{
+    let pending = action.pending();
+    let value = action.value();
+    Effect::new(move |_| {
+        form.set_submitting(pending.get());
+        let error = value.with(|value| match value {
+            Some(Err(e)) => Some(e.to_string()),
+            _ => None,
+        });
+        form.set_submit_error(error);
+    });
+}
+
 /// Resolves a redirect location to an (absolute) URL.
 pub(crate) fn resolve_redirect_url(loc: &str) -> Option<web_sys::Url> {
     let origin = match window().location().origin() {
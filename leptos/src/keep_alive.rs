@@ -0,0 +1,529 @@
+use crate::{children::TypedChildren, IntoView};
+use leptos_macro::component;
+use reactive_graph::{
+    computed::ArcMemo,
+    effect::RenderEffect,
+    graph::untrack,
+    owner::Owner,
+    traits::{Get, GetUntracked},
+};
+use tachys::{
+    html::attribute::{any_attribute::AnyAttribute, Attribute},
+    hydration::Cursor,
+    reactive_graph::OwnedView,
+    renderer::types::{Element, Node, Placeholder},
+    ssr::StreamBuilder,
+    view::{
+        add_attr::AddAnyAttr, Mountable, Position, PositionState, Render,
+        RenderHtml,
+    },
+};
+
+/// Shows its children whenever the condition in the `when` closure returns `true`, like
+/// [`Show`](crate::control_flow::Show), but keeps them alive while they are hidden.
+///
+/// The children are created the first time they are shown. When they are hidden, their DOM is
+/// detached and their effects and resources are paused with [`Owner::pause`], but their state is
+/// kept. When they are shown again, their DOM is reattached and their effects are resumed with
+/// [`Owner::resume_and_update`], without running the children again: any effects whose sources
+/// changed while they were hidden run once to catch up.
+///
+/// This is useful for tabs and other views that are switched often, and should not lose their
+/// state when they are switched away from.
+///
+/// ```rust
+/// # use leptos::prelude::*;
+/// # #[component]
+/// # pub fn App() -> impl IntoView {
+/// let tab = RwSignal::new(0);
+///
+/// view! {
+///     <button on:click=move |_| tab.set(0)>"Inbox"</button>
+///     <button on:click=move |_| tab.set(1)>"Drafts"</button>
+///     <KeepAlive when=move || tab.get() == 0>
+///         // the input keeps its value while another tab is shown
+///         <input placeholder="Search the inbox"/>
+///     </KeepAlive>
+///     <KeepAlive when=move || tab.get() == 1>
+///         <p>"No drafts"</p>
+///     </KeepAlive>
+/// }
+/// # }
+/// ```
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip_all))]
+#[component]
+pub fn KeepAlive<W, C>(
+    /// The children will be shown whenever the condition in the `when` closure returns `true`,
+    /// and kept alive while it returns `false`.
+    children: TypedChildren<C>,
+    /// A closure that returns a bool that determines whether the children are shown.
+    when: W,
+) -> impl IntoView
+where
+    W: Fn() -> bool + Send + Sync + 'static,
+    C: IntoView + 'static,
+{
+    let children = children.into_inner();
+    KeepAliveView {
+        owner: Owner::new(),
+        when: ArcMemo::new(move |_| when()),
+        children: Box::new(move || children().into_inner()),
+    }
+}
+
+struct KeepAliveView<C> {
+    // the children run under this owner, so that they can be paused and resumed together
+    owner: Owner,
+    when: ArcMemo<bool>,
+    children: Box<dyn FnOnce() -> C + Send>,
+}
+
+struct KeepAliveState<S, P = Placeholder> {
+    owner: Owner,
+    // created the first time the children are shown, and kept while they are hidden
+    children: Option<S>,
+    // always mounted after the children, to mark where they are reattached
+    placeholder: P,
+    shown: bool,
+}
+
+impl<S, P> KeepAliveState<S, P>
+where
+    S: Mountable,
+    P: Mountable,
+{
+    fn set_shown(
+        &mut self,
+        shown: bool,
+        build_children: &mut Option<impl FnOnce() -> S>,
+    ) {
+        if shown == self.shown {
+            return;
+        }
+        self.shown = shown;
+        if shown {
+            match &mut self.children {
+                Some(_) => self.owner.resume_and_update(),
+                None => self.children = build_children.take().map(|f| f()),
+            }
+            if let Some(children) = &mut self.children {
+                self.placeholder.insert_before_this(children);
+            }
+        } else {
+            if let Some(children) = &mut self.children {
+                children.unmount();
+            }
+            self.owner.pause();
+        }
+    }
+}
+
+impl<S, P> Mountable for KeepAliveState<S, P>
+where
+    S: Mountable,
+    P: Mountable,
+{
+    fn unmount(&mut self) {
+        if self.shown {
+            if let Some(children) = &mut self.children {
+                children.unmount();
+            }
+        }
+        self.placeholder.unmount();
+    }
+
+    fn mount(&mut self, parent: &Element, marker: Option<&Node>) {
+        if self.shown {
+            if let Some(children) = &mut self.children {
+                children.mount(parent, marker);
+            }
+        }
+        self.placeholder.mount(parent, marker);
+    }
+
+    fn insert_before_this(&self, child: &mut dyn Mountable) -> bool {
+        match &self.children {
+            Some(children) if self.shown => children.insert_before_this(child),
+            _ => self.placeholder.insert_before_this(child),
+        }
+    }
+
+    fn elements(&self) -> Vec<Element> {
+        match &self.children {
+            Some(children) if self.shown => children.elements(),
+            _ => vec![],
+        }
+    }
+}
+
+/// Creates the effect that shows and hides the children, building them under `owner` the first
+/// time they are shown.
+fn keep_alive_effect<S, P>(
+    owner: Owner,
+    when: ArcMemo<bool>,
+    children: impl FnOnce() -> S + 'static,
+    placeholder: impl FnOnce() -> P + 'static,
+) -> RenderEffect<KeepAliveState<S, P>>
+where
+    S: Mountable + 'static,
+    P: Mountable + 'static,
+{
+    let mut build_children = Some({
+        let owner = owner.clone();
+        move || untrack(|| owner.with(children))
+    });
+    let mut placeholder = Some(placeholder);
+    RenderEffect::new(move |prev: Option<KeepAliveState<S, P>>| {
+        let shown = when.get();
+        match prev {
+            Some(mut state) => {
+                state.set_shown(shown, &mut build_children);
+                state
+            }
+            None => KeepAliveState {
+                owner: owner.clone(),
+                children: if shown {
+                    build_children.take().map(|f| f())
+                } else {
+                    None
+                },
+                placeholder: placeholder.take().expect("built once")(),
+                shown,
+            },
+        }
+    })
+}
+
+impl<C> Render for KeepAliveView<C>
+where
+    C: Render + 'static,
+{
+    type State = RenderEffect<KeepAliveState<C::State>>;
+
+    fn build(self) -> Self::State {
+        let KeepAliveView {
+            owner,
+            when,
+            children,
+        } = self;
+        keep_alive_effect(
+            owner,
+            when,
+            move || children().build(),
+            || Render::build(()),
+        )
+    }
+
+    fn rebuild(self, state: &mut Self::State) {
+        let new = self.build();
+        let mut old = std::mem::replace(state, new);
+        old.insert_before_this(state);
+        old.unmount();
+    }
+}
+
+impl<C> AddAnyAttr for KeepAliveView<C>
+where
+    C: RenderHtml + 'static,
+{
+    type Output<SomeNewAttr: Attribute> =
+        KeepAliveView<C::Output<SomeNewAttr::CloneableOwned>>;
+
+    fn add_any_attr<NewAttr: Attribute>(
+        self,
+        attr: NewAttr,
+    ) -> Self::Output<NewAttr>
+    where
+        Self::Output<NewAttr>: RenderHtml,
+    {
+        let KeepAliveView {
+            owner,
+            when,
+            children,
+        } = self;
+        let attr = attr.into_cloneable_owned();
+        KeepAliveView {
+            owner,
+            when,
+            children: Box::new(move || children().add_any_attr(attr)),
+        }
+    }
+}
+
+impl<C> RenderHtml for KeepAliveView<C>
+where
+    C: RenderHtml + 'static,
+{
+    type AsyncOutput = Self;
+    type Owned = Self;
+
+    const MIN_LENGTH: usize = 0;
+
+    fn dry_resolve(&mut self) {}
+
+    async fn resolve(self) -> Self::AsyncOutput {
+        self
+    }
+
+    fn to_html_with_buf(
+        self,
+        buf: &mut String,
+        position: &mut Position,
+        escape: bool,
+        mark_branches: bool,
+        extra_attrs: Vec<AnyAttribute>,
+    ) {
+        // only the children that are shown are rendered on the server
+        if self.when.get_untracked() {
+            let children = self.owner.with(self.children);
+            OwnedView::new_with_owner(children, self.owner).to_html_with_buf(
+                buf,
+                position,
+                escape,
+                mark_branches,
+                extra_attrs,
+            );
+        }
+        ().to_html_with_buf(buf, position, escape, mark_branches, vec![]);
+    }
+
+    fn to_html_async_with_buf<const OUT_OF_ORDER: bool>(
+        self,
+        buf: &mut StreamBuilder,
+        position: &mut Position,
+        escape: bool,
+        mark_branches: bool,
+        extra_attrs: Vec<AnyAttribute>,
+    ) where
+        Self: Sized,
+    {
+        if self.when.get_untracked() {
+            let children = self.owner.with(self.children);
+            OwnedView::new_with_owner(children, self.owner)
+                .to_html_async_with_buf::<OUT_OF_ORDER>(
+                    buf,
+                    position,
+                    escape,
+                    mark_branches,
+                    extra_attrs,
+                );
+        }
+        ().to_html_async_with_buf::<OUT_OF_ORDER>(
+            buf,
+            position,
+            escape,
+            mark_branches,
+            vec![],
+        );
+    }
+
+    fn hydrate<const FROM_SERVER: bool>(
+        self,
+        cursor: &Cursor,
+        position: &PositionState,
+    ) -> Self::State {
+        let KeepAliveView {
+            owner,
+            when,
+            children,
+        } = self;
+        let cursor = cursor.to_owned();
+        let position = position.to_owned();
+        let mut children = Some(children);
+        let mut build_children: Option<Box<dyn FnOnce() -> C::State>> = None;
+        RenderEffect::new(move |prev: Option<KeepAliveState<C::State>>| {
+            let shown = when.get();
+            match prev {
+                Some(mut state) => {
+                    state.set_shown(shown, &mut build_children);
+                    state
+                }
+                None => {
+                    let children = children.take().expect("hydrated once");
+                    let hydrated = if shown {
+                        Some(untrack(|| {
+                            owner.with(|| {
+                                children()
+                                    .hydrate::<FROM_SERVER>(&cursor, &position)
+                            })
+                        }))
+                    } else {
+                        // the children were not rendered on the server, so they are built
+                        // the first time they are shown
+                        let owner = owner.clone();
+                        build_children = Some(Box::new(move || {
+                            untrack(|| owner.with(|| children().build()))
+                        }));
+                        None
+                    };
+                    KeepAliveState {
+                        owner: owner.clone(),
+                        children: hydrated,
+                        placeholder: RenderHtml::hydrate::<FROM_SERVER>(
+                            (),
+                            &cursor,
+                            &position,
+                        ),
+                        shown,
+                    }
+                }
+            }
+        })
+    }
+
+    fn into_owned(self) -> Self::Owned {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::keep_alive_effect;
+    use reactive_graph::{
+        computed::ArcMemo,
+        effect::RenderEffect,
+        owner::Owner,
+        signal::ArcRwSignal,
+        traits::{Get, Set},
+    };
+    use std::sync::{Arc, Mutex};
+    use tachys::{
+        renderer::types::{Element, Node},
+        view::Mountable,
+    };
+
+    type Log = Arc<Mutex<Vec<&'static str>>>;
+
+    struct Children {
+        log: Log,
+        _effect: RenderEffect<()>,
+    }
+
+    impl Mountable for Children {
+        fn unmount(&mut self) {
+            self.log.lock().unwrap().push("unmount children");
+        }
+
+        fn mount(&mut self, _parent: &Element, _marker: Option<&Node>) {
+            self.log.lock().unwrap().push("mount children");
+        }
+
+        fn insert_before_this(&self, _child: &mut dyn Mountable) -> bool {
+            false
+        }
+
+        fn elements(&self) -> Vec<Element> {
+            vec![]
+        }
+    }
+
+    struct Placeholder {
+        log: Log,
+    }
+
+    impl Mountable for Placeholder {
+        fn unmount(&mut self) {}
+
+        fn mount(&mut self, _parent: &Element, _marker: Option<&Node>) {}
+
+        fn insert_before_this(&self, _child: &mut dyn Mountable) -> bool {
+            self.log.lock().unwrap().push("insert children");
+            true
+        }
+
+        fn elements(&self) -> Vec<Element> {
+            vec![]
+        }
+    }
+
+    async fn tick() {
+        tokio::time::sleep(std::time::Duration::from_micros(1)).await;
+    }
+
+    #[tokio::test]
+    async fn keep_alive_pauses_hidden_children_and_resumes_them() {
+        _ = any_spawner::Executor::init_tokio();
+
+        tokio::task::LocalSet::new()
+            .run_until(async {
+                let owner = Owner::new();
+                owner.set();
+
+                let log = Log::default();
+                let builds = Arc::new(Mutex::new(0));
+                let effect_runs = Arc::new(Mutex::new(0));
+                let source = ArcRwSignal::new(0);
+                let shown = ArcRwSignal::new(false);
+
+                let _effect = keep_alive_effect(
+                    Owner::new(),
+                    ArcMemo::new({
+                        let shown = shown.clone();
+                        move |_| shown.get()
+                    }),
+                    {
+                        let log = Arc::clone(&log);
+                        let builds = Arc::clone(&builds);
+                        let effect_runs = Arc::clone(&effect_runs);
+                        let source = source.clone();
+                        move || {
+                            *builds.lock().unwrap() += 1;
+                            Children {
+                                log,
+                                _effect: RenderEffect::new(move |_| {
+                                    source.get();
+                                    *effect_runs.lock().unwrap() += 1;
+                                }),
+                            }
+                        }
+                    },
+                    {
+                        let log = Arc::clone(&log);
+                        move || Placeholder { log }
+                    },
+                );
+
+                // the children are not built until they are shown
+                tick().await;
+                assert_eq!(*builds.lock().unwrap(), 0);
+
+                shown.set(true);
+                tick().await;
+                assert_eq!(*builds.lock().unwrap(), 1);
+                assert_eq!(*effect_runs.lock().unwrap(), 1);
+                assert_eq!(*log.lock().unwrap(), ["insert children"]);
+
+                // while hidden, the children are detached and their effects do not run
+                shown.set(false);
+                tick().await;
+                source.set(1);
+                tick().await;
+                source.set(2);
+                tick().await;
+                assert_eq!(*effect_runs.lock().unwrap(), 1);
+                assert_eq!(
+                    *log.lock().unwrap(),
+                    ["insert children", "unmount children"]
+                );
+
+                // showing them again reattaches the same children, and catches their effects up once
+                shown.set(true);
+                tick().await;
+                assert_eq!(*builds.lock().unwrap(), 1);
+                assert_eq!(*effect_runs.lock().unwrap(), 2);
+                assert_eq!(
+                    *log.lock().unwrap(),
+                    ["insert children", "unmount children", "insert children"]
+                );
+
+                shown.set(false);
+                tick().await;
+                shown.set(true);
+                tick().await;
+                assert_eq!(*builds.lock().unwrap(), 1);
+                assert_eq!(*effect_runs.lock().unwrap(), 2);
+                assert_eq!(log.lock().unwrap().len(), 5);
+            })
+            .await;
+    }
+}
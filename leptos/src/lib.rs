@@ -225,12 +225,14 @@ pub mod error {
 /// Control-flow components like `<Show>`, `<For>`, and `<Await>`.
 pub mod control_flow {
     pub use crate::{
-        animated_show::*, await_::*, for_loop::*, show::*, show_let::*,
+        animated_show::*, await_::*, for_loop::*, keep_alive::*, show::*,
+        show_let::*,
     };
 }
 mod animated_show;
 mod await_;
 mod for_loop;
+mod keep_alive;
 mod show;
 mod show_let;
 
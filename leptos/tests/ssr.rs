@@ -205,3 +205,57 @@ fn ssr_option() {
 
     assert_eq!(rendered.to_html(), "<option></option>");
 }
+
+#[cfg(feature = "ssr")]
+#[test]
+fn ssr_for_signal_vec() {
+    use leptos::prelude::*;
+
+    let owner = Owner::new();
+    owner.set();
+
+    let todos = SignalVec::new(vec!["Wake up".to_string()]);
+    todos.insert(0, "Make coffee".to_string());
+    let rendered: View<HtmlElement<_, _, _>> = view! {
+        <ul>
+            <ForSignalVec each=todos let(todo)>
+                <li>{todo}</li>
+            </ForSignalVec>
+        </ul>
+    };
+
+    assert_eq!(
+        rendered.to_html(),
+        "<ul><li>Make coffee</li><li>Wake up</li><!></ul>"
+    );
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn ssr_keep_alive_scopes_context_to_its_children() {
+    use leptos::prelude::*;
+
+    #[derive(Clone)]
+    struct Tab(&'static str);
+
+    let owner = Owner::new();
+    owner.set();
+
+    let rendered: View<HtmlElement<_, _, _>> = view! {
+        <div>
+            <KeepAlive when=|| true>
+                {
+                    provide_context(Tab("inbox"));
+                    view! { <p>{use_context::<Tab>().map(|tab| tab.0)}</p> }
+                }
+            </KeepAlive>
+            <span>{move || use_context::<Tab>().map(|tab| tab.0)}</span>
+        </div>
+    };
+
+    // the context provided inside `<KeepAlive>` stays with its children
+    assert_eq!(
+        rendered.to_html(),
+        "<div><p>inbox</p><!><span><!></span></div>"
+    );
+}
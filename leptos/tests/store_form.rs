@@ -0,0 +1,59 @@
+#![cfg(feature = "stores")]
+
+use any_spawner::Executor;
+use leptos::{form::track_server_action, prelude::*};
+use reactive_stores::{Store, StoreForm};
+
+#[derive(Store, Default)]
+struct Signup {
+    email: String,
+}
+
+#[server]
+async fn sign_up(email: String) -> Result<(), ServerFnError> {
+    _ = email;
+    Ok(())
+}
+
+async fn tick() {
+    tokio::time::sleep(std::time::Duration::from_micros(1)).await;
+}
+
+#[tokio::test]
+async fn server_action_errors_become_the_submit_error() {
+    _ = Executor::init_tokio();
+    let owner = Owner::new();
+    owner.set();
+
+    tokio::task::LocalSet::new()
+        .run_until(async {
+            let form = StoreForm::new(Signup::default());
+            let email = form.field(form.store().email());
+            let action = ServerAction::<SignUp>::new();
+            track_server_action(form.clone(), action);
+            tick().await;
+            assert!(!form.is_submitting());
+            assert_eq!(form.submit_error().get_untracked(), None);
+
+            action
+                .value()
+                .set(Some(Err(ServerFnError::new("email is taken"))));
+            tick().await;
+            assert_eq!(
+                form.submit_error().get_untracked(),
+                Some(
+                    "error running server function: email is taken".to_string()
+                )
+            );
+
+            // fixing the field doesn't clear the error until the action resolves again
+            email.set("alice@example.com".to_string());
+            assert_eq!(email.get_untracked(), "alice@example.com");
+            assert!(form.submit_error().get_untracked().is_some());
+
+            action.value().set(Some(Ok(())));
+            tick().await;
+            assert_eq!(form.submit_error().get_untracked(), None);
+        })
+        .await;
+}
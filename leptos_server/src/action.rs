@@ -1,5 +1,7 @@
 use reactive_graph::{
-    actions::{Action, ArcAction},
+    actions::{
+        Action, ArcAction, ConcurrencyPolicy, OptimisticUpdate, RetryPolicy,
+    },
     owner::use_context,
     traits::DefinedAt,
 };
@@ -73,6 +75,34 @@ where
             defined_at: Location::caller(),
         }
     }
+
+    /// Sets a function that makes provisional changes to signals or stores whenever the action is
+    /// dispatched, which are rolled back if the server function returns an error.
+    ///
+    /// See [`ArcAction::with_optimistic_update`].
+    pub fn with_optimistic_update(
+        mut self,
+        update: impl Fn(&S, &mut OptimisticUpdate) + Send + Sync + 'static,
+    ) -> Self {
+        self.inner = self.inner.with_optimistic_update(update);
+        self
+    }
+
+    /// Sets how the action handles being dispatched while an earlier dispatch is still pending.
+    ///
+    /// See [`ArcAction::with_concurrency`].
+    pub fn with_concurrency(mut self, policy: ConcurrencyPolicy) -> Self {
+        self.inner = self.inner.with_concurrency(policy);
+        self
+    }
+
+    /// Sets how the action retries a dispatch whose server function returns an error.
+    ///
+    /// See [`ArcAction::with_retry`].
+    pub fn with_retry(mut self, policy: RetryPolicy) -> Self {
+        self.inner = self.inner.with_retry(policy);
+        self
+    }
 }
 
 impl<S> Deref for ArcServerAction<S>
@@ -161,6 +191,34 @@ where
             defined_at: Location::caller(),
         }
     }
+
+    /// Sets a function that makes provisional changes to signals or stores whenever the action is
+    /// dispatched, which are rolled back if the server function returns an error.
+    ///
+    /// See [`Action::with_optimistic_update`].
+    pub fn with_optimistic_update(
+        mut self,
+        update: impl Fn(&S, &mut OptimisticUpdate) + Send + Sync + 'static,
+    ) -> Self {
+        self.inner = self.inner.with_optimistic_update(update);
+        self
+    }
+
+    /// Sets how the action handles being dispatched while an earlier dispatch is still pending.
+    ///
+    /// See [`Action::with_concurrency`].
+    pub fn with_concurrency(mut self, policy: ConcurrencyPolicy) -> Self {
+        self.inner = self.inner.with_concurrency(policy);
+        self
+    }
+
+    /// Sets how the action retries a dispatch whose server function returns an error.
+    ///
+    /// See [`Action::with_retry`].
+    pub fn with_retry(mut self, policy: RetryPolicy) -> Self {
+        self.inner = self.inner.with_retry(policy);
+        self
+    }
 }
 
 impl<S> Clone for ServerAction<S>
@@ -12,9 +12,13 @@ mod multi_action;
 pub use multi_action::*;
 mod once_resource;
 pub use once_resource::*;
+mod query;
+pub use query::*;
 mod resource;
 pub use resource::*;
 mod shared;
+mod submission_store;
+pub use submission_store::*;
 
 use base64::{engine::general_purpose::STANDARD_NO_PAD, DecodeError, Engine};
 /// Re-export of the `codee` crate.
@@ -1,5 +1,8 @@
 use reactive_graph::{
-    actions::{ArcMultiAction, MultiAction},
+    actions::{
+        ArcMultiAction, ConcurrencyPolicy, MultiAction, OptimisticUpdate,
+        RetryPolicy, SubmissionStore,
+    },
     traits::DefinedAt,
 };
 use server_fn::ServerFn;
@@ -33,6 +36,67 @@ where
             defined_at: Location::caller(),
         }
     }
+
+    /// Sets a function that makes provisional changes to signals or stores whenever the action is
+    /// dispatched, which are rolled back if the server function returns an error.
+    ///
+    /// See [`ArcMultiAction::with_optimistic_update`].
+    pub fn with_optimistic_update(
+        mut self,
+        update: impl Fn(&S, &mut OptimisticUpdate) + Send + Sync + 'static,
+    ) -> Self {
+        self.inner = self.inner.with_optimistic_update(update);
+        self
+    }
+
+    /// Sets how the action handles being dispatched while earlier submissions are still pending.
+    ///
+    /// See [`ArcMultiAction::with_concurrency`].
+    pub fn with_concurrency(mut self, policy: ConcurrencyPolicy) -> Self {
+        self.inner = self.inner.with_concurrency(policy);
+        self
+    }
+
+    /// Sets how the action retries a submission whose server function returns an error.
+    ///
+    /// See [`ArcMultiAction::with_retry`].
+    pub fn with_retry(mut self, policy: RetryPolicy) -> Self {
+        self.inner = self.inner.with_retry(policy);
+        self
+    }
+
+    /// Persists the submissions that have not succeeded yet in `store`, such as a
+    /// `LocalStorageStore` in the browser or a `FileStore` on native targets, and replays them in
+    /// order when the action is back online.
+    ///
+    /// The action does not watch the network itself: call
+    /// [`set_online`](ArcMultiAction::set_online) when connectivity changes, for example from the
+    /// browser's `online` and `offline` events.
+    ///
+    /// See [`ArcMultiAction::with_offline_queue`].
+    pub fn with_offline_queue(
+        mut self,
+        store: impl SubmissionStore<S> + 'static,
+    ) -> Self {
+        self.inner = self.inner.with_offline_queue(store);
+        self
+    }
+
+    /// Like `with_offline_queue`, but only the server function errors for which `should_retry`
+    /// returns `true`, like network errors, hold up the queue until they are replayed. Other
+    /// errors resolve their submissions.
+    ///
+    /// See [`ArcMultiAction::with_offline_queue_retrying_if`].
+    pub fn with_offline_queue_retrying_if(
+        mut self,
+        store: impl SubmissionStore<S> + 'static,
+        should_retry: impl Fn(&S::Error) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.inner = self
+            .inner
+            .with_offline_queue_retrying_if(store, should_retry);
+        self
+    }
 }
 
 impl<S> Deref for ArcServerMultiAction<S>
@@ -127,6 +191,67 @@ where
             defined_at: Location::caller(),
         }
     }
+
+    /// Sets a function that makes provisional changes to signals or stores whenever the action is
+    /// dispatched, which are rolled back if the server function returns an error.
+    ///
+    /// See [`MultiAction::with_optimistic_update`].
+    pub fn with_optimistic_update(
+        mut self,
+        update: impl Fn(&S, &mut OptimisticUpdate) + Send + Sync + 'static,
+    ) -> Self {
+        self.inner = self.inner.with_optimistic_update(update);
+        self
+    }
+
+    /// Sets how the action handles being dispatched while earlier submissions are still pending.
+    ///
+    /// See [`MultiAction::with_concurrency`].
+    pub fn with_concurrency(mut self, policy: ConcurrencyPolicy) -> Self {
+        self.inner = self.inner.with_concurrency(policy);
+        self
+    }
+
+    /// Sets how the action retries a submission whose server function returns an error.
+    ///
+    /// See [`MultiAction::with_retry`].
+    pub fn with_retry(mut self, policy: RetryPolicy) -> Self {
+        self.inner = self.inner.with_retry(policy);
+        self
+    }
+
+    /// Persists the submissions that have not succeeded yet in `store`, such as a
+    /// `LocalStorageStore` in the browser or a `FileStore` on native targets, and replays them in
+    /// order when the action is back online.
+    ///
+    /// The action does not watch the network itself: call
+    /// [`set_online`](MultiAction::set_online) when connectivity changes, for example from the
+    /// browser's `online` and `offline` events.
+    ///
+    /// See [`MultiAction::with_offline_queue`].
+    pub fn with_offline_queue(
+        mut self,
+        store: impl SubmissionStore<S> + 'static,
+    ) -> Self {
+        self.inner = self.inner.with_offline_queue(store);
+        self
+    }
+
+    /// Like `with_offline_queue`, but only the server function errors for which `should_retry`
+    /// returns `true`, like network errors, hold up the queue until they are replayed. Other
+    /// errors resolve their submissions.
+    ///
+    /// See [`MultiAction::with_offline_queue_retrying_if`].
+    pub fn with_offline_queue_retrying_if(
+        mut self,
+        store: impl SubmissionStore<S> + 'static,
+        should_retry: impl Fn(&S::Error) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.inner = self
+            .inner
+            .with_offline_queue_retrying_if(store, should_retry);
+        self
+    }
 }
 
 impl<S> Clone for ServerMultiAction<S>
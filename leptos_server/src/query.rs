@@ -0,0 +1,615 @@
+use crate::{ArcResource, FromEncodedStr, IntoEncodedString};
+use any_spawner::Executor;
+use codee::{string::JsonSerdeCodec, Decoder, Encoder};
+use core::fmt::Debug;
+use futures::{
+    future::{self, Either, Ready, Shared},
+    Future, FutureExt,
+};
+use or_poisoned::OrPoisoned;
+use reactive_graph::{
+    computed::ArcAsyncDerived, owner::Owner, prelude::*, signal::ArcRwSignal,
+};
+use std::{
+    any::Any,
+    collections::HashMap,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+/// The key of a query in a [`QueryClient`].
+///
+/// A key is a list of segments, like `["users", "42"]`. Queries are invalidated by a prefix of
+/// their keys: invalidating `["users"]` invalidates every query whose key begins with `"users"`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct QueryKey(Vec<String>);
+
+impl QueryKey {
+    /// Creates a key from its segments.
+    pub fn new<S: ToString>(segments: impl IntoIterator<Item = S>) -> Self {
+        Self(segments.into_iter().map(|s| s.to_string()).collect())
+    }
+
+    /// The segments of the key.
+    pub fn segments(&self) -> &[String] {
+        &self.0
+    }
+
+    /// Returns `true` if this key begins with every segment of `prefix`.
+    pub fn starts_with(&self, prefix: &QueryKey) -> bool {
+        self.0.starts_with(&prefix.0)
+    }
+}
+
+impl From<&str> for QueryKey {
+    fn from(value: &str) -> Self {
+        Self(vec![value.to_string()])
+    }
+}
+
+impl From<String> for QueryKey {
+    fn from(value: String) -> Self {
+        Self(vec![value])
+    }
+}
+
+impl<S: ToString, const N: usize> From<[S; N]> for QueryKey {
+    fn from(value: [S; N]) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<S: ToString> From<Vec<S>> for QueryKey {
+    fn from(value: Vec<S>) -> Self {
+        Self::new(value)
+    }
+}
+
+/// Options for the queries cached by a [`QueryClient`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QueryOptions {
+    /// How long fetched data is considered fresh.
+    ///
+    /// A query that starts using stale data shows it immediately, and fetches it again in the
+    /// background. Defaults to zero, so that cached data is always revalidated. Use
+    /// [`Duration::MAX`] for data that never becomes stale.
+    pub stale_time: Duration,
+    /// How long data stays in the cache after the last query using it has been cleaned up.
+    ///
+    /// Defaults to five minutes. Use [`Duration::MAX`] to keep data until the client is dropped.
+    ///
+    /// This is ignored on the server, where each request usually has its own client, and the
+    /// data is dropped along with it at the end of the request.
+    pub gc_time: Duration,
+}
+
+impl Default for QueryOptions {
+    fn default() -> Self {
+        Self {
+            stale_time: Duration::ZERO,
+            gc_time: Duration::from_secs(5 * 60),
+        }
+    }
+}
+
+/// A cache of asynchronous data, shared by every query that uses the same [`QueryKey`].
+///
+/// Queries are resources that load their data through the cache:
+/// 1. Concurrent fetches for the same key are deduplicated, so that only one runs at a time.
+/// 2. Data that is still fresh is served from the cache without fetching it again.
+/// 3. Stale data is served from the cache while it is fetched again in the background. Once the
+///    new data has loaded, every query using that key is updated.
+/// 4. [`invalidate`](QueryClient::invalidate) marks every key with a given prefix as out of
+///    date, and immediately revalidates the queries that are currently using one of them.
+///
+/// Because queries are resources, their data is serialized from the server to the client, and
+/// the client adds it to its own cache while hydrating.
+///
+/// The client is usually provided via context at the root of the application. On the server,
+/// this means that each request has its own cache, rather than sharing data between users.
+#[derive(Clone, Default)]
+pub struct QueryClient {
+    options: QueryOptions,
+    entries: Arc<Mutex<HashMap<QueryKey, Arc<dyn AnyEntry>>>>,
+}
+
+impl Debug for QueryClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("QueryClient")
+            .field("options", &self.options)
+            .finish_non_exhaustive()
+    }
+}
+
+impl QueryClient {
+    /// Creates an empty cache with the default [`QueryOptions`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an empty cache with the given options.
+    pub fn with_options(options: QueryOptions) -> Self {
+        Self {
+            options,
+            entries: Default::default(),
+        }
+    }
+
+    /// The options used for every query in this cache.
+    pub fn options(&self) -> QueryOptions {
+        self.options
+    }
+
+    /// Loads the data for `key`.
+    ///
+    /// If the data is cached, this resolves to it immediately, and calls `fetcher` to revalidate
+    /// it in the background if it is stale. If it is not cached, this waits for the fetch that is
+    /// already in flight for `key`, or calls `fetcher` to start one.
+    pub fn fetch<T, Fut>(
+        &self,
+        key: impl Into<QueryKey>,
+        fetcher: impl FnOnce() -> Fut,
+    ) -> impl Future<Output = T> + Send + 'static
+    where
+        T: Clone + Send + Sync + 'static,
+        Fut: Future<Output = T> + Send + 'static,
+    {
+        self.entry::<T>(&key.into()).fetch(fetcher, true)
+    }
+
+    /// Returns the cached data for `key`, if any, whether or not it is stale.
+    pub fn get_query_data<T>(&self, key: impl Into<QueryKey>) -> Option<T>
+    where
+        T: Clone + Send + Sync + 'static,
+    {
+        let entry = self.existing_entry::<T>(&key.into())?;
+        let data = entry.state.lock().or_poisoned().data.clone();
+        data
+    }
+
+    /// Replaces the cached data for `key`, and updates every query using it.
+    ///
+    /// The new data is considered fresh, and a fetch that was already in flight for `key` is
+    /// ignored when it finishes.
+    pub fn set_query_data<T>(&self, key: impl Into<QueryKey>, value: T)
+    where
+        T: Clone + Send + Sync + 'static,
+    {
+        let entry = self.entry::<T>(&key.into());
+        let mut state = entry.state.lock().or_poisoned();
+        entry.store(&mut state, value);
+        drop(state);
+        entry.notify();
+    }
+
+    /// Marks the data for every key that begins with `prefix` as out of date.
+    ///
+    /// Queries that are currently using one of those keys fetch it again right away, while
+    /// showing the old data until the new data has loaded. Other keys are fetched again the next
+    /// time a query uses them.
+    pub fn invalidate(&self, prefix: impl Into<QueryKey>) {
+        let prefix = prefix.into();
+        let matching = self
+            .entries
+            .lock()
+            .or_poisoned()
+            .iter()
+            .filter(|(key, _)| key.starts_with(&prefix))
+            .map(|(_, entry)| Arc::clone(entry))
+            .collect::<Vec<_>>();
+        // invalidating notifies the queries using each entry, so it happens outside the lock
+        for entry in matching {
+            entry.invalidate();
+        }
+    }
+
+    /// Marks the data for every key as out of date.
+    ///
+    /// See [`invalidate`](QueryClient::invalidate).
+    pub fn invalidate_all(&self) {
+        self.invalidate(QueryKey::default());
+    }
+
+    /// Creates a query, which is a resource that loads its data through this cache, with the
+    /// encoding [`JsonSerdeCodec`].
+    ///
+    /// The resource reactively tracks the value returned by `key`. Whenever that value changes,
+    /// or the cached data for it is revalidated, the resource is updated with the data for that
+    /// key. `fetcher` is only called when the cache needs to load the data.
+    #[track_caller]
+    pub fn query<K, T, Fut>(
+        &self,
+        key: impl Fn() -> K + Send + Sync + 'static,
+        fetcher: impl Fn(K) -> Fut + Send + Sync + 'static,
+    ) -> ArcResource<T>
+    where
+        K: Into<QueryKey> + PartialEq + Clone + Send + Sync + 'static,
+        T: Clone + Send + Sync + 'static,
+        Fut: Future<Output = T> + Send + 'static,
+        JsonSerdeCodec: Encoder<T> + Decoder<T>,
+        <JsonSerdeCodec as Encoder<T>>::Error: Debug,
+        <JsonSerdeCodec as Decoder<T>>::Error: Debug,
+        <<JsonSerdeCodec as Decoder<T>>::Encoded as FromEncodedStr>::DecodingError:
+            Debug,
+        <JsonSerdeCodec as Encoder<T>>::Encoded: IntoEncodedString,
+        <JsonSerdeCodec as Decoder<T>>::Encoded: FromEncodedStr,
+    {
+        self.query_with_encoding(key, fetcher)
+    }
+
+    /// Creates a query, which is a resource that loads its data through this cache, with the
+    /// encoding `Ser`.
+    ///
+    /// See [`query`](QueryClient::query).
+    #[track_caller]
+    pub fn query_with_encoding<K, T, Ser, Fut>(
+        &self,
+        key: impl Fn() -> K + Send + Sync + 'static,
+        fetcher: impl Fn(K) -> Fut + Send + Sync + 'static,
+    ) -> ArcResource<T, Ser>
+    where
+        K: Into<QueryKey> + PartialEq + Clone + Send + Sync + 'static,
+        T: Clone + Send + Sync + 'static,
+        Fut: Future<Output = T> + Send + 'static,
+        Ser: Encoder<T> + Decoder<T>,
+        <Ser as Encoder<T>>::Error: Debug,
+        <Ser as Decoder<T>>::Error: Debug,
+        <<Ser as Decoder<T>>::Encoded as FromEncodedStr>::DecodingError: Debug,
+        <Ser as Encoder<T>>::Encoded: IntoEncodedString,
+        <Ser as Decoder<T>>::Encoded: FromEncodedStr,
+    {
+        let observer = Arc::new(QueryObserver {
+            client: self.clone(),
+            observed: Mutex::new(None),
+            fetched: Mutex::new(None),
+        });
+        Owner::on_cleanup({
+            let observer = Arc::clone(&observer);
+            move || observer.release()
+        });
+
+        let source = {
+            let observer = Arc::clone(&observer);
+            move || {
+                let key = key();
+                let entry = observer.observe::<T>(key.clone().into());
+                // the version changes whenever the cached data is replaced or invalidated
+                let version = entry.version.get();
+                (key, version)
+            }
+        };
+        let fetcher = {
+            let client = self.clone();
+            let observer = Arc::clone(&observer);
+            let fetcher = Arc::new(fetcher);
+            move |(key, _): (K, usize)| {
+                let client = client.clone();
+                let observer = Arc::clone(&observer);
+                let fetcher = Arc::clone(&fetcher);
+                // the resource creates this future even when it is hydrated with serialized data,
+                // so nothing is fetched until it is polled
+                async move {
+                    let query_key: QueryKey = key.clone().into();
+                    // stale data is revalidated when the query starts using a key, but not each
+                    // time the query is updated with the data that was just revalidated
+                    let is_new_key = observer.start_fetching(&query_key);
+                    client
+                        .entry::<T>(&query_key)
+                        .fetch(|| fetcher(key), is_new_key)
+                        .await
+                }
+            }
+        };
+        let resource =
+            ArcResource::<T, Ser>::new_with_options(source, fetcher, false);
+
+        // data that was serialized from the server is added to the cache while hydrating
+        let data: &ArcAsyncDerived<T> = &resource;
+        if let Some(value) = data.with_untracked(|value| value.clone()) {
+            observer.seed(value);
+        }
+
+        resource
+    }
+
+    fn entry<T>(&self, key: &QueryKey) -> Arc<QueryEntry<T>>
+    where
+        T: Clone + Send + Sync + 'static,
+    {
+        let mut entries = self.entries.lock().or_poisoned();
+        if let Some(entry) = entries.get(key) {
+            // if the key was last used for data of another type, its entry is replaced
+            if let Ok(entry) = Arc::clone(entry).into_any().downcast() {
+                return entry;
+            }
+        }
+        let entry = Arc::new(QueryEntry::new(self.options.stale_time));
+        entries.insert(key.clone(), Arc::clone(&entry) as Arc<dyn AnyEntry>);
+        entry
+    }
+
+    fn existing_entry<T>(&self, key: &QueryKey) -> Option<Arc<QueryEntry<T>>>
+    where
+        T: Clone + Send + Sync + 'static,
+    {
+        let entry = Arc::clone(self.entries.lock().or_poisoned().get(key)?);
+        entry.into_any().downcast().ok()
+    }
+
+    fn release(&self, key: QueryKey, entry: Arc<dyn AnyEntry>) {
+        let Some(releases) = entry.release() else {
+            return;
+        };
+        let gc_time = self.options.gc_time;
+        // on the server, this would leave a task waiting for every query of every request
+        if cfg!(feature = "ssr") || gc_time == Duration::MAX {
+            return;
+        }
+        let entries = Arc::downgrade(&self.entries);
+        let entry = Arc::downgrade(&entry);
+        Executor::spawn(async move {
+            Executor::sleep(gc_time).await;
+            let (Some(entries), Some(entry)) =
+                (entries.upgrade(), entry.upgrade())
+            else {
+                return;
+            };
+            if entry.is_collectable(releases) {
+                let mut entries = entries.lock().or_poisoned();
+                // the key may have been reused for data of another type in the meantime
+                if entries
+                    .get(&key)
+                    .is_some_and(|current| Arc::ptr_eq(current, &entry))
+                {
+                    entries.remove(&key);
+                }
+            }
+        });
+    }
+}
+
+/// Keeps track of the key that a single query is using.
+struct QueryObserver {
+    client: QueryClient,
+    observed: Mutex<Option<(QueryKey, Arc<dyn AnyEntry>)>>,
+    fetched: Mutex<Option<QueryKey>>,
+}
+
+impl QueryObserver {
+    fn observe<T>(&self, key: QueryKey) -> Arc<QueryEntry<T>>
+    where
+        T: Clone + Send + Sync + 'static,
+    {
+        let entry = self.client.entry::<T>(&key);
+        let mut observed = self.observed.lock().or_poisoned();
+        if observed.as_ref().map(|(prev, _)| prev) != Some(&key) {
+            entry.observe();
+            let prev = observed
+                .replace((key, Arc::clone(&entry) as Arc<dyn AnyEntry>));
+            if let Some((prev_key, prev_entry)) = prev {
+                self.client.release(prev_key, prev_entry);
+            }
+        }
+        entry
+    }
+
+    fn start_fetching(&self, key: &QueryKey) -> bool {
+        let mut fetched = self.fetched.lock().or_poisoned();
+        if fetched.as_ref() == Some(key) {
+            false
+        } else {
+            *fetched = Some(key.clone());
+            true
+        }
+    }
+
+    fn seed<T>(&self, value: T)
+    where
+        T: Clone + Send + Sync + 'static,
+    {
+        let key = self
+            .observed
+            .lock()
+            .or_poisoned()
+            .as_ref()
+            .map(|(key, _)| key.clone());
+        if let Some(key) = key {
+            self.client.entry::<T>(&key).seed(value);
+        }
+    }
+
+    fn release(&self) {
+        let observed = self.observed.lock().or_poisoned().take();
+        if let Some((key, entry)) = observed {
+            self.client.release(key, entry);
+        }
+    }
+}
+
+type SharedFetch<T> = Shared<Pin<Box<dyn Future<Output = T> + Send>>>;
+
+struct QueryEntry<T> {
+    stale_time: Duration,
+    version: ArcRwSignal<usize>,
+    state: Mutex<EntryState<T>>,
+}
+
+struct EntryState<T> {
+    data: Option<T>,
+    stale: bool,
+    invalidated: bool,
+    // incremented whenever the data is stored or invalidated, so that fetches and timers that
+    // were started before then can be ignored
+    generation: u64,
+    in_flight: Option<SharedFetch<T>>,
+    observers: usize,
+    releases: u64,
+}
+
+impl<T> QueryEntry<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    fn new(stale_time: Duration) -> Self {
+        Self {
+            stale_time,
+            version: ArcRwSignal::new(0),
+            state: Mutex::new(EntryState {
+                data: None,
+                stale: false,
+                invalidated: false,
+                generation: 0,
+                in_flight: None,
+                observers: 0,
+                releases: 0,
+            }),
+        }
+    }
+
+    fn fetch<Fut>(
+        self: &Arc<Self>,
+        fetcher: impl FnOnce() -> Fut,
+        revalidate_stale: bool,
+    ) -> Either<Ready<T>, SharedFetch<T>>
+    where
+        Fut: Future<Output = T> + Send + 'static,
+    {
+        let mut state = self.state.lock().or_poisoned();
+        match state.data.clone() {
+            Some(data) => {
+                let needs_revalidation =
+                    state.invalidated || (revalidate_stale && state.stale);
+                if needs_revalidation && state.in_flight.is_none() {
+                    let fetch = self.start_fetch(&mut state, fetcher());
+                    Executor::spawn(fetch.map(drop));
+                }
+                Either::Left(future::ready(data))
+            }
+            None => Either::Right(match state.in_flight.clone() {
+                Some(fetch) => fetch,
+                None => self.start_fetch(&mut state, fetcher()),
+            }),
+        }
+    }
+
+    fn start_fetch(
+        self: &Arc<Self>,
+        state: &mut EntryState<T>,
+        fut: impl Future<Output = T> + Send + 'static,
+    ) -> SharedFetch<T> {
+        let generation = state.generation;
+        let entry = Arc::downgrade(self);
+        let fetch: Pin<Box<dyn Future<Output = T> + Send>> =
+            Box::pin(async move {
+                let value = fut.await;
+                if let Some(entry) = entry.upgrade() {
+                    entry.finish(generation, value.clone());
+                }
+                value
+            });
+        let fetch = fetch.shared();
+        state.in_flight = Some(fetch.clone());
+        fetch
+    }
+
+    fn finish(self: &Arc<Self>, generation: u64, value: T) {
+        let mut state = self.state.lock().or_poisoned();
+        // the data was stored or invalidated after this fetch started
+        if state.generation != generation {
+            return;
+        }
+        let had_data = state.data.is_some();
+        self.store(&mut state, value);
+        drop(state);
+        // queries that were waiting for the data already have it, but queries showing the old data
+        // need to be updated
+        if had_data {
+            self.notify();
+        }
+    }
+
+    fn seed(self: &Arc<Self>, value: T) {
+        let mut state = self.state.lock().or_poisoned();
+        if state.data.is_none() {
+            self.store(&mut state, value);
+        }
+    }
+
+    fn store(self: &Arc<Self>, state: &mut EntryState<T>, value: T) {
+        state.data = Some(value);
+        state.invalidated = false;
+        state.in_flight = None;
+        state.generation += 1;
+        state.stale = self.stale_time.is_zero();
+        if !state.stale && self.stale_time != Duration::MAX {
+            let generation = state.generation;
+            let stale_time = self.stale_time;
+            let entry = Arc::downgrade(self);
+            Executor::spawn(async move {
+                Executor::sleep(stale_time).await;
+                if let Some(entry) = entry.upgrade() {
+                    let mut state = entry.state.lock().or_poisoned();
+                    if state.generation == generation {
+                        state.stale = true;
+                    }
+                }
+            });
+        }
+    }
+
+    fn notify(&self) {
+        self.version.update(|n| *n += 1);
+    }
+}
+
+/// The parts of a [`QueryEntry`] that do not depend on the type of its data.
+trait AnyEntry: Send + Sync {
+    fn invalidate(&self);
+
+    fn observe(&self);
+
+    /// Returns the number of times the entry has been released, if it is no longer observed.
+    fn release(&self) -> Option<u64>;
+
+    fn is_collectable(&self, releases: u64) -> bool;
+
+    fn into_any(self: Arc<Self>) -> Arc<dyn Any + Send + Sync>;
+}
+
+impl<T> AnyEntry for QueryEntry<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    fn invalidate(&self) {
+        let mut state = self.state.lock().or_poisoned();
+        state.invalidated = true;
+        state.generation += 1;
+        state.in_flight = None;
+        let is_observed = state.observers > 0;
+        drop(state);
+        if is_observed {
+            self.notify();
+        }
+    }
+
+    fn observe(&self) {
+        self.state.lock().or_poisoned().observers += 1;
+    }
+
+    fn release(&self) -> Option<u64> {
+        let mut state = self.state.lock().or_poisoned();
+        state.observers = state.observers.saturating_sub(1);
+        state.releases += 1;
+        (state.observers == 0).then_some(state.releases)
+    }
+
+    fn is_collectable(&self, releases: u64) -> bool {
+        let state = self.state.lock().or_poisoned();
+        state.observers == 0 && state.releases == releases
+    }
+
+    fn into_any(self: Arc<Self>) -> Arc<dyn Any + Send + Sync> {
+        self
+    }
+}
@@ -12,7 +12,7 @@ use codee::{
     Decoder, Encoder,
 };
 use core::{fmt::Debug, marker::PhantomData};
-use futures::Future;
+use futures::{future::Either, Future, Stream};
 use hydration_context::{SerializedDataId, SharedContext};
 use reactive_graph::{
     computed::{
@@ -366,6 +366,108 @@ where
         }
     }
 
+    /// Creates a new resource with the encoding `Ser`, which holds the latest item of a
+    /// [`Stream`], like a server-sent event stream or websocket.
+    ///
+    /// This takes a `source` function and a `fetcher`. The resource memoizes and reactively tracks
+    /// the value returned by `source`. Whenever that value changes, it will run the `fetcher` to
+    /// generate a new [`Stream`], and drop the previous one. The resource is loading until the
+    /// first item of the stream arrives; each later item replaces its value as it arrives, without
+    /// suspending. If the stream ends without yielding any items, the resource resolves to `None`.
+    ///
+    /// On creation, if you are on the server, this will only wait for the first item of the
+    /// stream (or for it to end without one), which will be serialized from the server to the
+    /// client. If you are on the client, that item will be deserialized, and the stream will be
+    /// created immediately to receive the items that follow it.
+    ///
+    /// If `blocking` is `true`, this is a blocking resource.
+    #[track_caller]
+    pub fn new_stream_with_options<S, St>(
+        source: impl Fn() -> S + Send + Sync + 'static,
+        fetcher: impl Fn(S) -> St + Send + Sync + 'static,
+        #[allow(unused)] // this is used with `feature = "ssr"`
+        blocking: bool,
+    ) -> ArcResource<Option<T>, Ser>
+    where
+        S: PartialEq + Clone + Send + Sync + 'static,
+        T: Send + Sync + 'static,
+        St: Stream<Item = T> + Send + 'static,
+    {
+        let shared_context = Owner::current_shared_context();
+        let id = shared_context
+            .as_ref()
+            .map(|sc| sc.next_id())
+            .unwrap_or_default();
+
+        let initial =
+            initial_stream_value::<T, Ser>(&id, shared_context.as_ref());
+        let is_ready = initial.is_some();
+
+        let refetch = ArcRwSignal::new(0);
+        let source = ArcMemo::new({
+            let refetch = refetch.clone();
+            move |_| (refetch.get(), run_in_resource_source_signal(&source))
+        });
+        let fun = {
+            let source = source.clone();
+            move || {
+                let (_, source) = source.get();
+                let stream = fetcher(source);
+                // only the first item is rendered and serialized on the server
+                #[cfg(feature = "ssr")]
+                let stream = futures::StreamExt::take(stream, 1);
+                if IS_SUPPRESSING_RESOURCE_LOAD.load(Ordering::Relaxed) {
+                    Either::Left(futures::stream::pending())
+                } else {
+                    Either::Right(stream)
+                }
+            }
+        };
+
+        let data = ArcAsyncDerived::new_stream_with_manual_dependencies(
+            initial, fun, &source,
+        );
+        if is_ready {
+            source.with_untracked(|_| ());
+            source.add_subscriber(data.to_any_subscriber());
+        }
+
+        #[cfg(feature = "ssr")]
+        if let Some(shared_context) = shared_context {
+            let value = data.clone();
+            let ready_fut = data.ready();
+
+            if blocking {
+                shared_context.defer_stream(Box::pin(data.ready()));
+            }
+
+            if shared_context.get_is_hydrating() {
+                shared_context.write_async(
+                    id,
+                    Box::pin(async move {
+                        ready_fut.await;
+                        value.with_untracked(|data| match &data {
+                            // TODO handle serialization errors
+                            Some(Some(val)) => format!(
+                                "{STREAM_ITEM}{}",
+                                Ser::encode(val).unwrap().into_encoded_string()
+                            ),
+                            _ => STREAM_ENDED.to_string(),
+                        })
+                    }),
+                );
+            }
+        }
+
+        ArcResource {
+            ser: PhantomData,
+            data,
+            refetch,
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: Location::caller(),
+        }
+    }
+
     /// Synchronously, reactively reads the current value of the resource and applies the function
     /// `f` to its value if it is `Some(_)`.
     #[track_caller]
@@ -398,37 +500,83 @@ where
 {
     #[cfg(feature = "hydration")]
     {
-        use std::borrow::Borrow;
+        let shared_context = Owner::current_shared_context();
+        if let Some(value) = shared_context.and_then(|sc| sc.read_data(id)) {
+            return decode_initial_value::<T, Ser>(&value);
+        }
+    }
+    None
+}
 
+// A stream can end without yielding an item, and an item can encode to any string (including an
+// empty one), so stream resources write which of the two happened before the item itself.
+#[cfg(any(feature = "ssr", feature = "hydration"))]
+const STREAM_ITEM: &str = "+";
+#[cfg(any(feature = "ssr", feature = "hydration"))]
+const STREAM_ENDED: &str = "-";
+
+/// Like [`initial_value`], for the data written by a stream resource: `Some(None)` if the stream
+/// ended without any items.
+#[inline(always)]
+#[allow(unused)]
+fn initial_stream_value<T, Ser>(
+    id: &SerializedDataId,
+    shared_context: Option<&Arc<dyn SharedContext + Send + Sync>>,
+) -> Option<Option<T>>
+where
+    Ser: Encoder<T> + Decoder<T>,
+    <Ser as Encoder<T>>::Error: Debug,
+    <Ser as Decoder<T>>::Error: Debug,
+    <<Ser as Decoder<T>>::Encoded as FromEncodedStr>::DecodingError: Debug,
+    <Ser as Encoder<T>>::Encoded: IntoEncodedString,
+    <Ser as Decoder<T>>::Encoded: FromEncodedStr,
+{
+    #[cfg(feature = "hydration")]
+    {
         let shared_context = Owner::current_shared_context();
-        if let Some(shared_context) = shared_context {
-            let value = shared_context.read_data(id);
-            if let Some(value) = value {
-                let encoded =
-                    match <Ser as Decoder<T>>::Encoded::from_encoded_str(&value)
-                    {
-                        Ok(value) => value,
-                        Err(e) => {
-                            #[cfg(feature = "tracing")]
-                            tracing::error!("couldn't deserialize: {e:?}");
-                            return None;
-                        }
-                    };
-                let encoded = encoded.borrow();
-                match Ser::decode(encoded) {
-                    Ok(value) => return Some(value),
-                    #[allow(unused)]
-                    Err(e) => {
-                        #[cfg(feature = "tracing")]
-                        tracing::error!("couldn't deserialize: {e:?}");
-                    }
-                }
+        if let Some(value) = shared_context.and_then(|sc| sc.read_data(id)) {
+            if value == STREAM_ENDED {
+                return Some(None);
             }
+            return value
+                .strip_prefix(STREAM_ITEM)
+                .and_then(decode_initial_value::<T, Ser>)
+                .map(Some);
         }
     }
     None
 }
 
+#[cfg(feature = "hydration")]
+fn decode_initial_value<T, Ser>(value: &str) -> Option<T>
+where
+    Ser: Encoder<T> + Decoder<T>,
+    <Ser as Encoder<T>>::Error: Debug,
+    <Ser as Decoder<T>>::Error: Debug,
+    <<Ser as Decoder<T>>::Encoded as FromEncodedStr>::DecodingError: Debug,
+    <Ser as Encoder<T>>::Encoded: IntoEncodedString,
+    <Ser as Decoder<T>>::Encoded: FromEncodedStr,
+{
+    use std::borrow::Borrow;
+
+    let encoded = match <Ser as Decoder<T>>::Encoded::from_encoded_str(value) {
+        Ok(value) => value,
+        #[allow(unused)]
+        Err(e) => {
+            #[cfg(feature = "tracing")]
+            tracing::error!("couldn't deserialize: {e:?}");
+            return None;
+        }
+    };
+    let encoded = encoded.borrow();
+    let decoded = Ser::decode(encoded);
+    #[cfg(feature = "tracing")]
+    if let Err(e) = &decoded {
+        tracing::error!("couldn't deserialize: {e:?}");
+    }
+    decoded.ok()
+}
+
 impl<T, E, Ser> ArcResource<Result<T, E>, Ser>
 where
     Ser: Encoder<Result<T, E>> + Decoder<Result<T, E>>,
@@ -511,6 +659,23 @@ where
     {
         ArcResource::new_with_options(source, fetcher, true)
     }
+
+    /// Creates a new resource with the encoding [`JsonSerdeCodec`], which holds the latest item
+    /// of a [`Stream`].
+    ///
+    /// See [`ArcResource::new_stream_with_options`] for details.
+    #[track_caller]
+    pub fn new_stream<S, St>(
+        source: impl Fn() -> S + Send + Sync + 'static,
+        fetcher: impl Fn(S) -> St + Send + Sync + 'static,
+    ) -> ArcResource<Option<T>, JsonSerdeCodec>
+    where
+        S: PartialEq + Clone + Send + Sync + 'static,
+        T: Send + Sync + 'static,
+        St: Stream<Item = T> + Send + 'static,
+    {
+        ArcResource::new_stream_with_options(source, fetcher, false)
+    }
 }
 
 impl<T> ArcResource<T, FromToStringCodec>
@@ -1083,6 +1248,23 @@ where
     {
         Resource::new_with_options(source, fetcher, true)
     }
+
+    /// Creates a new resource with the encoding [`JsonSerdeCodec`], which holds the latest item
+    /// of a [`Stream`].
+    ///
+    /// See [`ArcResource::new_stream_with_options`] for details.
+    #[track_caller]
+    pub fn new_stream<S, St>(
+        source: impl Fn() -> S + Send + Sync + 'static,
+        fetcher: impl Fn(S) -> St + Send + Sync + 'static,
+    ) -> Resource<Option<T>, JsonSerdeCodec>
+    where
+        S: PartialEq + Clone + Send + Sync + 'static,
+        T: Send + Sync + 'static,
+        St: Stream<Item = T> + Send + 'static,
+    {
+        Resource::new_stream_with_options(source, fetcher, false)
+    }
 }
 
 #[cfg(feature = "serde-wasm-bindgen")]
@@ -1369,6 +1551,32 @@ where
         }
     }
 
+    /// Creates a new resource with the encoding `Ser`, which holds the latest item of a
+    /// [`Stream`].
+    ///
+    /// See [`ArcResource::new_stream_with_options`] for details.
+    #[track_caller]
+    pub fn new_stream_with_options<S, St>(
+        source: impl Fn() -> S + Send + Sync + 'static,
+        fetcher: impl Fn(S) -> St + Send + Sync + 'static,
+        blocking: bool,
+    ) -> Resource<Option<T>, Ser>
+    where
+        S: Send + Sync + Clone + PartialEq + 'static,
+        T: Send + Sync + 'static,
+        St: Stream<Item = T> + Send + 'static,
+    {
+        let ArcResource { data, refetch, .. }: ArcResource<Option<T>, Ser> =
+            ArcResource::new_stream_with_options(source, fetcher, blocking);
+        Resource {
+            ser: PhantomData,
+            data: data.into(),
+            refetch: refetch.into(),
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: Location::caller(),
+        }
+    }
+
     /// Synchronously, reactively reads the current value of the resource and applies the function
     /// `f` to its value if it is `Some(_)`.
     pub fn map<U>(&self, f: impl FnOnce(&T) -> U) -> Option<U> {
@@ -0,0 +1,154 @@
+use reactive_graph::actions::SubmissionStore;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{fmt::Display, marker::PhantomData};
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+use std::{io, path::PathBuf};
+
+/// A [`SubmissionStore`] that keeps submissions in the browser's `localStorage`, encoded as JSON,
+/// so that they are replayed after the page is reloaded.
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+#[derive(Debug, Clone)]
+pub struct LocalStorageStore<I> {
+    key: String,
+    ty: PhantomData<fn() -> I>,
+}
+
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+impl<I> LocalStorageStore<I> {
+    /// Creates a store that keeps submissions under the given `localStorage` key.
+    pub fn new(key: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            ty: PhantomData,
+        }
+    }
+
+    fn storage() -> Option<web_sys::Storage> {
+        web_sys::window()?.local_storage().ok().flatten()
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+impl<I> SubmissionStore<I> for LocalStorageStore<I>
+where
+    I: Serialize + DeserializeOwned,
+{
+    fn load(&self) -> Vec<I> {
+        let Some(json) = Self::storage()
+            .and_then(|storage| storage.get_item(&self.key).ok().flatten())
+        else {
+            return Vec::new();
+        };
+        decode(&json, format_args!("localStorage key {:?}", self.key))
+    }
+
+    fn save(&self, inputs: &[I]) {
+        let Some(storage) = Self::storage() else {
+            return;
+        };
+        let result = if inputs.is_empty() {
+            storage.remove_item(&self.key)
+        } else {
+            let Some(json) = encode(inputs) else {
+                return;
+            };
+            storage.set_item(&self.key, &json)
+        };
+        if result.is_err() {
+            reactive_graph::log_warning(format_args!(
+                "couldn't save submissions to localStorage key {:?}",
+                self.key
+            ));
+        }
+    }
+}
+
+/// A [`SubmissionStore`] that keeps submissions in a file, encoded as JSON, so that they are
+/// replayed after the app is restarted.
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+#[derive(Debug, Clone)]
+pub struct FileStore<I> {
+    path: PathBuf,
+    ty: PhantomData<fn() -> I>,
+}
+
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+impl<I> FileStore<I> {
+    /// Creates a store that keeps submissions in the file at `path`, which is created when there
+    /// is a submission to save.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            ty: PhantomData,
+        }
+    }
+
+    fn write(&self, json: &str) -> io::Result<()> {
+        // writes to a temporary file first, so that a crash cannot leave a partial file behind
+        let tmp = self.path.with_extension("tmp");
+        std::fs::write(&tmp, json)?;
+        std::fs::rename(tmp, &self.path)
+    }
+}
+
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+impl<I> SubmissionStore<I> for FileStore<I>
+where
+    I: Serialize + DeserializeOwned,
+{
+    fn load(&self) -> Vec<I> {
+        let json = match std::fs::read_to_string(&self.path) {
+            Ok(json) => json,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Vec::new(),
+            Err(e) => {
+                reactive_graph::log_warning(format_args!(
+                    "couldn't load submissions from {}: {e}",
+                    self.path.display()
+                ));
+                return Vec::new();
+            }
+        };
+        decode(&json, self.path.display())
+    }
+
+    fn save(&self, inputs: &[I]) {
+        let result = if inputs.is_empty() {
+            match std::fs::remove_file(&self.path) {
+                Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+                result => result,
+            }
+        } else {
+            let Some(json) = encode(inputs) else {
+                return;
+            };
+            self.write(&json)
+        };
+        if let Err(e) = result {
+            reactive_graph::log_warning(format_args!(
+                "couldn't save submissions to {}: {e}",
+                self.path.display()
+            ));
+        }
+    }
+}
+
+/// Encodes the inputs of submissions as JSON, in the format shared by every store here.
+fn encode<I: Serialize>(inputs: &[I]) -> Option<String> {
+    serde_json::to_string(inputs)
+        .inspect_err(|e| {
+            reactive_graph::log_warning(format_args!(
+                "couldn't serialize submissions: {e}"
+            ));
+        })
+        .ok()
+}
+
+/// Decodes the inputs of submissions encoded with [`encode`], loaded from `source`.
+fn decode<I: DeserializeOwned>(json: &str, source: impl Display) -> Vec<I> {
+    serde_json::from_str(json).unwrap_or_else(|e| {
+        reactive_graph::log_warning(format_args!(
+            "couldn't load submissions from {source}: {e}"
+        ));
+        Vec::new()
+    })
+}
@@ -0,0 +1,168 @@
+#[cfg(feature = "hydration")]
+mod shared_context;
+
+use any_spawner::{Executor, TestExecutor};
+use futures::{channel::oneshot, FutureExt};
+use leptos_server::{QueryClient, QueryOptions};
+use reactive_graph::{owner::Owner, traits::GetUntracked};
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+// a fetcher that counts how many times it is called, and resolves to `value`
+fn counted(
+    calls: &Arc<AtomicUsize>,
+    value: i32,
+) -> impl FnOnce() -> futures::future::Ready<i32> {
+    let calls = Arc::clone(calls);
+    move || {
+        calls.fetch_add(1, Ordering::Relaxed);
+        futures::future::ready(value)
+    }
+}
+
+#[test]
+fn concurrent_fetches_for_a_key_are_deduplicated() {
+    _ = Executor::init_test_executor();
+    let client = QueryClient::new();
+    let calls = Arc::new(AtomicUsize::new(0));
+    let (tx, rx) = oneshot::channel();
+    let rx = rx.shared();
+
+    let results = Arc::new(Mutex::new(Vec::new()));
+    for _ in 0..2 {
+        let fetch = client.fetch("answer", {
+            let calls = Arc::clone(&calls);
+            let rx = rx.clone();
+            move || {
+                calls.fetch_add(1, Ordering::Relaxed);
+                async move { rx.await.unwrap() }
+            }
+        });
+        let results = Arc::clone(&results);
+        Executor::spawn(async move {
+            let value: i32 = fetch.await;
+            results.lock().unwrap().push(value);
+        });
+    }
+    TestExecutor::run_until_stalled();
+    assert_eq!(calls.load(Ordering::Relaxed), 1);
+
+    tx.send(42).unwrap();
+    TestExecutor::run_until_stalled();
+    assert_eq!(*results.lock().unwrap(), [42, 42]);
+    assert_eq!(client.get_query_data::<i32>("answer"), Some(42));
+}
+
+#[test]
+fn invalidate_marks_every_key_with_the_prefix_as_out_of_date() {
+    _ = Executor::init_test_executor();
+    let client = QueryClient::with_options(QueryOptions {
+        stale_time: Duration::MAX,
+        ..Default::default()
+    });
+    client.set_query_data(["users", "1"], 1);
+    client.set_query_data(["users", "2"], 2);
+    client.set_query_data(["posts", "1"], 3);
+
+    client.invalidate("users");
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    for key in [["users", "1"], ["users", "2"], ["posts", "1"]] {
+        // out-of-date data is still served while it is fetched again
+        Executor::spawn(client.fetch(key, counted(&calls, 0)).map(drop));
+    }
+    TestExecutor::run_until_stalled();
+    assert_eq!(calls.load(Ordering::Relaxed), 2);
+    assert_eq!(client.get_query_data::<i32>(["users", "1"]), Some(0));
+    assert_eq!(client.get_query_data::<i32>(["posts", "1"]), Some(3));
+}
+
+#[test]
+fn data_is_revalidated_once_it_becomes_stale() {
+    _ = Executor::init_test_executor();
+    let client = QueryClient::with_options(QueryOptions {
+        stale_time: Duration::from_secs(10),
+        ..Default::default()
+    });
+    client.set_query_data("answer", 1);
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    Executor::spawn(client.fetch("answer", counted(&calls, 2)).map(drop));
+    TestExecutor::run_until_stalled();
+    assert_eq!(calls.load(Ordering::Relaxed), 0);
+
+    TestExecutor::advance(Duration::from_secs(10));
+    Executor::spawn(client.fetch("answer", counted(&calls, 2)).map(drop));
+    TestExecutor::run_until_stalled();
+    assert_eq!(calls.load(Ordering::Relaxed), 1);
+    assert_eq!(client.get_query_data::<i32>("answer"), Some(2));
+}
+
+#[cfg(not(feature = "ssr"))]
+#[test]
+fn unused_data_is_collected_after_the_gc_time() {
+    _ = Executor::init_test_executor();
+    let client = QueryClient::with_options(QueryOptions {
+        gc_time: Duration::from_secs(60),
+        ..Default::default()
+    });
+
+    let owner = Owner::new();
+    let query = owner
+        .with(|| client.query(|| "answer", |_| futures::future::ready(42)));
+    TestExecutor::run_until_stalled();
+    assert_eq!(query.get_untracked(), Some(42));
+
+    owner.cleanup();
+    TestExecutor::advance(Duration::from_secs(59));
+    assert_eq!(client.get_query_data::<i32>("answer"), Some(42));
+    TestExecutor::advance(Duration::from_secs(1));
+    assert_eq!(client.get_query_data::<i32>("answer"), None);
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn unused_data_is_not_collected_on_the_server() {
+    _ = Executor::init_test_executor();
+    let client = QueryClient::new();
+
+    let owner = Owner::new();
+    owner.with(|| client.query(|| "answer", |_| futures::future::ready(42)));
+    TestExecutor::run_until_stalled();
+
+    owner.cleanup();
+    assert_eq!(TestExecutor::next_timer(), None);
+    assert_eq!(client.get_query_data::<i32>("answer"), Some(42));
+}
+
+#[cfg(feature = "hydration")]
+#[test]
+fn hydrating_a_query_seeds_the_cache() {
+    use shared_context::TestSharedContext;
+
+    _ = Executor::init_test_executor();
+    let client = QueryClient::new();
+    let calls = Arc::new(AtomicUsize::new(0));
+
+    let owner = Owner::new_root(Some(TestSharedContext::client([(0, "42")])));
+    let query = owner.with(|| {
+        let calls = Arc::clone(&calls);
+        client.query(
+            || "answer",
+            move |_| {
+                calls.fetch_add(1, Ordering::Relaxed);
+                futures::future::ready(0)
+            },
+        )
+    });
+    TestExecutor::run_until_stalled();
+
+    assert_eq!(query.get_untracked(), Some(42));
+    assert_eq!(client.get_query_data::<i32>("answer"), Some(42));
+    assert_eq!(calls.load(Ordering::Relaxed), 0);
+}
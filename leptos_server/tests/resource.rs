@@ -0,0 +1,91 @@
+#![cfg(all(feature = "ssr", feature = "hydration"))]
+
+mod shared_context;
+
+use any_spawner::{Executor, TestExecutor};
+use codee::string::FromToStringCodec;
+use leptos_server::ArcResource;
+use reactive_graph::{owner::Owner, traits::GetUntracked};
+use shared_context::TestSharedContext;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+// a stream resource of strings, whose fetcher counts how many times it is called
+fn stream_resource(
+    calls: &Arc<AtomicUsize>,
+    items: &'static [&'static str],
+) -> ArcResource<Option<String>, FromToStringCodec> {
+    let calls = Arc::clone(calls);
+    ArcResource::new_stream_with_options(
+        || (),
+        move |_| {
+            calls.fetch_add(1, Ordering::Relaxed);
+            futures::stream::iter(items.iter().map(|item| item.to_string()))
+        },
+        false,
+    )
+}
+
+#[test]
+fn the_first_item_of_a_stream_is_hydrated() {
+    _ = Executor::init_test_executor();
+    let server = TestSharedContext::server();
+    let calls = Arc::new(AtomicUsize::new(0));
+
+    let owner = Owner::new_root(Some(server.clone()));
+    let resource = owner.with(|| stream_resource(&calls, &["", "later"]));
+    TestExecutor::run_until_stalled();
+    assert_eq!(resource.get_untracked(), Some(Some(String::new())));
+    assert_eq!(server.written_ids(), [0]);
+
+    // an empty string is an item like any other, and the client only creates the stream to
+    // receive the items after it
+    let owner = Owner::new_root(Some(server.hydrate()));
+    let resource = owner.with(|| stream_resource(&calls, &["client"]));
+    assert_eq!(resource.get_untracked(), Some(Some(String::new())));
+    assert_eq!(calls.load(Ordering::Relaxed), 2);
+    TestExecutor::run_until_stalled();
+    assert_eq!(resource.get_untracked(), Some(Some("client".to_string())));
+    assert_eq!(calls.load(Ordering::Relaxed), 2);
+}
+
+#[test]
+fn a_stream_that_ends_without_items_is_hydrated_as_none() {
+    _ = Executor::init_test_executor();
+    let server = TestSharedContext::server();
+    let calls = Arc::new(AtomicUsize::new(0));
+
+    let owner = Owner::new_root(Some(server.clone()));
+    let resource = owner.with(|| stream_resource(&calls, &[]));
+    TestExecutor::run_until_stalled();
+    assert_eq!(resource.get_untracked(), Some(None));
+    assert_eq!(server.written_ids(), [0]);
+
+    // the client still creates the stream, to receive any items it yields later
+    let owner = Owner::new_root(Some(server.hydrate()));
+    let resource = owner.with(|| stream_resource(&calls, &["client"]));
+    assert_eq!(resource.get_untracked(), Some(None));
+    TestExecutor::run_until_stalled();
+    assert_eq!(resource.get_untracked(), Some(Some("client".to_string())));
+    assert_eq!(calls.load(Ordering::Relaxed), 2);
+}
+
+#[test]
+fn awaiting_a_stream_without_items_resolves_to_none() {
+    _ = Executor::init_test_executor();
+    let calls = Arc::new(AtomicUsize::new(0));
+
+    let owner = Owner::new();
+    let resource = owner.with(|| stream_resource(&calls, &[]));
+    let value = Arc::new(std::sync::Mutex::new(None));
+    Executor::spawn({
+        let value = Arc::clone(&value);
+        async move {
+            *value.lock().unwrap() = Some(resource.await);
+        }
+    });
+    TestExecutor::run_until_stalled();
+    assert_eq!(*value.lock().unwrap(), Some(None));
+}
@@ -0,0 +1,151 @@
+use futures::FutureExt;
+use hydration_context::{
+    PinnedFuture, PinnedStream, SerializedDataId, SharedContext,
+};
+use or_poisoned::OrPoisoned;
+use std::{
+    collections::HashMap,
+    fmt::{self, Debug},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+};
+use throw_error::{Error, ErrorId};
+
+/// A shared context that keeps the data written on the server in memory, so that a shared
+/// context for the client can read it back while hydrating.
+#[derive(Default)]
+pub struct TestSharedContext {
+    is_browser: bool,
+    id: AtomicUsize,
+    written: Mutex<Vec<(SerializedDataId, PinnedFuture<String>)>>,
+    data: HashMap<SerializedDataId, String>,
+}
+
+impl Debug for TestSharedContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TestSharedContext")
+            .field("is_browser", &self.is_browser)
+            .field("data", &self.data)
+            .finish_non_exhaustive()
+    }
+}
+
+#[allow(dead_code)]
+impl TestSharedContext {
+    /// A context for rendering on the server.
+    pub fn server() -> Arc<Self> {
+        Arc::new(Self::default())
+    }
+
+    /// A context for hydrating with the given serialized data, by ID.
+    pub fn client<'a>(
+        data: impl IntoIterator<Item = (usize, &'a str)>,
+    ) -> Arc<Self> {
+        Arc::new(Self {
+            is_browser: true,
+            data: data
+                .into_iter()
+                .map(|(id, value)| (SerializedDataId::new(id), value.into()))
+                .collect(),
+            ..Default::default()
+        })
+    }
+
+    /// A context for hydrating with the data written on the server so far, which must all be
+    /// ready.
+    pub fn hydrate(&self) -> Arc<Self> {
+        let written = std::mem::take(&mut *self.written.lock().or_poisoned());
+        Arc::new(Self {
+            is_browser: true,
+            data: written
+                .into_iter()
+                .map(|(id, fut)| {
+                    (id, fut.now_or_never().expect("data should be ready"))
+                })
+                .collect(),
+            ..Default::default()
+        })
+    }
+
+    /// The IDs of the data written on the server so far.
+    pub fn written_ids(&self) -> Vec<usize> {
+        self.written
+            .lock()
+            .or_poisoned()
+            .iter()
+            .map(|(id, _)| id.clone().into_inner())
+            .collect()
+    }
+}
+
+impl SharedContext for TestSharedContext {
+    fn is_browser(&self) -> bool {
+        self.is_browser
+    }
+
+    fn next_id(&self) -> SerializedDataId {
+        SerializedDataId::new(self.id.fetch_add(1, Ordering::Relaxed))
+    }
+
+    fn write_async(&self, id: SerializedDataId, fut: PinnedFuture<String>) {
+        if !self.is_browser {
+            self.written.lock().or_poisoned().push((id, fut));
+        }
+    }
+
+    fn read_data(&self, id: &SerializedDataId) -> Option<String> {
+        self.data.get(id).cloned()
+    }
+
+    fn await_data(&self, _id: &SerializedDataId) -> Option<String> {
+        None
+    }
+
+    fn pending_data(&self) -> Option<PinnedStream<String>> {
+        None
+    }
+
+    fn during_hydration(&self) -> bool {
+        self.is_browser
+    }
+
+    fn hydration_complete(&self) {}
+
+    fn get_is_hydrating(&self) -> bool {
+        true
+    }
+
+    fn set_is_hydrating(&self, _is_hydrating: bool) {}
+
+    fn take_errors(&self) -> Vec<(SerializedDataId, ErrorId, Error)> {
+        Vec::new()
+    }
+
+    fn errors(&self, _boundary_id: &SerializedDataId) -> Vec<(ErrorId, Error)> {
+        Vec::new()
+    }
+
+    fn seal_errors(&self, _boundary_id: &SerializedDataId) {}
+
+    fn register_error(
+        &self,
+        _error_boundary: SerializedDataId,
+        _error_id: ErrorId,
+        _error: Error,
+    ) {
+    }
+
+    fn defer_stream(&self, _wait_for: PinnedFuture<()>) {}
+
+    fn await_deferred(&self) -> Option<PinnedFuture<()>> {
+        None
+    }
+
+    fn set_incomplete_chunk(&self, _id: SerializedDataId) {}
+
+    fn get_incomplete_chunk(&self, _id: &SerializedDataId) -> bool {
+        false
+    }
+}
@@ -0,0 +1,84 @@
+#![cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+
+use any_spawner::{Executor, TestExecutor};
+use futures::channel::oneshot;
+use leptos_server::FileStore;
+use reactive_graph::{
+    actions::{ArcMultiAction, SubmissionStore},
+    owner::Owner,
+};
+use std::{
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+type Responses = Arc<Mutex<Vec<(i32, oneshot::Sender<Result<i32, ()>>)>>>;
+
+// a path in the temporary directory that is unique to this test run
+fn temp_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir()
+        .join(format!("leptos_server_{name}_{}.json", std::process::id()));
+    _ = std::fs::remove_file(&path);
+    path
+}
+
+fn action_with(
+    store: FileStore<i32>,
+) -> (ArcMultiAction<i32, Result<i32, ()>>, Responses) {
+    let responses = Responses::default();
+    let action = ArcMultiAction::new({
+        let responses = Arc::clone(&responses);
+        move |n: &i32| {
+            let (tx, rx) = oneshot::channel();
+            responses.lock().unwrap().push((*n, tx));
+            async move { rx.await.unwrap_or(Err(())) }
+        }
+    });
+    action.set_online(false);
+    (action.with_offline_queue(store), responses)
+}
+
+#[test]
+fn file_store_keeps_submissions_until_they_are_replayed() {
+    _ = Executor::init_test_executor();
+    let owner = Owner::new();
+    owner.set();
+
+    let path = temp_path("submissions");
+    let (action, _) = action_with(FileStore::new(&path));
+    action.dispatch(1);
+    action.dispatch(2);
+    TestExecutor::run_until_stalled();
+    assert_eq!(FileStore::<i32>::new(&path).load(), vec![1, 2]);
+    drop(action);
+
+    // as if the app were restarted
+    let (action, responses) = action_with(FileStore::new(&path));
+    action.set_online(true);
+    TestExecutor::run_until_stalled();
+    for expected in [1, 2] {
+        let (n, tx) = responses.lock().unwrap().remove(0);
+        assert_eq!(n, expected);
+        _ = tx.send(Ok(n));
+        TestExecutor::run_until_stalled();
+    }
+
+    // once every submission has succeeded, the file is removed
+    assert!(!path.exists());
+    assert!(FileStore::<i32>::new(&path).load().is_empty());
+}
+
+#[test]
+fn file_store_ignores_a_corrupt_file() {
+    let path = temp_path("corrupt_submissions");
+    std::fs::write(&path, "[1, 2").unwrap();
+    let store = FileStore::<i32>::new(&path);
+    assert!(store.load().is_empty());
+
+    // saving replaces the corrupt file, in the same JSON format as `LocalStorageStore`
+    store.save(&[3]);
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "[3]");
+    assert_eq!(store.load(), vec![3]);
+    store.save(&[]);
+    assert!(!path.exists());
+}
@@ -1,3 +1,9 @@
+use super::{
+    optimistic::{Optimistic, OptimisticUpdate},
+    policy::{
+        until_sent, Admission, ConcurrencyPolicy, Retry, RetryPolicy, Scheduler,
+    },
+};
 use crate::{
     computed::{ArcMemo, Memo, ScopedFuture},
     diagnostics::is_suppressing_resource_load,
@@ -9,14 +15,15 @@ use crate::{
     unwrap_signal,
 };
 use any_spawner::Executor;
-use futures::{channel::oneshot, select, FutureExt};
+use futures::{channel::oneshot, future::Either, select, FutureExt};
+use or_poisoned::OrPoisoned;
 use send_wrapper::SendWrapper;
 use std::{
     future::Future,
     ops::{Deref, DerefMut},
     panic::Location,
     pin::Pin,
-    sync::Arc,
+    sync::{Arc, Mutex},
 };
 
 /// An action runs some asynchronous code when you dispatch a new value to it, and gives you
@@ -106,6 +113,10 @@ pub struct ArcAction<I, O> {
     action_fn: Arc<
         dyn Fn(&I) -> Pin<Box<dyn Future<Output = O> + Send>> + Send + Sync,
     >,
+    optimistic: Option<Optimistic<I, O>>,
+    concurrency: ConcurrencyPolicy,
+    retry: Option<Retry<I, O>>,
+    scheduler: Arc<Mutex<Scheduler>>,
     #[cfg(any(debug_assertions, leptos_debuginfo))]
     defined_at: &'static Location<'static>,
 }
@@ -119,6 +130,10 @@ impl<I, O> Clone for ArcAction<I, O> {
             version: self.version.clone(),
             dispatched: self.dispatched.clone(),
             action_fn: self.action_fn.clone(),
+            optimistic: self.optimistic.clone(),
+            concurrency: self.concurrency,
+            retry: self.retry.clone(),
+            scheduler: Arc::clone(&self.scheduler),
             #[cfg(any(debug_assertions, leptos_debuginfo))]
             defined_at: self.defined_at,
         }
@@ -212,6 +227,10 @@ where
                     ScopedFuture::new_untracked(untrack(|| action_fn(input)))
                 }))
             }),
+            optimistic: None,
+            concurrency: ConcurrencyPolicy::default(),
+            retry: None,
+            scheduler: Default::default(),
             #[cfg(any(debug_assertions, leptos_debuginfo))]
             defined_at: Location::caller(),
         }
@@ -227,6 +246,114 @@ where
             **guard = None;
         }
     }
+
+    /// Sets how the action handles being dispatched while an earlier dispatch is still pending.
+    ///
+    /// By default, every dispatch runs right away. See [`ConcurrencyPolicy`] for the alternatives.
+    ///
+    /// ```rust
+    /// # use reactive_graph::actions::*;
+    /// # use reactive_graph::prelude::*;
+    /// # tokio_test::block_on(async move {
+    /// # any_spawner::Executor::init_tokio(); let owner = reactive_graph::owner::Owner::new(); owner.set();
+    /// # let _guard = reactive_graph::diagnostics::SpecialNonReactiveZone::enter();
+    /// let search = ArcAction::new(|query: &String| {
+    ///     let query = query.clone();
+    ///     async move {
+    ///         # tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    ///         query.len()
+    ///     }
+    /// })
+    /// .with_concurrency(ConcurrencyPolicy::LatestWins);
+    ///
+    /// // the first search is canceled by the second one
+    /// search.dispatch("lept".to_string());
+    /// search.dispatch("leptos".to_string());
+    ///
+    /// # tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    /// assert_eq!(search.value().get(), Some(6));
+    /// assert_eq!(search.version().get(), 1);
+    /// # });
+    /// ```
+    pub fn with_concurrency(mut self, policy: ConcurrencyPolicy) -> Self {
+        self.concurrency = policy;
+        self
+    }
+}
+
+impl<I, T, E> ArcAction<I, Result<T, E>>
+where
+    I: 'static,
+    T: 'static,
+    E: 'static,
+{
+    /// Sets a function that makes provisional changes to signals or stores whenever the action is
+    /// dispatched, so that the expected result can be shown before the action resolves.
+    ///
+    /// If the action resolves to an `Err(_)`, or is aborted with its [`ActionAbortHandle`], the
+    /// changes made by that dispatch are rolled back. See [`OptimisticUpdate`] for how each kind of
+    /// change is rolled back.
+    ///
+    /// ```rust
+    /// # use reactive_graph::actions::*;
+    /// # use reactive_graph::prelude::*;
+    /// # use reactive_graph::signal::ArcRwSignal;
+    /// # tokio_test::block_on(async move {
+    /// # any_spawner::Executor::init_tokio(); let owner = reactive_graph::owner::Owner::new(); owner.set();
+    /// # let _guard = reactive_graph::diagnostics::SpecialNonReactiveZone::enter();
+    /// let todos = ArcRwSignal::new(vec!["Buy milk".to_string()]);
+    /// let add_todo = ArcAction::new(|task: &String| {
+    ///     let task = task.clone();
+    ///     async move { Err::<(), _>(format!("couldn't save {task}")) }
+    /// })
+    /// .with_optimistic_update({
+    ///     let todos = todos.clone();
+    ///     move |task, update| {
+    ///         let added = task.clone();
+    ///         update.update(
+    ///             &todos,
+    ///             |todos| todos.push(task.clone()),
+    ///             move |todos| {
+    ///                 if let Some(index) = todos.iter().rposition(|todo| *todo == added) {
+    ///                     todos.remove(index);
+    ///                 }
+    ///             },
+    ///         )
+    ///     }
+    /// });
+    ///
+    /// add_todo.dispatch("Walk the dog".to_string());
+    /// assert_eq!(todos.get().len(), 2);
+    ///
+    /// # any_spawner::Executor::tick().await;
+    /// // saving the new todo failed, so it has been removed again
+    /// assert_eq!(todos.get().len(), 1);
+    /// # });
+    /// ```
+    pub fn with_optimistic_update(
+        mut self,
+        update: impl Fn(&I, &mut OptimisticUpdate) + Send + Sync + 'static,
+    ) -> Self {
+        self.optimistic = Some(Optimistic::new(update));
+        self
+    }
+}
+
+impl<I, T, E> ArcAction<I, Result<T, E>>
+where
+    I: Clone + 'static,
+    T: 'static,
+    E: 'static,
+{
+    /// Sets how the action retries a dispatch that resolves to an `Err(_)`.
+    ///
+    /// Each retry calls the action function again with a copy of the input. The dispatch remains
+    /// pending until it succeeds or runs out of retries, and only its final result updates the
+    /// value and version of the action.
+    pub fn with_retry(mut self, policy: RetryPolicy) -> Self {
+        self.retry = Some(Retry::new(policy));
+        self
+    }
 }
 
 /// A handle that allows aborting an in-flight action. It is returned from [`Action::dispatch`] or
@@ -255,46 +382,11 @@ where
     /// Calls the `async` function with a reference to the input type as its argument.
     #[track_caller]
     pub fn dispatch(&self, input: I) -> ActionAbortHandle {
-        let (abort_tx, mut abort_rx) = oneshot::channel();
-        if !is_suppressing_resource_load() {
-            let mut fut = (self.action_fn)(&input).fuse();
-
-            // Update the state before loading
-            self.in_flight.update(|n| *n += 1);
-            let current_version = self.dispatched.get_value();
-            self.input.try_update(|inp| **inp = Some(input));
-
-            // Spawn the task
-            crate::spawn({
-                let input = self.input.clone();
-                let version = self.version.clone();
-                let dispatched = self.dispatched.clone();
-                let value = self.value.clone();
-                let in_flight = self.in_flight.clone();
-                async move {
-                    select! {
-                        // if the abort message has been sent, bail and do nothing
-                        _ = abort_rx => {
-                            in_flight.update(|n| *n = n.saturating_sub(1));
-                        },
-                        // otherwise, update the value
-                        result = fut => {
-                            in_flight.update(|n| *n = n.saturating_sub(1));
-                            let is_latest = dispatched.get_value() <= current_version;
-                            if is_latest {
-                                version.update(|n| *n += 1);
-                                value.update(|n| **n = Some(result));
-                            }
-                        }
-                    }
-                    if in_flight.get_untracked() == 0 {
-                        input.update(|inp| **inp = None);
-                    }
-                }
-            });
+        let (handle, task) = self.schedule(input);
+        if let Some(task) = task {
+            crate::spawn(task);
         }
-
-        ActionAbortHandle(abort_tx)
+        handle
     }
 }
 
@@ -307,45 +399,141 @@ where
     /// ensuring that it is spawned on the current thread.
     #[track_caller]
     pub fn dispatch_local(&self, input: I) -> ActionAbortHandle {
-        let (abort_tx, mut abort_rx) = oneshot::channel();
-        if !is_suppressing_resource_load() {
-            let mut fut = (self.action_fn)(&input).fuse();
+        let (handle, task) = self.schedule(input);
+        if let Some(task) = task {
+            Executor::spawn_local(task);
+        }
+        handle
+    }
+
+    /// Accepts or drops a new dispatch, according to the concurrency policy, and returns the task
+    /// that runs it if it was accepted.
+    #[track_caller]
+    fn schedule(
+        &self,
+        input: I,
+    ) -> (ActionAbortHandle, Option<impl Future<Output = ()>>) {
+        let (abort_tx, abort_rx) = oneshot::channel();
+        let handle = ActionAbortHandle(abort_tx);
+        if is_suppressing_resource_load() {
+            return (handle, None);
+        }
+        let (start, canceled) =
+            match self.scheduler.lock().or_poisoned().admit(self.concurrency) {
+                Admission::Run { canceled } => (None, canceled),
+                Admission::Wait { start } => (Some(start), None),
+                Admission::Drop => return (handle, None),
+            };
+
+        let retry_input =
+            self.retry.as_ref().map(|retry| retry.clone_input(&input));
+        let pending_update = self
+            .optimistic
+            .as_ref()
+            .map(|optimistic| optimistic.apply(&input));
 
-            // Update the state before loading
-            self.in_flight.update(|n| *n += 1);
-            let current_version = self.dispatched.get_value();
-            self.input.try_update(|inp| **inp = Some(input));
+        // Update the state before loading
+        self.in_flight.update(|n| *n += 1);
+        let current_version = self.dispatched.get_value();
 
-            // Spawn the task
-            Executor::spawn_local({
-                let input = self.input.clone();
-                let version = self.version.clone();
-                let value = self.value.clone();
-                let dispatched = self.dispatched.clone();
-                let in_flight = self.in_flight.clone();
-                async move {
-                    select! {
-                        // if the abort message has been sent, bail and do nothing
-                        _ = abort_rx => {
-                            in_flight.update(|n| *n = n.saturating_sub(1));
-                        },
-                        // otherwise, update the value
-                        result = fut => {
-                            in_flight.update(|n| *n = n.saturating_sub(1));
-                            let is_latest = dispatched.get_value() <= current_version;
-                            if is_latest {
-                                version.update(|n| *n += 1);
-                                value.update(|n| **n = Some(result));
-                            }
+        // a dispatch that runs right away starts now, while one that has to wait for an earlier
+        // dispatch keeps its input until it starts
+        let first_run = match start {
+            None => {
+                let fut = (self.action_fn)(&input);
+                self.input.try_update(|inp| **inp = Some(input));
+                Either::Left(fut)
+            }
+            Some(start) => Either::Right((start, input)),
+        };
+
+        let input = self.input.clone();
+        let version = self.version.clone();
+        let dispatched = self.dispatched.clone();
+        let value = self.value.clone();
+        let in_flight = self.in_flight.clone();
+        let action_fn = Arc::clone(&self.action_fn);
+        let retry = self.retry.clone();
+        let scheduler = Arc::clone(&self.scheduler);
+        let task = async move {
+            let mut aborted = Box::pin(until_sent(Some(abort_rx))).fuse();
+            let mut canceled = Box::pin(until_sent(canceled)).fuse();
+
+            let fut = match first_run {
+                Either::Left(fut) => fut,
+                Either::Right((start, first_input)) => {
+                    let mut start =
+                        Box::pin(async move { start.await.is_ok() }).fuse();
+                    let started = select! {
+                        started = start => started,
+                        _ = aborted => false,
+                    };
+                    if !started {
+                        // the dispatch was aborted, or replaced by a later one, while waiting
+                        in_flight.update(|n| *n = n.saturating_sub(1));
+                        if let Some(pending_update) = pending_update {
+                            pending_update.abort();
                         }
+                        if in_flight.get_untracked() == 0 {
+                            input.update(|inp| **inp = None);
+                        }
+                        return;
                     }
-                    if in_flight.get_untracked() == 0 {
-                        input.update(|inp| **inp = None);
+                    let fut = action_fn(&first_input);
+                    input.try_update(|inp| **inp = Some(first_input));
+                    fut
+                }
+            };
+
+            let mut run = Box::pin(async {
+                let mut result = fut.await;
+                if let (Some(retry), Some(retry_input)) = (&retry, &retry_input)
+                {
+                    let mut retries = 0;
+                    while retries < retry.policy.max_retries
+                        && retry.failed(&result)
+                    {
+                        Executor::sleep(retry.policy.delay(retries)).await;
+                        retries += 1;
+                        result = action_fn(retry_input).await;
                     }
                 }
-            });
-        }
-        ActionAbortHandle(abort_tx)
+                result
+            })
+            .fuse();
+            let result = select! {
+                // if the abort message has been sent, or a later dispatch has canceled this one,
+                // bail and do nothing
+                _ = aborted => None,
+                _ = canceled => None,
+                // otherwise, update the value
+                result = run => Some(result),
+            };
+
+            in_flight.update(|n| *n = n.saturating_sub(1));
+            scheduler.lock().or_poisoned().finish();
+            match result {
+                Some(result) => {
+                    if let Some(pending_update) = pending_update {
+                        pending_update.resolve(&result);
+                    }
+                    let is_latest = dispatched.get_value() <= current_version;
+                    if is_latest {
+                        version.update(|n| *n += 1);
+                        value.update(|n| **n = Some(result));
+                    }
+                }
+                None => {
+                    if let Some(pending_update) = pending_update {
+                        pending_update.abort();
+                    }
+                }
+            }
+            if in_flight.get_untracked() == 0 {
+                input.update(|inp| **inp = None);
+            }
+        };
+        (handle, Some(task))
     }
 }
 
@@ -389,6 +577,10 @@ where
                     ScopedFuture::new_untracked(untrack(|| action_fn(input)))
                 })))
             }),
+            optimistic: None,
+            concurrency: ConcurrencyPolicy::default(),
+            retry: None,
+            scheduler: Default::default(),
             #[cfg(any(debug_assertions, leptos_debuginfo))]
             defined_at: Location::caller(),
         }
@@ -731,6 +923,57 @@ where
     pub fn clear(&self) {
         self.inner.try_with_value(|inner| inner.clear());
     }
+
+    /// Sets how the action handles being dispatched while an earlier dispatch is still pending.
+    ///
+    /// See [`ArcAction::with_concurrency`].
+    pub fn with_concurrency(self, policy: ConcurrencyPolicy) -> Self {
+        self.inner
+            .try_update_value(|inner| inner.concurrency = policy);
+        self
+    }
+}
+
+impl<I, T, E> Action<I, Result<T, E>>
+where
+    I: 'static,
+    T: 'static,
+    E: 'static,
+{
+    /// Sets a function that makes provisional changes to signals or stores whenever the action is
+    /// dispatched, so that the expected result can be shown before the action resolves.
+    ///
+    /// If the action resolves to an `Err(_)`, or is aborted with its [`ActionAbortHandle`], the
+    /// changes made by that dispatch are rolled back. See [`OptimisticUpdate`] for how each kind of
+    /// change is rolled back.
+    ///
+    /// See [`ArcAction::with_optimistic_update`].
+    pub fn with_optimistic_update(
+        self,
+        update: impl Fn(&I, &mut OptimisticUpdate) + Send + Sync + 'static,
+    ) -> Self {
+        self.inner.try_update_value(|inner| {
+            inner.optimistic = Some(Optimistic::new(update));
+        });
+        self
+    }
+}
+
+impl<I, T, E> Action<I, Result<T, E>>
+where
+    I: Clone + 'static,
+    T: 'static,
+    E: 'static,
+{
+    /// Sets how the action retries a dispatch that resolves to an `Err(_)`.
+    ///
+    /// See [`ArcAction::with_retry`].
+    pub fn with_retry(self, policy: RetryPolicy) -> Self {
+        self.inner.try_update_value(|inner| {
+            inner.retry = Some(Retry::new(policy));
+        });
+        self
+    }
 }
 
 impl<I, O> Action<I, O>
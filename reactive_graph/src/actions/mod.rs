@@ -2,5 +2,11 @@
 
 mod action;
 mod multi_action;
+mod offline;
+mod optimistic;
+mod policy;
 pub use action::*;
 pub use multi_action::*;
+pub use offline::{MemoryStore, SubmissionStore};
+pub use optimistic::OptimisticUpdate;
+pub use policy::{ConcurrencyPolicy, RetryPolicy};
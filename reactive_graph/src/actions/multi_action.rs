@@ -1,11 +1,27 @@
+use super::{
+    offline::{OfflineQueue, RunSubmission, SubmissionStore},
+    optimistic::{Optimistic, OptimisticUpdate},
+    policy::{
+        until_sent, Admission, ConcurrencyPolicy, RetryPolicy, Scheduler,
+    },
+};
 use crate::{
     diagnostics::is_suppressing_resource_load,
     owner::{ArenaItem, FromLocal, LocalStorage, Storage, SyncStorage},
     signal::{ArcReadSignal, ArcRwSignal, ReadSignal, RwSignal},
-    traits::{DefinedAt, Dispose, GetUntracked, Set, Update},
+    traits::{DefinedAt, Dispose, GetUntracked, Set, Update, WithUntracked},
     unwrap_signal,
 };
-use std::{fmt::Debug, future::Future, panic::Location, pin::Pin, sync::Arc};
+use any_spawner::Executor;
+use futures::{future::Either, select, FutureExt};
+use or_poisoned::OrPoisoned;
+use std::{
+    fmt::Debug,
+    future::Future,
+    panic::Location,
+    pin::Pin,
+    sync::{Arc, Mutex},
+};
 
 /// An action that synchronizes multiple imperative `async` calls to the reactive system,
 /// tracking the progress of each one.
@@ -142,6 +158,15 @@ where
     O: Send + Sync + 'static,
     S: Storage<ArcMultiAction<I, O>>,
 {
+    /// Sets how the action handles being dispatched while earlier submissions are still pending.
+    ///
+    /// See [`ArcMultiAction::with_concurrency`].
+    pub fn with_concurrency(self, policy: ConcurrencyPolicy) -> Self {
+        self.inner
+            .try_update_value(|inner| inner.concurrency = policy);
+        self
+    }
+
     /// Calls the `async` function with a reference to the input type as its argument.
     ///
     /// This can be called any number of times: each submission will be dispatched, running
@@ -233,6 +258,85 @@ where
         self.inner
             .try_with_value(|inner| inner.dispatch_sync(value));
     }
+
+    /// Runs the submissions that are queued or have failed, one at a time and in order, if the
+    /// action has an offline queue and is online.
+    ///
+    /// See [`ArcMultiAction::replay`].
+    pub fn replay(&self) {
+        self.inner.try_with_value(|inner| inner.replay());
+    }
+
+    /// Sets whether the action is able to run its submissions.
+    ///
+    /// See [`ArcMultiAction::set_online`].
+    pub fn set_online(&self, online: bool) {
+        self.inner.try_with_value(|inner| inner.set_online(online));
+    }
+}
+
+impl<I, T, E, S> MultiAction<I, Result<T, E>, S>
+where
+    I: 'static,
+    T: 'static,
+    E: 'static,
+    S: Storage<ArcMultiAction<I, Result<T, E>>>,
+{
+    /// Sets a function that makes provisional changes to signals or stores whenever the action is
+    /// dispatched, so that the expected result can be shown before each submission resolves.
+    ///
+    /// See [`ArcMultiAction::with_optimistic_update`].
+    pub fn with_optimistic_update(
+        self,
+        update: impl Fn(&I, &mut OptimisticUpdate) + Send + Sync + 'static,
+    ) -> Self {
+        self.inner.try_update_value(|inner| {
+            inner.optimistic = Some(Optimistic::new(update));
+        });
+        self
+    }
+}
+
+impl<I, T, E, S> MultiAction<I, Result<T, E>, S>
+where
+    I: Clone + Send + Sync + 'static,
+    T: Send + Sync + 'static,
+    E: Send + Sync + 'static,
+    S: Storage<ArcMultiAction<I, Result<T, E>>>,
+{
+    /// Sets how the action retries a submission that resolves to an `Err(_)`.
+    ///
+    /// See [`ArcMultiAction::with_retry`].
+    pub fn with_retry(self, policy: RetryPolicy) -> Self {
+        self.inner.try_update_value(|inner| inner.set_retry(policy));
+        self
+    }
+
+    /// Persists the submissions that have not succeeded yet in `store`, so that they can be
+    /// replayed in order when the action is back online.
+    ///
+    /// See [`ArcMultiAction::with_offline_queue`].
+    pub fn with_offline_queue(
+        self,
+        store: impl SubmissionStore<I> + 'static,
+    ) -> Self {
+        self.with_offline_queue_retrying_if(store, |_| true)
+    }
+
+    /// Like [`with_offline_queue`](MultiAction::with_offline_queue), but only the errors for
+    /// which `should_retry` returns `true` hold up the queue until they are replayed.
+    ///
+    /// See [`ArcMultiAction::with_offline_queue_retrying_if`].
+    pub fn with_offline_queue_retrying_if(
+        self,
+        store: impl SubmissionStore<I> + 'static,
+        should_retry: impl Fn(&E) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.inner.try_update_value(|inner| {
+            inner.set_offline_queue(OfflineQueue::new(store, should_retry));
+        });
+        self
+    }
 }
 
 impl<I, O> MultiAction<I, O>
@@ -317,6 +421,14 @@ where
             .unwrap_or_else(unwrap_signal!(self))
             .into()
     }
+
+    /// Whether the action is online. See [`ArcMultiAction::set_online`].
+    pub fn online(&self) -> ReadSignal<bool> {
+        self.inner
+            .try_with_value(|inner| inner.online())
+            .unwrap_or_else(unwrap_signal!(self))
+            .into()
+    }
 }
 
 /// An action that synchronizes multiple imperative `async` calls to the reactive system,
@@ -359,10 +471,12 @@ where
 pub struct ArcMultiAction<I, O> {
     version: ArcRwSignal<usize>,
     submissions: ArcRwSignal<Vec<ArcSubmission<I, O>>>,
-    #[allow(clippy::complexity)]
-    action_fn: Arc<
-        dyn Fn(&I) -> Pin<Box<dyn Future<Output = O> + Send>> + Send + Sync,
-    >,
+    action_fn: RunSubmission<I, O>,
+    optimistic: Option<Optimistic<I, O>>,
+    online: ArcRwSignal<bool>,
+    offline: Option<OfflineQueue<I, O>>,
+    concurrency: ConcurrencyPolicy,
+    scheduler: Arc<Mutex<Scheduler>>,
 }
 
 impl<I, O> Debug for ArcMultiAction<I, O>
@@ -388,6 +502,11 @@ where
             version: self.version.clone(),
             submissions: self.submissions.clone(),
             action_fn: Arc::clone(&self.action_fn),
+            optimistic: self.optimistic.clone(),
+            online: self.online.clone(),
+            offline: self.offline.clone(),
+            concurrency: self.concurrency,
+            scheduler: Arc::clone(&self.scheduler),
         }
     }
 }
@@ -434,8 +553,122 @@ impl<I, O> ArcMultiAction<I, O> {
             version: ArcRwSignal::new(0),
             submissions: ArcRwSignal::new(Vec::new()),
             action_fn,
+            optimistic: None,
+            online: ArcRwSignal::new(true),
+            offline: None,
+            concurrency: ConcurrencyPolicy::default(),
+            scheduler: Default::default(),
         }
     }
+
+    /// Sets how the action handles being dispatched while earlier submissions are still pending.
+    ///
+    /// By default, every submission runs right away. A submission that is dropped by the policy
+    /// is not added to the [submissions](ArcMultiAction::submissions) at all, and one that is
+    /// canceled by a later submission, or replaced while it is waiting, is marked as
+    /// [canceled](ArcSubmission::canceled).
+    ///
+    /// This has no effect on an action with an
+    /// [offline queue](ArcMultiAction::with_offline_queue), which always runs its submissions one
+    /// at a time.
+    pub fn with_concurrency(mut self, policy: ConcurrencyPolicy) -> Self {
+        self.concurrency = policy;
+        self
+    }
+}
+
+impl<I, T, E> ArcMultiAction<I, Result<T, E>>
+where
+    I: 'static,
+    T: 'static,
+    E: 'static,
+{
+    /// Sets a function that makes provisional changes to signals or stores whenever the action is
+    /// dispatched, so that the expected result can be shown before each submission resolves.
+    ///
+    /// If a submission resolves to an `Err(_)`, or has been [canceled](ArcSubmission::cancel)
+    /// by the time it resolves, the changes made when it was dispatched are rolled back. See
+    /// [`OptimisticUpdate`] for how each kind of change is rolled back.
+    pub fn with_optimistic_update(
+        mut self,
+        update: impl Fn(&I, &mut OptimisticUpdate) + Send + Sync + 'static,
+    ) -> Self {
+        self.optimistic = Some(Optimistic::new(update));
+        self
+    }
+}
+
+impl<I, T, E> ArcMultiAction<I, Result<T, E>>
+where
+    I: Clone + Send + Sync + 'static,
+    T: Send + Sync + 'static,
+    E: Send + Sync + 'static,
+{
+    /// Sets how the action retries a submission that resolves to an `Err(_)`.
+    ///
+    /// Each retry calls the action function again with a copy of the input. The submission
+    /// remains pending until it succeeds or runs out of retries, and only its final result is
+    /// stored as its value.
+    pub fn with_retry(mut self, policy: RetryPolicy) -> Self {
+        self.set_retry(policy);
+        self
+    }
+
+    fn set_retry(&mut self, policy: RetryPolicy) {
+        let action_fn = Arc::clone(&self.action_fn);
+        self.action_fn = Arc::new(move |input: &I| {
+            let action_fn = Arc::clone(&action_fn);
+            let input = input.clone();
+            Box::pin(async move {
+                let mut result = action_fn(&input).await;
+                let mut retries = 0;
+                while retries < policy.max_retries && result.is_err() {
+                    Executor::sleep(policy.delay(retries)).await;
+                    retries += 1;
+                    result = action_fn(&input).await;
+                }
+                result
+            }) as Pin<Box<dyn Future<Output = Result<T, E>> + Send>>
+        });
+    }
+
+    /// Persists the submissions that have not succeeded yet in `store`, so that they can be
+    /// replayed in order when the action is back [online](ArcMultiAction::set_online).
+    ///
+    /// With an offline queue, submissions run one at a time, in the order in which they were
+    /// dispatched. A submission dispatched while the action is offline stays
+    /// [queued](SubmissionState::Queued) until it comes back online. A submission that resolves to
+    /// an `Err(_)` is marked as [failed](SubmissionState::Failed), and holds up the submissions
+    /// behind it until it is replayed, either when the action comes back online or when
+    /// [`replay`](ArcMultiAction::replay) is called. To let some errors resolve the submission
+    /// instead, use [`with_offline_queue_retrying_if`](ArcMultiAction::with_offline_queue_retrying_if).
+    /// Canceling a submission that is waiting removes it from the queue.
+    ///
+    /// Any submissions that are already in `store`, for example from before the app was restarted,
+    /// are added to the queue right away. Optimistic updates are only applied to submissions that
+    /// are dispatched, not to those that are restored.
+    pub fn with_offline_queue(
+        self,
+        store: impl SubmissionStore<I> + 'static,
+    ) -> Self {
+        self.with_offline_queue_retrying_if(store, |_| true)
+    }
+
+    /// Like [`with_offline_queue`](ArcMultiAction::with_offline_queue), but only a submission
+    /// that resolves to an error for which `should_retry` returns `true` is marked as
+    /// [failed](SubmissionState::Failed) and holds up the queue until it is replayed.
+    ///
+    /// Any other error is permanent, like a validation error that would only fail again: the
+    /// submission is resolved with it, any optimistic update is rolled back, and the queue moves
+    /// on to the next submission.
+    pub fn with_offline_queue_retrying_if(
+        mut self,
+        store: impl SubmissionStore<I> + 'static,
+        should_retry: impl Fn(&E) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.set_offline_queue(OfflineQueue::new(store, should_retry));
+        self
+    }
 }
 
 impl<I, O> ArcMultiAction<I, O>
@@ -491,28 +724,108 @@ where
     /// ```
     pub fn dispatch(&self, input: I) {
         if !is_suppressing_resource_load() {
-            let fut = (self.action_fn)(&input);
+            if let Some(offline) = &self.offline {
+                let pending_update = self
+                    .optimistic
+                    .as_ref()
+                    .map(|optimistic| optimistic.apply(&input));
+                let submission = offline.enqueue(input, pending_update);
+                self.submissions.try_update(|subs| subs.push(submission));
+                self.replay();
+                return;
+            }
+
+            let (start, canceled) = match self
+                .scheduler
+                .lock()
+                .or_poisoned()
+                .admit(self.concurrency)
+            {
+                Admission::Run { canceled } => (None, canceled),
+                Admission::Wait { start } => (Some(start), None),
+                Admission::Drop => return,
+            };
+
+            // a submission that has to wait for earlier ones is only called once it starts
+            let run = match start {
+                None => Either::Left((self.action_fn)(&input)),
+                Some(start) => Either::Right(start),
+            };
+            let pending_update = self
+                .optimistic
+                .as_ref()
+                .map(|optimistic| optimistic.apply(&input));
 
             let submission = ArcSubmission {
                 input: ArcRwSignal::new(Some(input)),
                 value: ArcRwSignal::new(None),
                 pending: ArcRwSignal::new(true),
                 canceled: ArcRwSignal::new(false),
+                state: ArcRwSignal::new(if matches!(run, Either::Left(_)) {
+                    SubmissionState::InFlight
+                } else {
+                    SubmissionState::Queued
+                }),
             };
 
             self.submissions
                 .try_update(|subs| subs.push(submission.clone()));
 
             let version = self.version.clone();
+            let action_fn = Arc::clone(&self.action_fn);
+            let scheduler = Arc::clone(&self.scheduler);
 
             crate::spawn(async move {
-                let new_value = fut.await;
-                let canceled = submission.canceled.get_untracked();
-                if !canceled {
-                    submission.value.try_set(Some(new_value));
+                let fut = match run {
+                    Either::Left(fut) => Some(fut),
+                    Either::Right(start) => {
+                        if start.await.is_ok() {
+                            submission.state.try_set(SubmissionState::InFlight);
+                            submission.input.with_untracked(|input| {
+                                input.as_ref().map(|input| action_fn(input))
+                            })
+                        } else {
+                            // the submission was replaced by a later one while waiting
+                            None
+                        }
+                    }
+                };
+                let is_running = fut.is_some();
+                let new_value = match fut {
+                    // a submission canceled while waiting is not run at all
+                    Some(_) if submission.canceled.get_untracked() => None,
+                    Some(fut) => {
+                        let mut fut = fut.fuse();
+                        let mut canceled =
+                            Box::pin(until_sent(canceled)).fuse();
+                        select! {
+                            new_value = fut => Some(new_value),
+                            _ = canceled => None,
+                        }
+                    }
+                    None => None,
+                };
+                if is_running {
+                    scheduler.lock().or_poisoned().finish();
+                }
+                if new_value.is_none() {
+                    submission.canceled.try_set(true);
+                }
+
+                let new_value =
+                    new_value.filter(|_| !submission.canceled.get_untracked());
+                if let Some(pending_update) = pending_update {
+                    match &new_value {
+                        Some(new_value) => pending_update.resolve(new_value),
+                        None => pending_update.abort(),
+                    }
+                }
+                if new_value.is_some() {
+                    submission.value.try_set(new_value);
                 }
                 submission.input.try_set(None);
                 submission.pending.try_set(false);
+                submission.state.try_set(SubmissionState::Resolved);
                 version.try_update(|n| *n += 1);
             })
         }
@@ -565,12 +878,44 @@ where
             value: ArcRwSignal::new(Some(value)),
             pending: ArcRwSignal::new(false),
             canceled: ArcRwSignal::new(false),
+            state: ArcRwSignal::new(SubmissionState::Resolved),
         };
 
         self.submissions
             .try_update(|subs| subs.push(submission.clone()));
         self.version.try_update(|n| *n += 1);
     }
+
+    /// Runs the submissions that are queued or have failed, one at a time and in order, if the
+    /// action has an [offline queue](ArcMultiAction::with_offline_queue) and is online.
+    pub fn replay(&self) {
+        if let Some(offline) = &self.offline {
+            offline.replay(
+                self.online.clone(),
+                self.version.clone(),
+                Arc::clone(&self.action_fn),
+            );
+        }
+    }
+
+    /// Sets whether the action is able to run its submissions, for example because the device
+    /// has a network connection.
+    ///
+    /// While the action is offline, new submissions are queued rather than run, if it has an
+    /// [offline queue](ArcMultiAction::with_offline_queue). Coming back online replays them.
+    pub fn set_online(&self, online: bool) {
+        self.online.try_set(online);
+        if online {
+            self.replay();
+        }
+    }
+
+    fn set_offline_queue(&mut self, offline: OfflineQueue<I, O>) {
+        let restored = offline.restore();
+        self.submissions.try_update(|subs| subs.extend(restored));
+        self.offline = Some(offline);
+        self.replay();
+    }
 }
 
 impl<I, O> ArcMultiAction<I, O> {
@@ -637,6 +982,29 @@ impl<I, O> ArcMultiAction<I, O> {
     pub fn version(&self) -> ArcRwSignal<usize> {
         self.version.clone()
     }
+
+    /// Whether the action is online. See [`set_online`](ArcMultiAction::set_online).
+    pub fn online(&self) -> ArcReadSignal<bool> {
+        self.online.read_only()
+    }
+}
+
+/// Where a submission to a [`MultiAction`] is in its lifecycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SubmissionState {
+    /// The submission is waiting to run, because the action is offline or is still running
+    /// earlier submissions.
+    Queued,
+    /// The submission is running.
+    InFlight,
+    /// The submission resolved to an error, and is waiting to be replayed.
+    ///
+    /// Only multi-actions with an [offline queue](ArcMultiAction::with_offline_queue) replay
+    /// failed submissions. Other multi-actions mark every submission as resolved, whatever its
+    /// value.
+    Failed,
+    /// The submission has resolved, or has been canceled.
+    Resolved,
 }
 
 /// An action that has been submitted by dispatching it to a [`MultiAction`].
@@ -650,6 +1018,7 @@ pub struct ArcSubmission<I, O> {
     pending: ArcRwSignal<bool>,
     /// Controls this submission has been canceled.
     canceled: ArcRwSignal<bool>,
+    state: ArcRwSignal<SubmissionState>,
 }
 
 impl<I, O> ArcSubmission<I, O>
@@ -682,6 +1051,12 @@ where
         self.canceled.read_only()
     }
 
+    /// Whether this submission is queued, in flight, has failed, or has resolved.
+    #[track_caller]
+    pub fn state(&self) -> ArcReadSignal<SubmissionState> {
+        self.state.read_only()
+    }
+
     /// Cancels the submission. This will not necessarily prevent the `Future`
     /// from continuing to run, but it will update the returned value.
     #[track_caller]
@@ -690,6 +1065,34 @@ where
         // futures
         self.canceled.try_set(true);
     }
+
+    pub(super) fn queued(input: I) -> Self {
+        Self {
+            input: ArcRwSignal::new(Some(input)),
+            value: ArcRwSignal::new(None),
+            pending: ArcRwSignal::new(true),
+            canceled: ArcRwSignal::new(false),
+            state: ArcRwSignal::new(SubmissionState::Queued),
+        }
+    }
+
+    pub(super) fn start(&self) {
+        self.pending.try_set(true);
+        self.state.try_set(SubmissionState::InFlight);
+    }
+
+    /// Stores the value of the submission. A failed submission keeps its input, so that it can be
+    /// replayed.
+    pub(super) fn finish(&self, value: Option<O>, state: SubmissionState) {
+        if let Some(value) = value {
+            self.value.try_set(Some(value));
+        }
+        if state != SubmissionState::Failed {
+            self.input.try_set(None);
+        }
+        self.pending.try_set(false);
+        self.state.try_set(state);
+    }
 }
 
 impl<I, O> Clone for ArcSubmission<I, O> {
@@ -699,6 +1102,7 @@ impl<I, O> Clone for ArcSubmission<I, O> {
             value: self.value.clone(),
             pending: self.pending.clone(),
             canceled: self.canceled.clone(),
+            state: self.state.clone(),
         }
     }
 }
@@ -718,6 +1122,7 @@ where
     pending: RwSignal<bool>,
     /// Controls this submission has been canceled.
     canceled: RwSignal<bool>,
+    state: RwSignal<SubmissionState>,
 }
 
 impl<I, O> From<ArcSubmission<I, O>> for Submission<I, O>
@@ -731,12 +1136,14 @@ where
             value,
             pending,
             canceled,
+            state,
         } = value;
         Self {
             input: input.into(),
             value: value.into(),
             pending: pending.into(),
             canceled: canceled.into(),
+            state: state.into(),
         }
     }
 }
@@ -752,12 +1159,14 @@ where
             value,
             pending,
             canceled,
+            state,
         } = value;
         Self {
             input: RwSignal::from_local(input),
             value: RwSignal::from_local(value),
             pending: pending.into(),
             canceled: canceled.into(),
+            state: state.into(),
         }
     }
 }
@@ -798,6 +1207,12 @@ impl<I, O, S> Submission<I, O, S> {
         self.canceled.read_only()
     }
 
+    /// Whether this submission is queued, in flight, has failed, or has resolved.
+    #[track_caller]
+    pub fn state(&self) -> ReadSignal<SubmissionState> {
+        self.state.read_only()
+    }
+
     /// Cancels the submission. This will not necessarily prevent the `Future`
     /// from continuing to run, but it will update the returned value.
     #[track_caller]
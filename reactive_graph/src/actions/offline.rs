@@ -0,0 +1,319 @@
+use super::{
+    multi_action::{ArcSubmission, SubmissionState},
+    optimistic::PendingUpdate,
+};
+use crate::{
+    signal::ArcRwSignal,
+    traits::{GetUntracked, Update},
+};
+use or_poisoned::OrPoisoned;
+use std::{
+    collections::VecDeque,
+    fmt::Debug,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+};
+
+/// A backend that persists the submissions of a [`MultiAction`](super::MultiAction) that have not
+/// succeeded yet, so that they can be replayed later, even after the app has been restarted.
+///
+/// See [`ArcMultiAction::with_offline_queue`](super::ArcMultiAction::with_offline_queue).
+pub trait SubmissionStore<I>: Send + Sync {
+    /// Loads the inputs of the submissions that were waiting when they were last saved, in the
+    /// order in which they were dispatched.
+    fn load(&self) -> Vec<I>;
+
+    /// Replaces the stored inputs with the inputs of every submission that is queued or has
+    /// failed, in the order in which they were dispatched.
+    fn save(&self, inputs: &[I]);
+}
+
+/// A [`SubmissionStore`] that keeps submissions in memory.
+///
+/// Submissions stored in memory do not outlive the app, but they do outlive any single
+/// multi-action: clones of a `MemoryStore` share the same submissions.
+pub struct MemoryStore<I> {
+    inputs: Arc<Mutex<Vec<I>>>,
+}
+
+impl<I> MemoryStore<I> {
+    /// Creates an empty store.
+    pub fn new() -> Self {
+        Self {
+            inputs: Default::default(),
+        }
+    }
+}
+
+impl<I> Default for MemoryStore<I> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I> Clone for MemoryStore<I> {
+    fn clone(&self) -> Self {
+        Self {
+            inputs: Arc::clone(&self.inputs),
+        }
+    }
+}
+
+impl<I: Debug> Debug for MemoryStore<I> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MemoryStore")
+            .field("inputs", &self.inputs)
+            .finish()
+    }
+}
+
+impl<I> SubmissionStore<I> for MemoryStore<I>
+where
+    I: Clone + Send + Sync,
+{
+    fn load(&self) -> Vec<I> {
+        self.inputs.lock().or_poisoned().clone()
+    }
+
+    fn save(&self, inputs: &[I]) {
+        *self.inputs.lock().or_poisoned() = inputs.to_vec();
+    }
+}
+
+pub(crate) type RunSubmission<I, O> =
+    Arc<dyn Fn(&I) -> Pin<Box<dyn Future<Output = O> + Send>> + Send + Sync>;
+
+/// The submissions of a multi-action that have not succeeded yet, which are run one at a time, in
+/// order, while the action is online.
+pub(crate) struct OfflineQueue<I, O> {
+    store: Arc<dyn SubmissionStore<I>>,
+    clone_input: fn(&I) -> I,
+    // whether an output is a failure that should be replayed later
+    should_retry: Arc<dyn Fn(&O) -> bool + Send + Sync>,
+    state: Arc<Mutex<QueueState<I, O>>>,
+}
+
+impl<I, O> Clone for OfflineQueue<I, O> {
+    fn clone(&self) -> Self {
+        Self {
+            store: Arc::clone(&self.store),
+            clone_input: self.clone_input,
+            should_retry: Arc::clone(&self.should_retry),
+            state: Arc::clone(&self.state),
+        }
+    }
+}
+
+struct QueueState<I, O> {
+    entries: VecDeque<Entry<I, O>>,
+    replaying: bool,
+}
+
+struct Entry<I, O> {
+    input: I,
+    submission: ArcSubmission<I, O>,
+    update: Option<PendingUpdate<O>>,
+}
+
+impl<I, T, E> OfflineQueue<I, Result<T, E>>
+where
+    I: Clone + 'static,
+    T: 'static,
+    E: 'static,
+{
+    pub(crate) fn new(
+        store: impl SubmissionStore<I> + 'static,
+        should_retry: impl Fn(&E) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            store: Arc::new(store),
+            clone_input: I::clone,
+            should_retry: Arc::new(move |output: &Result<T, E>| {
+                output.as_ref().err().is_some_and(&should_retry)
+            }),
+            state: Arc::new(Mutex::new(QueueState {
+                entries: VecDeque::new(),
+                replaying: false,
+            })),
+        }
+    }
+}
+
+impl<I, O> OfflineQueue<I, O>
+where
+    I: 'static,
+    O: 'static,
+{
+    /// Queues the submissions that were stored by an earlier session, and returns them.
+    pub(crate) fn restore(&self) -> Vec<ArcSubmission<I, O>> {
+        let restored = self
+            .store
+            .load()
+            .into_iter()
+            .map(|input| {
+                let submission =
+                    ArcSubmission::queued((self.clone_input)(&input));
+                Entry {
+                    input,
+                    submission,
+                    update: None,
+                }
+            })
+            .collect::<Vec<_>>();
+        let submissions = restored
+            .iter()
+            .map(|entry| entry.submission.clone())
+            .collect();
+        self.state.lock().or_poisoned().entries.extend(restored);
+        submissions
+    }
+
+    /// Adds a newly-dispatched submission to the end of the queue, and returns it.
+    pub(crate) fn enqueue(
+        &self,
+        input: I,
+        update: Option<PendingUpdate<O>>,
+    ) -> ArcSubmission<I, O> {
+        let submission = ArcSubmission::queued((self.clone_input)(&input));
+        self.state.lock().or_poisoned().entries.push_back(Entry {
+            input,
+            submission: submission.clone(),
+            update,
+        });
+        self.save();
+        submission
+    }
+
+    /// Saves every submission that has not been canceled.
+    pub(crate) fn save(&self) {
+        let inputs = self
+            .state
+            .lock()
+            .or_poisoned()
+            .entries
+            .iter()
+            .filter(|entry| !entry.submission.canceled().get_untracked())
+            .map(|entry| (self.clone_input)(&entry.input))
+            .collect::<Vec<_>>();
+        self.store.save(&inputs);
+    }
+
+    /// Runs the waiting submissions one at a time, in order, until all of them have succeeded,
+    /// one of them fails, or the action goes offline.
+    pub(crate) fn replay(
+        &self,
+        online: ArcRwSignal<bool>,
+        version: ArcRwSignal<usize>,
+        run: RunSubmission<I, O>,
+    ) where
+        I: Send + Sync,
+        O: Send + Sync,
+    {
+        {
+            let mut state = self.state.lock().or_poisoned();
+            if state.replaying
+                || state.entries.is_empty()
+                || !online.get_untracked()
+            {
+                return;
+            }
+            state.replaying = true;
+        }
+
+        let queue = self.clone();
+        crate::spawn(async move {
+            while let Some((input, submission)) = queue.next(&online) {
+                submission.start();
+                let output = run(&input).await;
+                version.try_update(|n| *n += 1);
+                if !queue.complete(output) {
+                    break;
+                }
+            }
+        });
+    }
+
+    /// Takes the input of the next submission to run, skipping any that have been canceled. Stops
+    /// replaying if there are none, or if the action has gone offline.
+    fn next(
+        &self,
+        online: &ArcRwSignal<bool>,
+    ) -> Option<(I, ArcSubmission<I, O>)> {
+        let mut state = self.state.lock().or_poisoned();
+        let mut canceled = Vec::new();
+        while state
+            .entries
+            .front()
+            .is_some_and(|entry| entry.submission.canceled().get_untracked())
+        {
+            canceled.extend(state.entries.pop_front());
+        }
+        let next = match state.entries.front() {
+            Some(entry) if online.get_untracked() => Some((
+                (self.clone_input)(&entry.input),
+                entry.submission.clone(),
+            )),
+            _ => {
+                state.replaying = false;
+                None
+            }
+        };
+        drop(state);
+        if !canceled.is_empty() {
+            for entry in canceled {
+                entry.cancel();
+            }
+            self.save();
+        }
+        next
+    }
+
+    /// Resolves the submission at the front of the queue with its output. Returns `false` if it
+    /// failed and should be retried, which stops replaying and keeps it at the front of the
+    /// queue. Other failures are resolved like successes, and removed from the queue.
+    fn complete(&self, output: O) -> bool {
+        let mut state = self.state.lock().or_poisoned();
+        let Some(entry) = state.entries.front() else {
+            state.replaying = false;
+            return false;
+        };
+        if entry.submission.canceled().get_untracked() {
+            let entry = state.entries.pop_front().expect("checked above");
+            drop(state);
+            entry.cancel();
+            self.save();
+            true
+        } else if (self.should_retry)(&output) {
+            let submission = entry.submission.clone();
+            state.replaying = false;
+            drop(state);
+            submission.finish(Some(output), SubmissionState::Failed);
+            false
+        } else {
+            let entry = state.entries.pop_front().expect("checked above");
+            drop(state);
+            if let Some(update) = entry.update {
+                update.resolve(&output);
+            }
+            entry
+                .submission
+                .finish(Some(output), SubmissionState::Resolved);
+            self.save();
+            true
+        }
+    }
+}
+
+impl<I, O> Entry<I, O>
+where
+    I: 'static,
+    O: 'static,
+{
+    fn cancel(self) {
+        if let Some(update) = self.update {
+            update.abort();
+        }
+        self.submission.finish(None, SubmissionState::Resolved);
+    }
+}
@@ -0,0 +1,159 @@
+use crate::{
+    graph::untrack,
+    traits::{GetUntracked, Set, Update},
+};
+use std::{fmt::Debug, sync::Arc};
+
+/// Provisional changes made to signals or stores when an action is dispatched, before its result
+/// is known.
+///
+/// If the action fails or is aborted, the changes are rolled back in reverse order. Rolling back
+/// only undoes the changes made by that dispatch, so that the changes made by other dispatches
+/// that are still pending, and any other writes made in the meantime, are kept.
+///
+/// See [`ArcAction::with_optimistic_update`](super::ArcAction::with_optimistic_update).
+#[derive(Default)]
+pub struct OptimisticUpdate {
+    #[allow(clippy::type_complexity)]
+    rollbacks: Vec<Box<dyn FnOnce() + Send + Sync>>,
+}
+
+impl Debug for OptimisticUpdate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OptimisticUpdate")
+            .field("changes", &self.rollbacks.len())
+            .finish()
+    }
+}
+
+impl OptimisticUpdate {
+    /// Provisionally replaces the value of `target`.
+    ///
+    /// Rolling back restores the value it replaced, but only if `target` still holds `value`.
+    /// If it has been changed since, whether by a later dispatch or by any other write, rolling
+    /// back leaves it alone.
+    #[track_caller]
+    pub fn set<S, T>(&mut self, target: &S, value: T)
+    where
+        S: GetUntracked<Value = T>
+            + Set<Value = T>
+            + Update<Value = T>
+            + Clone
+            + Send
+            + Sync
+            + 'static,
+        T: Clone + PartialEq + Send + Sync + 'static,
+    {
+        let Some(previous) = target.try_get_untracked() else {
+            return;
+        };
+        target.set(value.clone());
+        let target = target.clone();
+        self.on_rollback(move || {
+            target.try_maybe_update(|current| {
+                let unchanged = *current == value;
+                if unchanged {
+                    *current = previous;
+                }
+                (unchanged, ())
+            });
+        });
+    }
+
+    /// Provisionally updates the value of `target` in place with `apply`.
+    ///
+    /// Rolling back calls `undo` on whatever the value of `target` is by then, so `undo` should
+    /// reverse only the change made by `apply`: for example, removing the item that `apply`
+    /// pushed, rather than restoring a copy of the whole list.
+    #[track_caller]
+    pub fn update<S, T>(
+        &mut self,
+        target: &S,
+        apply: impl FnOnce(&mut T),
+        undo: impl FnOnce(&mut T) + Send + Sync + 'static,
+    ) where
+        S: Update<Value = T> + Clone + Send + Sync + 'static,
+        T: 'static,
+    {
+        if target.try_update(apply).is_none() {
+            return;
+        }
+        let target = target.clone();
+        self.on_rollback(move || {
+            target.try_update(undo);
+        });
+    }
+
+    /// Registers a function that undoes some other provisional change.
+    ///
+    /// Like the `undo` function given to [`update`](OptimisticUpdate::update), this should only
+    /// reverse that change, as other changes may have been made since.
+    pub fn on_rollback(&mut self, fun: impl FnOnce() + Send + Sync + 'static) {
+        self.rollbacks.push(Box::new(fun));
+    }
+
+    fn rollback(self) {
+        for rollback in self.rollbacks.into_iter().rev() {
+            rollback();
+        }
+    }
+}
+
+/// The optimistic update function of an action, along with how to tell whether it failed.
+pub(crate) struct Optimistic<I, O> {
+    #[allow(clippy::type_complexity)]
+    apply: Arc<dyn Fn(&I, &mut OptimisticUpdate) + Send + Sync>,
+    failed: fn(&O) -> bool,
+}
+
+impl<I, O> Clone for Optimistic<I, O> {
+    fn clone(&self) -> Self {
+        Self {
+            apply: Arc::clone(&self.apply),
+            failed: self.failed,
+        }
+    }
+}
+
+impl<I, T, E> Optimistic<I, Result<T, E>> {
+    pub(crate) fn new(
+        apply: impl Fn(&I, &mut OptimisticUpdate) + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            apply: Arc::new(apply),
+            failed: Result::is_err,
+        }
+    }
+}
+
+impl<I, O> Optimistic<I, O> {
+    /// Applies the optimistic update for a newly-dispatched input.
+    pub(crate) fn apply(&self, input: &I) -> PendingUpdate<O> {
+        let mut update = OptimisticUpdate::default();
+        untrack(|| (self.apply)(input, &mut update));
+        PendingUpdate {
+            update,
+            failed: self.failed,
+        }
+    }
+}
+
+/// The optimistic changes made for a single dispatch, which are kept only if it succeeds.
+pub(crate) struct PendingUpdate<O> {
+    update: OptimisticUpdate,
+    failed: fn(&O) -> bool,
+}
+
+impl<O> PendingUpdate<O> {
+    /// Rolls back the changes if the action resolved to an error.
+    pub(crate) fn resolve(self, output: &O) {
+        if (self.failed)(output) {
+            self.update.rollback();
+        }
+    }
+
+    /// Rolls back the changes, because the action will not resolve.
+    pub(crate) fn abort(self) {
+        self.update.rollback();
+    }
+}
@@ -0,0 +1,197 @@
+use futures::channel::oneshot;
+use std::{collections::VecDeque, future::pending, time::Duration};
+
+/// How an action handles being dispatched while an earlier dispatch is still pending.
+///
+/// Dispatches that are waiting to run count as pending, so an action's `pending()` signal is
+/// `true` until every dispatch it has accepted has either run or been dropped.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ConcurrencyPolicy {
+    /// Every dispatch runs right away, concurrently with any that are still pending.
+    #[default]
+    Concurrent,
+    /// Every dispatch runs right away, and cancels any dispatches that are still pending, so that
+    /// only the most recent one can update the value.
+    LatestWins,
+    /// Dispatches run one at a time, in the order in which they were dispatched.
+    Queue,
+    /// Dispatches made while another is pending are dropped without running.
+    ///
+    /// This is what RxJS calls `exhaustMap`.
+    DropWhilePending,
+    /// Dispatches run one at a time. Of the dispatches made while one is pending, only the most
+    /// recent is kept, and runs once the pending one has finished; the others are dropped.
+    ///
+    /// This is like [`DropWhilePending`](ConcurrencyPolicy::DropWhilePending), except that the
+    /// last dispatch made while one is pending is not lost.
+    QueueLatest,
+}
+
+/// How an action retries a dispatch that resolves to an error.
+///
+/// The delay before each retry grows exponentially: the first retry waits `initial_delay`, and
+/// each later retry waits `multiplier` times as long as the one before, up to `max_delay`. The
+/// dispatch stays pending until it succeeds or runs out of retries, and only its final result
+/// updates the action's value and version.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    /// How many times a failed dispatch is retried.
+    pub max_retries: usize,
+    /// How long to wait before the first retry.
+    pub initial_delay: Duration,
+    /// The factor by which the delay grows after each retry.
+    pub multiplier: f64,
+    /// The longest delay between two retries.
+    pub max_delay: Duration,
+}
+
+impl RetryPolicy {
+    /// Retries up to `max_retries` times, waiting `initial_delay` before the first retry and
+    /// doubling the delay after each one.
+    pub fn exponential(max_retries: usize, initial_delay: Duration) -> Self {
+        Self {
+            max_retries,
+            initial_delay,
+            multiplier: 2.0,
+            max_delay: Duration::MAX,
+        }
+    }
+
+    /// Sets the longest delay between two retries.
+    pub fn with_max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// How long to wait before the given retry, counting from zero.
+    pub fn delay(&self, retry: usize) -> Duration {
+        let factor = self
+            .multiplier
+            .powi(i32::try_from(retry).unwrap_or(i32::MAX));
+        Duration::try_from_secs_f64(self.initial_delay.as_secs_f64() * factor)
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay)
+    }
+}
+
+/// The retry policy of an action, along with how to tell whether it failed.
+pub(crate) struct Retry<I, O> {
+    pub(crate) policy: RetryPolicy,
+    clone_input: fn(&I) -> I,
+    failed: fn(&O) -> bool,
+}
+
+impl<I, O> Clone for Retry<I, O> {
+    fn clone(&self) -> Self {
+        Self {
+            policy: self.policy,
+            clone_input: self.clone_input,
+            failed: self.failed,
+        }
+    }
+}
+
+impl<I: Clone, T, E> Retry<I, Result<T, E>> {
+    pub(crate) fn new(policy: RetryPolicy) -> Self {
+        Self {
+            policy,
+            clone_input: I::clone,
+            failed: Result::is_err,
+        }
+    }
+}
+
+impl<I, O> Retry<I, O> {
+    /// Keeps a copy of the input, which each retry is called with.
+    pub(crate) fn clone_input(&self, input: &I) -> I {
+        (self.clone_input)(input)
+    }
+
+    pub(crate) fn failed(&self, output: &O) -> bool {
+        (self.failed)(output)
+    }
+}
+
+/// Decides when each dispatch of an action runs, according to its [`ConcurrencyPolicy`].
+#[derive(Debug, Default)]
+pub(crate) struct Scheduler {
+    running: usize,
+    waiting: VecDeque<oneshot::Sender<()>>,
+    // cancels the dispatches that are running, for `ConcurrencyPolicy::LatestWins`
+    cancel_running: Vec<oneshot::Sender<()>>,
+}
+
+/// What happens to a new dispatch.
+pub(crate) enum Admission {
+    /// The dispatch runs right away, until it finishes or a message is sent to `canceled`.
+    Run {
+        canceled: Option<oneshot::Receiver<()>>,
+    },
+    /// The dispatch runs once a message is sent to `start`. If the sender is dropped instead, the
+    /// dispatch has been dropped.
+    Wait { start: oneshot::Receiver<()> },
+    /// The dispatch is dropped without running.
+    Drop,
+}
+
+impl Scheduler {
+    pub(crate) fn admit(&mut self, policy: ConcurrencyPolicy) -> Admission {
+        let is_busy = self.running > 0;
+        let canceled = match policy {
+            ConcurrencyPolicy::Concurrent => None,
+            ConcurrencyPolicy::LatestWins => {
+                for cancel in self.cancel_running.drain(..) {
+                    _ = cancel.send(());
+                }
+                let (tx, rx) = oneshot::channel();
+                self.cancel_running.push(tx);
+                Some(rx)
+            }
+            ConcurrencyPolicy::Queue if is_busy => {
+                let (tx, rx) = oneshot::channel();
+                self.waiting.push_back(tx);
+                return Admission::Wait { start: rx };
+            }
+            ConcurrencyPolicy::DropWhilePending if is_busy => {
+                return Admission::Drop;
+            }
+            ConcurrencyPolicy::QueueLatest if is_busy => {
+                // replaces the dispatch that was waiting, if any
+                self.waiting.clear();
+                let (tx, rx) = oneshot::channel();
+                self.waiting.push_back(tx);
+                return Admission::Wait { start: rx };
+            }
+            _ => None,
+        };
+        self.running += 1;
+        Admission::Run { canceled }
+    }
+
+    /// Marks a dispatch as no longer running, and starts the next one that is waiting, if any.
+    pub(crate) fn finish(&mut self) {
+        self.running = self.running.saturating_sub(1);
+        while let Some(start) = self.waiting.pop_front() {
+            // a dispatch that was aborted while waiting is skipped
+            if start.send(()).is_ok() {
+                self.running += 1;
+                break;
+            }
+        }
+    }
+}
+
+/// Resolves once a message is sent on the channel, but never if there is no channel or its
+/// sender is dropped without sending one.
+///
+/// A `select!` on the receiver itself skips it once its sender has been dropped, so dropping an
+/// [`ActionAbortHandle`](super::ActionAbortHandle) has never aborted a dispatch; this keeps that
+/// behavior.
+pub(crate) async fn until_sent(rx: Option<oneshot::Receiver<()>>) {
+    if let Some(rx) = rx {
+        if rx.await.is_ok() {
+            return;
+        }
+    }
+    pending().await
+}
@@ -5,6 +5,7 @@ mod async_derived;
 mod inner;
 mod memo;
 mod selector;
+mod set_selector;
 use crate::{
     prelude::*,
     signal::RwSignal,
@@ -17,6 +18,7 @@ pub use arc_memo::*;
 pub use async_derived::*;
 pub use memo::*;
 pub use selector::*;
+pub use set_selector::*;
 
 /// Derives a reactive slice of an [`RwSignal`].
 ///
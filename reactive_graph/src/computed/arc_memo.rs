@@ -154,17 +154,31 @@ where
     pub fn new_owning(
         fun: impl Fn(Option<T>) -> (T, bool) + Send + Sync + 'static,
     ) -> Self {
+        #[cfg(any(debug_assertions, leptos_debuginfo))]
+        let defined_at = Location::caller();
         let inner = Arc::new_cyclic(|weak| {
             let subscriber = AnySubscriber(
                 weak.as_ptr() as usize,
                 Weak::clone(weak) as Weak<dyn Subscriber + Send + Sync>,
             );
 
-            MemoInner::new(Arc::new(fun), subscriber)
+            MemoInner::new(
+                Arc::new(fun),
+                subscriber,
+                #[cfg(any(debug_assertions, leptos_debuginfo))]
+                defined_at,
+            )
         });
+        #[cfg(feature = "inspector")]
+        crate::inspector::register(
+            crate::inspector::NodeKind::Memo,
+            &inner,
+            std::any::type_name::<Self>(),
+            Some(Location::caller()),
+        );
         Self {
             #[cfg(any(debug_assertions, leptos_debuginfo))]
-            defined_at: Location::caller(),
+            defined_at,
             inner,
         }
     }
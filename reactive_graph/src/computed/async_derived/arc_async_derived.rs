@@ -9,8 +9,8 @@ use crate::{
     computed::suspense::SuspenseContext,
     diagnostics::SpecialNonReactiveFuture,
     graph::{
-        AnySource, AnySubscriber, ReactiveNode, Source, SourceSet, Subscriber,
-        SubscriberSet, ToAnySource, ToAnySubscriber, WithObserver,
+        untrack, AnySource, AnySubscriber, ReactiveNode, Source, SourceSet,
+        Subscriber, SubscriberSet, ToAnySource, ToAnySubscriber, WithObserver,
     },
     owner::{use_context, Owner},
     send_wrapper_ext::SendOption,
@@ -26,16 +26,21 @@ use crate::{
 };
 use async_lock::RwLock as AsyncRwLock;
 use core::fmt::Debug;
-use futures::{channel::oneshot, FutureExt, StreamExt};
+use futures::{
+    channel::oneshot,
+    future::{abortable, AbortHandle},
+    FutureExt, Stream, StreamExt,
+};
 use or_poisoned::OrPoisoned;
 use std::{
     future::Future,
     mem,
     ops::{Deref, DerefMut},
     panic::Location,
+    pin::Pin,
     sync::{
         atomic::{AtomicBool, Ordering},
-        Arc, RwLock, Weak,
+        Arc, Mutex, OnceLock, RwLock, Weak,
     },
     task::Waker,
 };
@@ -237,6 +242,13 @@ macro_rules! spawn_derived {
             suspenses: Vec::new(),
             pending_suspenses: Vec::new()
         }));
+        #[cfg(feature = "inspector")]
+        crate::inspector::register(
+            crate::inspector::NodeKind::AsyncDerived,
+            &inner,
+            std::any::type_name::<Self>(),
+            Some(Location::caller()),
+        );
         let value = Arc::new(AsyncRwLock::new($initial));
         let wakers = Arc::new(RwLock::new(Vec::new()));
 
@@ -310,6 +322,8 @@ macro_rules! spawn_derived {
                 let inner = Arc::downgrade(&this.inner);
                 let wakers = Arc::downgrade(&this.wakers);
                 let loading = Arc::downgrade(&this.loading);
+                #[cfg(feature = "tracing")]
+                let defined_at = this.defined_at();
                 let fut = async move {
                     // if the AsyncDerived has *already* been marked dirty (i.e., one of its
                     // sources has changed after creation), we should throw out the Future
@@ -324,7 +338,7 @@ macro_rules! spawn_derived {
                     }
 
                     while rx.next().await.is_some() {
-                        let update_if_necessary = !owner.paused() && if $should_track {
+                        let update_if_necessary = !owner.paused_for(&any_subscriber) && if $should_track {
                             any_subscriber
                                 .with_observer(|| any_subscriber.update_if_necessary())
                         } else {
@@ -377,6 +391,16 @@ macro_rules! spawn_derived {
                                         version
                                     };
 
+                                    #[cfg(feature = "tracing")]
+                                    let fut = tracing::Instrument::instrument(
+                                        fut,
+                                        crate::trace::span(
+                                            crate::trace::SpanKind::AsyncDerived,
+                                            any_subscriber.0,
+                                            Some(&owner),
+                                            defined_at,
+                                        ),
+                                    );
                                     let new_value = fut.await;
 
                                     let latest_version = {
@@ -597,6 +621,247 @@ impl<T: 'static> ArcAsyncDerived<T> {
     }
 }
 
+impl<T: Send + Sync + 'static> ArcAsyncDerived<Option<T>> {
+    /// Creates a new async derived computation from a [`Stream`], which holds the latest item
+    /// that the stream has yielded.
+    ///
+    /// This calls `fun` once when created, and again whenever a reactive value that it read while
+    /// creating the stream changes, dropping the previous stream. Only reading the first item is
+    /// treated as loading: it is what `.await`ing this, or reading it under a
+    /// [`Suspense`](crate::computed::suspense::SuspenseContext), waits for. Each later item
+    /// replaces the value as it arrives, and notifies subscribers, without suspending. If the
+    /// stream ends without yielding any items, it resolves to `None`.
+    ///
+    /// This is useful for live data, like a stream of server-sent events or websocket messages.
+    ///
+    /// ```rust
+    /// # use reactive_graph::computed::*;
+    /// # use reactive_graph::signal::*;
+    /// # use reactive_graph::prelude::*;
+    /// # use futures::channel::mpsc;
+    /// # tokio_test::block_on(async move {
+    /// # any_spawner::Executor::init_tokio(); let owner = reactive_graph::owner::Owner::new(); owner.set();
+    /// # let _guard = reactive_graph::diagnostics::SpecialNonReactiveZone::enter();
+    /// let (tx, rx) = mpsc::unbounded();
+    /// let rx = std::sync::Mutex::new(Some(rx));
+    /// let latest = ArcAsyncDerived::new_stream(move || {
+    ///     rx.lock().unwrap().take().expect("only called once")
+    /// });
+    ///
+    /// tx.unbounded_send(1).unwrap();
+    /// // waits for the first item
+    /// assert_eq!(latest.clone().await, Some(1));
+    ///
+    /// // later items replace the value as they arrive
+    /// tx.unbounded_send(2).unwrap();
+    /// # tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    /// assert_eq!(latest.get(), Some(Some(2)));
+    /// # });
+    /// ```
+    #[track_caller]
+    pub fn new_stream<St>(fun: impl Fn() -> St + Send + Sync + 'static) -> Self
+    where
+        St: Stream<Item = T> + Send + 'static,
+    {
+        let pump = StreamPump::new();
+        let fun = {
+            let pump = pump.clone();
+            move || {
+                let fut = pump.first_item(fun());
+                #[cfg(feature = "sandboxed-arenas")]
+                let fut = Sandboxed::new(fut);
+                fut
+            }
+        };
+        let initial_value = SendOption::new(None);
+        let (this, _) = spawn_derived!(
+            crate::spawn,
+            initial_value,
+            fun,
+            true,
+            true,
+            true,
+            None::<ArcTrigger>
+        );
+        pump.set_target(&this);
+        this
+    }
+
+    /// Creates a new async derived computation from a [`Stream`] with an initial value, which
+    /// only reruns when `source` changes.
+    ///
+    /// If the initial value is `Some(_)`, the stream is started immediately, and each of its
+    /// items (including the first) replaces the value without suspending.
+    #[doc(hidden)]
+    #[track_caller]
+    pub fn new_stream_with_manual_dependencies<St, S>(
+        initial_value: Option<Option<T>>,
+        fun: impl Fn() -> St + Send + Sync + 'static,
+        source: &S,
+    ) -> Self
+    where
+        St: Stream<Item = T> + Send + 'static,
+        S: Track,
+    {
+        let has_initial = initial_value.is_some();
+        let pump = StreamPump::new();
+        let fun = Arc::new(fun);
+        let first_item = {
+            let pump = pump.clone();
+            let fun = Arc::clone(&fun);
+            move || {
+                // the stream is only created once this is polled, which it is not if there is an
+                // initial value: that stream is created below instead
+                let pump = pump.clone();
+                let fun = Arc::clone(&fun);
+                let fut =
+                    ScopedFuture::new_untracked_with_diagnostics(async move {
+                        pump.first_item(fun()).await
+                    });
+                #[cfg(feature = "sandboxed-arenas")]
+                let fut = Sandboxed::new(fut);
+                fut
+            }
+        };
+        // the stream is created before `source` is tracked, so that running `fun` for the first
+        // time does not mark this as changed
+        let initial_stream = has_initial.then(|| untrack(|| Box::pin(fun())));
+        let initial_value = SendOption::new(initial_value);
+        let (this, _) = spawn_derived!(
+            crate::spawn,
+            initial_value,
+            first_item,
+            true,
+            false,
+            false,
+            Some(source)
+        );
+        pump.set_target(&this);
+        if let Some(stream) = initial_stream {
+            pump.pump(stream);
+        }
+        this
+    }
+}
+
+/// Writes the items of the latest stream of an async derived value created with
+/// [`ArcAsyncDerived::new_stream`] to it as they arrive, after the first item.
+struct StreamPump<T> {
+    target: Arc<OnceLock<WeakAsyncDerived<Option<T>>>>,
+    // aborts the task that writes the items of the latest stream; shared by the derived's
+    // function, and aborted when it is dropped
+    abort: Arc<StreamAbort>,
+}
+
+impl<T> Clone for StreamPump<T> {
+    fn clone(&self) -> Self {
+        Self {
+            target: Arc::clone(&self.target),
+            abort: Arc::clone(&self.abort),
+        }
+    }
+}
+
+#[derive(Default)]
+struct StreamAbort(Mutex<Option<AbortHandle>>);
+
+impl StreamAbort {
+    fn replace(&self, handle: Option<AbortHandle>) {
+        if let Some(prev) =
+            mem::replace(&mut *self.0.lock().or_poisoned(), handle)
+        {
+            prev.abort();
+        }
+    }
+}
+
+impl Drop for StreamAbort {
+    fn drop(&mut self) {
+        self.replace(None);
+    }
+}
+
+struct WeakAsyncDerived<T> {
+    value: Weak<AsyncRwLock<SendOption<T>>>,
+    wakers: Weak<RwLock<Vec<Waker>>>,
+    inner: Weak<RwLock<ArcAsyncDerivedInner>>,
+    loading: Weak<AtomicBool>,
+}
+
+impl<T: Send + Sync + 'static> StreamPump<T> {
+    fn new() -> Self {
+        Self {
+            target: Default::default(),
+            abort: Default::default(),
+        }
+    }
+
+    fn set_target(&self, derived: &ArcAsyncDerived<Option<T>>) {
+        _ = self.target.set(WeakAsyncDerived {
+            value: Arc::downgrade(&derived.value),
+            wakers: Arc::downgrade(&derived.wakers),
+            inner: Arc::downgrade(&derived.inner),
+            loading: Arc::downgrade(&derived.loading),
+        });
+    }
+
+    /// Stops writing the items of the previous stream, and returns a `Future` that resolves to
+    /// the first item of `stream` (or `None`, if it ends without any), after which its remaining
+    /// items are written as they arrive.
+    fn first_item(
+        &self,
+        stream: impl Stream<Item = T> + Send + 'static,
+    ) -> impl Future<Output = SendOption<Option<T>>> + Send + 'static {
+        self.abort.replace(None);
+        let pump = self.clone();
+        let mut stream = Box::pin(stream);
+        async move {
+            let first = stream.next().await;
+            if first.is_some() {
+                pump.pump(stream);
+            }
+            SendOption::new(Some(first))
+        }
+    }
+
+    /// Spawns a task that writes each item of `stream` to the derived value, until the stream
+    /// ends, the value is dropped, or another stream replaces it.
+    fn pump(
+        &self,
+        mut stream: Pin<Box<impl Stream<Item = T> + Send + 'static>>,
+    ) {
+        let target = Arc::clone(&self.target);
+        let (task, handle) = abortable(async move {
+            while let Some(item) = stream.next().await {
+                let Some(target) = target.get() else {
+                    break;
+                };
+                let (Some(value), Some(wakers), Some(inner), Some(loading)) = (
+                    target.value.upgrade(),
+                    target.wakers.upgrade(),
+                    target.inner.upgrade(),
+                    target.loading.upgrade(),
+                ) else {
+                    break;
+                };
+                ArcAsyncDerived::set_inner_value(
+                    SendOption::new(Some(Some(item))),
+                    value,
+                    wakers,
+                    inner,
+                    loading,
+                    None,
+                )
+                .await;
+            }
+        });
+        self.abort.replace(Some(handle));
+        crate::spawn(async move {
+            _ = task.await;
+        });
+    }
+}
+
 impl<T: 'static> ArcAsyncDerived<T> {
     #[doc(hidden)]
     #[track_caller]
@@ -14,6 +14,7 @@ use crate::{
     unwrap_signal,
 };
 use core::fmt::Debug;
+use futures::Stream;
 use or_poisoned::OrPoisoned;
 use std::{
     future::Future,
@@ -187,6 +188,29 @@ where
     }
 }
 
+impl<T> AsyncDerived<Option<T>>
+where
+    T: Send + Sync + 'static,
+{
+    /// Creates a new async derived computation from a [`Stream`], which holds the latest item
+    /// that the stream has yielded.
+    ///
+    /// See [`ArcAsyncDerived::new_stream`] for details.
+    #[track_caller]
+    pub fn new_stream<St>(fun: impl Fn() -> St + Send + Sync + 'static) -> Self
+    where
+        St: Stream<Item = T> + Send + 'static,
+    {
+        Self {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: Location::caller(),
+            inner: ArenaItem::new_with_storage(ArcAsyncDerived::new_stream(
+                fun,
+            )),
+        }
+    }
+}
+
 impl<T> AsyncDerived<T> {
     #[doc(hidden)]
     pub fn new_mock<Fut>(fun: impl Fn() -> Fut + 'static) -> Self
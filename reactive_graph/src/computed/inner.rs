@@ -1,3 +1,5 @@
+#[cfg(any(debug_assertions, leptos_debuginfo))]
+use crate::graph::{CycleNode, CycleNodeKind};
 use crate::{
     graph::{
         AnySource, AnySubscriber, Observer, ReactiveNode, ReactiveNodeState,
@@ -6,6 +8,8 @@ use crate::{
     owner::{Owner, Storage, StorageAccess},
 };
 use or_poisoned::OrPoisoned;
+#[cfg(any(debug_assertions, leptos_debuginfo, feature = "tracing"))]
+use std::panic::Location;
 use std::{
     fmt::Debug,
     sync::{Arc, RwLock, RwLockWriteGuard},
@@ -21,6 +25,8 @@ where
     pub(crate) fun: Arc<dyn Fn(Option<T>) -> (T, bool) + Send + Sync>,
     pub(crate) owner: Owner,
     pub(crate) reactivity: RwLock<MemoInnerReactivity>,
+    #[cfg(any(debug_assertions, leptos_debuginfo))]
+    pub(crate) defined_at: &'static Location<'static>,
 }
 
 pub(crate) struct MemoInnerReactivity {
@@ -47,6 +53,8 @@ where
     pub fn new(
         fun: Arc<dyn Fn(Option<T>) -> (T, bool) + Send + Sync>,
         any_subscriber: AnySubscriber,
+        #[cfg(any(debug_assertions, leptos_debuginfo))]
+        defined_at: &'static Location<'static>,
     ) -> Self {
         Self {
             value: Arc::new(RwLock::new(None)),
@@ -58,6 +66,20 @@ where
                 subscribers: SubscriberSet::new(),
                 any_subscriber,
             }),
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at,
+        }
+    }
+
+    #[cfg(feature = "tracing")]
+    fn defined_at(&self) -> Option<&'static Location<'static>> {
+        #[cfg(any(debug_assertions, leptos_debuginfo))]
+        {
+            Some(self.defined_at)
+        }
+        #[cfg(not(any(debug_assertions, leptos_debuginfo)))]
+        {
+            None
         }
     }
 }
@@ -80,9 +102,9 @@ where
                     lock.state = ReactiveNodeState::Check;
                 }
             }
-            for sub in
-                (&reactivity.read().or_poisoned().subscribers).into_iter()
-            {
+            // subscribers (like immediate effects) may read this memo as they are marked
+            let subs = reactivity.read().or_poisoned().subscribers.clone();
+            for sub in subs {
                 sub.mark_check();
             }
         }
@@ -90,13 +112,24 @@ where
     }
 
     fn mark_subscribers_check(&self) {
-        let lock = self.reactivity.read().or_poisoned();
-        for sub in (&lock.subscribers).into_iter() {
+        let subs = self.reactivity.read().or_poisoned().subscribers.clone();
+        for sub in subs {
             sub.mark_check();
         }
     }
 
     fn update_if_necessary(&self) -> bool {
+        // a memo that is updated again while it is already updating depends on itself
+        #[cfg(any(debug_assertions, leptos_debuginfo))]
+        let updating = crate::graph::start_updating(
+            self as *const Self as *const () as usize,
+            CycleNode {
+                kind: CycleNodeKind::Memo,
+                value_type: std::any::type_name::<T>(),
+                defined_at: self.defined_at,
+            },
+        );
+
         /// codegen optimisation:
         fn needs_update(reactivity: &RwLock<MemoInnerReactivity>) -> bool {
             let (state, sources) = {
@@ -131,6 +164,15 @@ where
             }
             let any_subscriber = inner_1(&self.reactivity);
 
+            #[cfg(feature = "tracing")]
+            let _span = crate::trace::span(
+                crate::trace::SpanKind::Memo,
+                any_subscriber.0,
+                Some(&self.owner),
+                self.defined_at(),
+            )
+            .entered();
+
             let (new_value, changed) = self.owner.with_cleanup(|| {
                 any_subscriber.with_observer(|| {
                     (self.fun)(value.map(StorageAccess::into_taken))
@@ -145,6 +187,11 @@ where
                 *value_lock = Some(S::wrap(new_value));
             }
 
+            // the memo has finished updating, so its subscribers can read it again while they
+            // are notified
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            drop(updating);
+
             /// codegen optimisation:
             fn inner_2(
                 changed: bool,
@@ -0,0 +1,237 @@
+use crate::{
+    signal::ArcTrigger,
+    traits::{Notify, Track},
+};
+use or_poisoned::OrPoisoned;
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::{
+    fmt::Debug,
+    hash::Hash,
+    sync::{Arc, RwLock},
+};
+
+/// A reactive set of selected keys that only notifies the subscribers of a key when that key is
+/// selected or deselected.
+///
+/// This is the multi-selection counterpart of a [`Selector`](super::Selector): checking whether
+/// each row of a large table is selected with [`selected`](SetSelector::selected) means that
+/// selecting or deselecting one row only notifies that row, rather than every row. Changing many
+/// keys at once with [`select_all`](SetSelector::select_all) or [`clear`](SetSelector::clear)
+/// notifies each affected row once.
+///
+/// Reading the whole selection, with [`len`](SetSelector::len) or [`keys`](SetSelector::keys),
+/// subscribes to every change.
+///
+/// ```
+/// # use reactive_graph::computed::*;
+/// # use reactive_graph::prelude::*;
+/// # use reactive_graph::owner::StoredValue; let owner = reactive_graph::owner::Owner::new(); owner.set();
+/// let selection = SetSelector::new();
+/// let row_runs = StoredValue::new(0);
+/// let row_3 = Memo::new({
+///     let selection = selection.clone();
+///     move |_| {
+///         row_runs.update_value(|n| *n += 1);
+///         selection.selected(&3)
+///     }
+/// });
+///
+/// assert_eq!(row_3.get(), false);
+/// assert_eq!(row_runs.get_value(), 1);
+///
+/// // selecting another row does not notify row 3
+/// selection.select(1);
+/// assert_eq!(row_3.get(), false);
+/// assert_eq!(row_runs.get_value(), 1);
+///
+/// selection.toggle(3);
+/// assert_eq!(row_3.get(), true);
+/// assert_eq!(row_runs.get_value(), 2);
+/// assert_eq!(selection.len(), 2);
+/// ```
+pub struct SetSelector<K> {
+    inner: Arc<RwLock<SetSelectorInner<K>>>,
+    // notified whenever any key is selected or deselected
+    changed: ArcTrigger,
+}
+
+struct SetSelectorInner<K> {
+    selected: FxHashSet<K>,
+    subs: FxHashMap<K, ArcTrigger>,
+}
+
+impl<K> Clone for SetSelector<K> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+            changed: self.changed.clone(),
+        }
+    }
+}
+
+impl<K: Debug> Debug for SetSelector<K> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SetSelector")
+            .field("selected", &self.inner.read().or_poisoned().selected)
+            .finish()
+    }
+}
+
+impl<K> Default for SetSelector<K>
+where
+    K: Eq + Hash + Clone + Send + Sync + 'static,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K> FromIterator<K> for SetSelector<K>
+where
+    K: Eq + Hash + Clone + Send + Sync + 'static,
+{
+    fn from_iter<T: IntoIterator<Item = K>>(iter: T) -> Self {
+        let selector = Self::new();
+        selector.inner.write().or_poisoned().selected.extend(iter);
+        selector
+    }
+}
+
+impl<K> SetSelector<K>
+where
+    K: Eq + Hash + Clone + Send + Sync + 'static,
+{
+    /// Creates a new selector, with no keys selected.
+    #[track_caller]
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(SetSelectorInner {
+                selected: FxHashSet::default(),
+                subs: FxHashMap::default(),
+            })),
+            changed: ArcTrigger::new(),
+        }
+    }
+
+    /// Reactively checks whether the given key is selected.
+    ///
+    /// This only subscribes to changes to this key.
+    pub fn selected(&self, key: &K) -> bool {
+        let trigger = {
+            let sub = self.inner.read().or_poisoned().subs.get(key).cloned();
+            sub.unwrap_or_else(|| {
+                self.inner
+                    .write()
+                    .or_poisoned()
+                    .subs
+                    .entry(key.clone())
+                    .or_default()
+                    .clone()
+            })
+        };
+        // tracking before reading means that a change made in between still notifies this key
+        trigger.track();
+        self.inner.read().or_poisoned().selected.contains(key)
+    }
+
+    /// Reactively returns how many keys are selected.
+    pub fn len(&self) -> usize {
+        self.changed.track();
+        self.inner.read().or_poisoned().selected.len()
+    }
+
+    /// Reactively checks whether no keys are selected.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Reactively returns all the selected keys, in no particular order.
+    pub fn keys(&self) -> Vec<K> {
+        self.changed.track();
+        self.inner
+            .read()
+            .or_poisoned()
+            .selected
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    /// Selects the given key.
+    pub fn select(&self, key: K) {
+        self.set_selected(key, true);
+    }
+
+    /// Deselects the given key.
+    pub fn deselect(&self, key: &K) {
+        self.change(|selected| {
+            if selected.remove(key) {
+                vec![key.clone()]
+            } else {
+                Vec::new()
+            }
+        });
+    }
+
+    /// Selects the given key if it is not selected, and deselects it if it is.
+    pub fn toggle(&self, key: K) {
+        self.change(|selected| {
+            if !selected.remove(&key) {
+                selected.insert(key.clone());
+            }
+            vec![key]
+        });
+    }
+
+    /// Selects or deselects the given key.
+    pub fn set_selected(&self, key: K, is_selected: bool) {
+        if is_selected {
+            self.change(|selected| {
+                if selected.insert(key.clone()) {
+                    vec![key]
+                } else {
+                    Vec::new()
+                }
+            });
+        } else {
+            self.deselect(&key);
+        }
+    }
+
+    /// Selects all the given keys, notifying each key that was not already selected.
+    pub fn select_all(&self, keys: impl IntoIterator<Item = K>) {
+        self.change(|selected| {
+            keys.into_iter()
+                .filter(|key| selected.insert(key.clone()))
+                .collect()
+        });
+    }
+
+    /// Deselects every key, notifying each key that was selected.
+    pub fn clear(&self) {
+        self.change(|selected| selected.drain().collect());
+    }
+
+    /// Removes the listener for the given key, without changing whether it is selected.
+    pub fn remove_listener(&self, key: &K) {
+        self.inner.write().or_poisoned().subs.remove(key);
+    }
+
+    /// Changes the selection, and then notifies the keys that `fun` returns as changed.
+    fn change(&self, fun: impl FnOnce(&mut FxHashSet<K>) -> Vec<K>) {
+        let (changed, triggers) = {
+            let mut inner = self.inner.write().or_poisoned();
+            let changed = fun(&mut inner.selected);
+            let triggers = changed
+                .iter()
+                .filter_map(|key| inner.subs.get(key).cloned())
+                .collect::<Vec<_>>();
+            (changed, triggers)
+        };
+        // the lock is released first, so that subscribers can read the new selection
+        if !changed.is_empty() {
+            triggers.notify();
+            self.changed.notify();
+        }
+    }
+}
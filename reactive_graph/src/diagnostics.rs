@@ -7,6 +7,46 @@
 //!
 //! This module provides utilities to suppress those warnings by entering a
 //! [`SpecialNonReactiveZone`].
+//!
+//! ## Leak and Disposal Diagnostics
+//!
+//! With the `leak-diagnostics` feature enabled, every value stored in the arena (which backs
+//! `Copy` types like [`RwSignal`](crate::signal::RwSignal) and
+//! [`StoredValue`](crate::owner::StoredValue)) is recorded along with its type, the location at
+//! which it was created, and the [`Owner`](crate::owner::Owner) it was created under. This makes
+//! it possible to find values that outlive the owner they were meant to belong to:
+//! [`live_node_counts`] counts the values still alive under each owner, and
+//! [`assert_no_live_nodes`] can be used in tests to check that cleaning up an owner left nothing
+//! behind.
+//!
+//! Each attempt to access a value after it has been disposed is also recorded with the location
+//! at which the value was created, and can be inspected with [`take_disposed_accesses`]. An access
+//! that panics, like calling `.get()` on a disposed signal, also logs a warning naming the type of
+//! the value and the owner it was created under.
+//!
+//! Records of disposed values are kept so that later accesses can be reported, until enough new
+//! values have been created that they are pruned, so this feature should not be enabled in
+//! production builds.
+//!
+//! ```rust
+//! # #[cfg(feature = "leak-diagnostics")] {
+//! use reactive_graph::{diagnostics, owner::Owner, prelude::*, signal::RwSignal};
+//!
+//! let owner = Owner::new();
+//! let count = owner.with(|| RwSignal::new(0));
+//! owner.cleanup();
+//! diagnostics::assert_no_live_nodes(&owner);
+//!
+//! assert_eq!(count.try_get_untracked(), None);
+//! let accesses = diagnostics::take_disposed_accesses();
+//! assert!(accesses.iter().any(|access| access.node.type_name.contains("RwSignal")));
+//! # }
+//! ```
+
+#[cfg(feature = "leak-diagnostics")]
+mod leaks;
+#[cfg(feature = "leak-diagnostics")]
+pub use leaks::*;
 
 /// Marks an execution block that is known not to be reactive, and suppresses warnings.
 #[derive(Debug)]
@@ -0,0 +1,222 @@
+use crate::owner::{ArenaHandle, NodeId, Owner};
+use or_poisoned::OrPoisoned;
+use rustc_hash::FxHashMap;
+use std::{
+    cell::RefCell,
+    collections::{HashMap, VecDeque},
+    fmt::Write,
+    panic::Location,
+    sync::{LazyLock, Mutex},
+};
+
+static REGISTRY: LazyLock<Mutex<Registry>> = LazyLock::new(Default::default);
+
+/// The number of recorded nodes at which disposed nodes are first pruned.
+const MIN_PRUNE_AT: usize = 1024;
+
+/// The number of accesses to disposed values that are kept until they are taken.
+const MAX_DISPOSED_ACCESSES: usize = 1024;
+
+#[derive(Default)]
+struct Registry {
+    // keyed by arena as well as node, because each sandboxed arena assigns its own ids
+    nodes: FxHashMap<(usize, NodeId), Entry>,
+    // disposed nodes are pruned when the registry grows to this size, so that it stays
+    // proportional to the number of live nodes
+    prune_at: usize,
+    disposed_accesses: VecDeque<DisposedAccess>,
+}
+
+struct Entry {
+    arena: ArenaHandle,
+    info: ArenaNodeInfo,
+}
+
+impl Registry {
+    fn live_nodes(&self) -> impl Iterator<Item = &ArenaNodeInfo> {
+        self.nodes
+            .values()
+            .filter(|entry| entry.arena.contains(entry.info.node))
+            .map(|entry| &entry.info)
+    }
+
+    fn insert(&mut self, key: (usize, NodeId), entry: Entry) {
+        if self.nodes.len() >= self.prune_at.max(MIN_PRUNE_AT) {
+            self.nodes
+                .retain(|_, entry| entry.arena.contains(entry.info.node));
+            self.prune_at = self.nodes.len() * 2;
+        }
+        self.nodes.insert(key, entry);
+    }
+}
+
+/// A value that was stored in the arena, like the value of a [`RwSignal`](crate::signal::RwSignal)
+/// or a [`StoredValue`](crate::owner::StoredValue).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArenaNodeInfo {
+    node: NodeId,
+    /// The type of the stored value.
+    pub type_name: &'static str,
+    /// Where the value was created.
+    pub defined_at: &'static Location<'static>,
+    /// The [`Owner::debug_id`] of the owner the value was created under, followed by the ids of
+    /// its ancestors. Empty if there was no owner.
+    pub owners: Vec<usize>,
+}
+
+impl ArenaNodeInfo {
+    /// The [`Owner::debug_id`] of the owner the value was created under, if any.
+    pub fn owner(&self) -> Option<usize> {
+        self.owners.first().copied()
+    }
+}
+
+/// An attempt to access a value in the arena after it had already been disposed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DisposedAccess {
+    /// The value that had been disposed.
+    pub node: ArenaNodeInfo,
+}
+
+pub(crate) fn register_node(
+    node: NodeId,
+    type_name: &'static str,
+    defined_at: &'static Location<'static>,
+) {
+    let arena = ArenaHandle::current();
+    let owners = Owner::current()
+        .map(|owner| {
+            let mut owners = vec![owner.debug_id()];
+            owners.extend(owner.ancestry());
+            owners
+        })
+        .unwrap_or_default();
+    let key = (arena.id(), node);
+    let info = ArenaNodeInfo {
+        node,
+        type_name,
+        defined_at,
+        owners,
+    };
+    REGISTRY
+        .lock()
+        .or_poisoned()
+        .insert(key, Entry { arena, info });
+}
+
+thread_local! {
+    // the most recent access to a disposed value on this thread, if it was recorded
+    static LAST_DISPOSED_ACCESS: RefCell<Option<DisposedAccess>> = const { RefCell::new(None) };
+}
+
+/// Records an attempt to access a disposed value, without warning about it: a `try_` accessor
+/// returning `None` is often expected.
+pub(crate) fn report_disposed_access(node: NodeId) {
+    let key = (ArenaHandle::current().id(), node);
+    let mut registry = REGISTRY.lock().or_poisoned();
+    // values created before diagnostics were reset, or pruned since, are not reported
+    let access = registry.nodes.get(&key).map(|entry| DisposedAccess {
+        node: entry.info.clone(),
+    });
+    if let Some(access) = &access {
+        if registry.disposed_accesses.len() == MAX_DISPOSED_ACCESSES {
+            registry.disposed_accesses.pop_front();
+        }
+        registry.disposed_accesses.push_back(access.clone());
+    }
+    LAST_DISPOSED_ACCESS.with_borrow_mut(|last| *last = access);
+}
+
+/// Warns about the most recent access to a disposed value on this thread, which is about to
+/// panic at `accessed_at`.
+pub(crate) fn warn_disposed_access(accessed_at: &'static Location<'static>) {
+    let Some(access) = LAST_DISPOSED_ACCESS.with_borrow_mut(Option::take)
+    else {
+        return;
+    };
+    let owner = match access.node.owner() {
+        Some(owner) => format!("under owner {owner}"),
+        None => "with no owner".to_string(),
+    };
+    crate::log_warning(format_args!(
+        "At {}, you tried to access a {} that was created at {} {owner}, \
+         but it has already been disposed.",
+        accessed_at, access.node.type_name, access.node.defined_at
+    ));
+}
+
+/// Returns every value that is still stored in the arena.
+pub fn live_nodes() -> Vec<ArenaNodeInfo> {
+    REGISTRY
+        .lock()
+        .or_poisoned()
+        .live_nodes()
+        .cloned()
+        .collect()
+}
+
+/// Returns every value that is still stored in the arena, and was created under the given owner
+/// or any of its descendants.
+pub fn live_nodes_owned_by(owner: &Owner) -> Vec<ArenaNodeInfo> {
+    let id = owner.debug_id();
+    REGISTRY
+        .lock()
+        .or_poisoned()
+        .live_nodes()
+        .filter(|node| node.owners.contains(&id))
+        .cloned()
+        .collect()
+}
+
+/// Returns the number of values still stored in the arena, grouped by the
+/// [`Owner::debug_id`] of the owner each was created under, or `None` for values created with no
+/// owner.
+pub fn live_node_counts() -> HashMap<Option<usize>, usize> {
+    let mut counts = HashMap::new();
+    for node in REGISTRY.lock().or_poisoned().live_nodes() {
+        *counts.entry(node.owner()).or_default() += 1;
+    }
+    counts
+}
+
+/// Returns every attempt to access a disposed value since this was last called, and clears
+/// them.
+///
+/// Only the most recent 1024 accesses are kept.
+pub fn take_disposed_accesses() -> Vec<DisposedAccess> {
+    std::mem::take(&mut REGISTRY.lock().or_poisoned().disposed_accesses).into()
+}
+
+/// Panics if any value created under the given owner, or any of its descendants, is still stored
+/// in the arena.
+///
+/// This is intended to be called in tests, after the owner has been cleaned up. The panic message
+/// lists where each remaining value was created.
+#[track_caller]
+pub fn assert_no_live_nodes(owner: &Owner) {
+    let live = live_nodes_owned_by(owner);
+    if !live.is_empty() {
+        let mut message = format!(
+            "expected owner {} to have no live arena nodes, but found {}:",
+            owner.debug_id(),
+            live.len()
+        );
+        for node in live {
+            _ = write!(
+                message,
+                "\n  - {} created at {}",
+                node.type_name, node.defined_at
+            );
+        }
+        panic!("{message}");
+    }
+}
+
+/// Forgets every value that has been recorded so far, including values that have been disposed,
+/// and clears any recorded accesses to disposed values.
+pub fn reset() {
+    let mut registry = REGISTRY.lock().or_poisoned();
+    registry.nodes.clear();
+    registry.prune_at = 0;
+    registry.disposed_accesses.clear();
+}
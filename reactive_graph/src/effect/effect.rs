@@ -8,10 +8,22 @@ use crate::{
     owner::{ArenaItem, LocalStorage, Owner, Storage, SyncStorage},
     traits::Dispose,
 };
+#[cfg(feature = "tracing")]
+use crate::{effect::inner::effect_span, trace::SpanKind};
+#[cfg(feature = "inspector")]
+use crate::{effect::inner::register_effect, inspector::NodeKind};
+#[cfg(any(debug_assertions, leptos_debuginfo))]
+use crate::{effect::inner::start_updating, graph::CycleNodeKind};
 use any_spawner::Executor;
-use futures::StreamExt;
+use futures::{
+    future::{poll_fn, select, Either},
+    StreamExt,
+};
 use or_poisoned::OrPoisoned;
+#[cfg(any(debug_assertions, leptos_debuginfo))]
+use std::panic::Location;
 use std::{
+    future::Future,
     mem,
     sync::{atomic::AtomicBool, Arc, RwLock},
 };
@@ -92,7 +104,11 @@ impl<S> Dispose for Effect<S> {
     }
 }
 
-fn effect_base() -> (Receiver, Owner, Arc<RwLock<EffectInner>>) {
+fn effect_base(
+    #[allow(unused)]
+    #[cfg(any(debug_assertions, leptos_debuginfo))]
+    defined_at: &'static Location<'static>,
+) -> (Receiver, Owner, Arc<RwLock<EffectInner>>) {
     let (mut observer, rx) = channel();
 
     // spawn the effect asynchronously
@@ -106,10 +122,98 @@ fn effect_base() -> (Receiver, Owner, Arc<RwLock<EffectInner>>) {
         observer,
         sources: SourceSet::new(),
     }));
+    #[cfg(feature = "inspector")]
+    register_effect(
+        NodeKind::Effect,
+        &inner,
+        "Effect",
+        #[cfg(any(debug_assertions, leptos_debuginfo))]
+        defined_at,
+    );
 
     (rx, owner, inner)
 }
 
+/// Drives an async effect: each time the effect is notified and its sources have changed, the
+/// in-flight future (if any) is dropped and a new one is started. Reactive values read while
+/// creating the future, or during its first poll, are tracked as dependencies.
+async fn run_async_effect<Fut>(
+    mut rx: Receiver,
+    owner: Owner,
+    subscriber: AnySubscriber,
+    #[cfg(any(debug_assertions, leptos_debuginfo))]
+    defined_at: &'static Location<'static>,
+    mut fun: impl FnMut() -> Fut,
+) where
+    Fut: Future<Output = ()>,
+{
+    let mut first_run = true;
+    let mut current = None;
+
+    loop {
+        let notified = match &mut current {
+            Some(run) => match select(rx.next(), run).await {
+                Either::Left((notified, _)) => notified,
+                Either::Right(_) => {
+                    current = None;
+                    continue;
+                }
+            },
+            None => rx.next().await,
+        };
+        if notified.is_none() {
+            break;
+        }
+
+        if !owner.paused_for(&subscriber)
+            && (subscriber.with_observer(|| subscriber.update_if_necessary())
+                || first_run)
+        {
+            first_run = false;
+            // abort the previous run before starting the new one
+            drop(current.take());
+            subscriber.clear_sources(&subscriber);
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            let updating = start_updating::<()>(
+                CycleNodeKind::Effect,
+                &subscriber,
+                defined_at,
+            );
+
+            #[cfg(feature = "tracing")]
+            let span = effect_span(
+                SpanKind::Effect,
+                &subscriber,
+                &owner,
+                #[cfg(any(debug_assertions, leptos_debuginfo))]
+                defined_at,
+            );
+            let mut fut = Box::pin({
+                #[cfg(feature = "tracing")]
+                let _span = span.enter();
+                owner.with_cleanup(|| {
+                    subscriber.with_observer(|| run_in_effect_scope(&mut fun))
+                })
+            });
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            drop(updating);
+            let owner = owner.clone();
+            let mut observer = Some(subscriber.clone());
+            current = Some(poll_fn(move |cx| {
+                #[cfg(feature = "tracing")]
+                let _span = span.enter();
+                // only track the synchronous part of the future, before its first `.await`
+                let observer = observer.take();
+                owner.with(|| {
+                    observer.with_observer(|| {
+                        run_in_effect_scope(|| fut.as_mut().poll(cx))
+                    })
+                })
+            }));
+        }
+    }
+}
+
 #[cfg(debug_assertions)]
 thread_local! {
     static EFFECT_SCOPE_ACTIVE: AtomicBool = const { AtomicBool::new(false) };
@@ -165,12 +269,18 @@ impl Effect<LocalStorage> {
     /// This spawns a task on the local thread using
     /// [`spawn_local`](any_spawner::Executor::spawn_local). For an effect that can be spawned on
     /// any thread, use [`new_sync`](Effect::new_sync).
+    #[track_caller]
     pub fn new<T, M>(mut fun: impl EffectFunction<T, M> + 'static) -> Self
     where
         T: 'static,
     {
+        #[cfg(any(debug_assertions, leptos_debuginfo))]
+        let defined_at = Location::caller();
         let inner = cfg!(feature = "effects").then(|| {
-            let (mut rx, owner, inner) = effect_base();
+            let (mut rx, owner, inner) = effect_base(
+                #[cfg(any(debug_assertions, leptos_debuginfo))]
+                defined_at,
+            );
             let value = Arc::new(RwLock::new(None::<T>));
             let mut first_run = true;
 
@@ -180,13 +290,31 @@ impl Effect<LocalStorage> {
 
                 async move {
                     while rx.next().await.is_some() {
-                        if !owner.paused()
+                        if !owner.paused_for(&subscriber)
                             && (subscriber.with_observer(|| {
                                 subscriber.update_if_necessary()
                             }) || first_run)
                         {
                             first_run = false;
                             subscriber.clear_sources(&subscriber);
+                            #[cfg(any(debug_assertions, leptos_debuginfo))]
+                            let _updating = start_updating::<T>(
+                                CycleNodeKind::Effect,
+                                &subscriber,
+                                defined_at,
+                            );
+                            #[cfg(feature = "tracing")]
+                            let _span = effect_span(
+                                SpanKind::Effect,
+                                &subscriber,
+                                &owner,
+                                #[cfg(any(
+                                    debug_assertions,
+                                    leptos_debuginfo
+                                ))]
+                                defined_at,
+                            )
+                            .entered();
 
                             let old_value =
                                 mem::take(&mut *value.write().or_poisoned());
@@ -207,6 +335,74 @@ impl Effect<LocalStorage> {
         Self { inner }
     }
 
+    /// Creates a new effect from an async function, which runs once on the next “tick”, and
+    /// then runs again when reactive values that it reads change.
+    ///
+    /// Only reactive values that are read before the future first yields (i.e., before its
+    /// first `.await` that is not immediately ready) are tracked. When one of them changes, the
+    /// in-flight future is dropped, cancelling it, before the function is called again. The
+    /// future is also dropped when the effect is stopped or disposed.
+    ///
+    /// This spawns a task on the local thread using
+    /// [`spawn_local`](any_spawner::Executor::spawn_local). For an effect that can be spawned on
+    /// any thread, use [`new_async_sync`](Effect::new_async_sync).
+    ///
+    /// ```
+    /// # use reactive_graph::prelude::*;
+    /// # use reactive_graph::signal::*;
+    /// # use reactive_graph::effect::Effect;
+    /// # use any_spawner::{Executor, TestExecutor};
+    /// # use std::time::Duration;
+    /// # Executor::init_test_executor().unwrap();
+    /// # let owner = reactive_graph::owner::Owner::new(); owner.set();
+    /// let (query, set_query) = signal("a");
+    /// let (results, set_results) = signal(Vec::new());
+    ///
+    /// Effect::new_async(move || async move {
+    ///     // tracked, because it is read before the first `.await`
+    ///     let query = query.get();
+    ///     Executor::sleep(Duration::from_millis(100)).await;
+    ///     set_results.update(|results| results.push(query));
+    /// });
+    ///
+    /// # TestExecutor::advance(Duration::from_millis(50));
+    /// // (50ms later, before the first run has finished...)
+    /// set_query.set("b");
+    /// // (...the first run is cancelled, and the second run finishes)
+    /// # TestExecutor::advance(Duration::from_millis(100));
+    /// # if cfg!(feature = "effects") {
+    /// assert_eq!(results.get(), vec!["b"]);
+    /// # }
+    /// ```
+    #[track_caller]
+    pub fn new_async<Fut>(fun: impl FnMut() -> Fut + 'static) -> Self
+    where
+        Fut: Future<Output = ()> + 'static,
+    {
+        #[cfg(any(debug_assertions, leptos_debuginfo))]
+        let defined_at = Location::caller();
+        let inner = cfg!(feature = "effects").then(|| {
+            let (rx, owner, inner) = effect_base(
+                #[cfg(any(debug_assertions, leptos_debuginfo))]
+                defined_at,
+            );
+            let subscriber = inner.to_any_subscriber();
+
+            Executor::spawn_local(run_async_effect(
+                rx,
+                owner,
+                subscriber,
+                #[cfg(any(debug_assertions, leptos_debuginfo))]
+                defined_at,
+                fun,
+            ));
+
+            ArenaItem::new_with_storage(Some(inner))
+        });
+
+        Self { inner }
+    }
+
     /// A version of [`Effect::new`] that only listens to any dependency
     /// that is accessed inside `dependency_fn`.
     ///
@@ -310,6 +506,7 @@ impl Effect<LocalStorage> {
     /// # }).await;
     /// # });
     /// ```
+    #[track_caller]
     pub fn watch<D, T>(
         mut dependency_fn: impl FnMut() -> D + 'static,
         mut handler: impl FnMut(&D, Option<&D>, Option<T>) -> T + 'static,
@@ -319,8 +516,13 @@ impl Effect<LocalStorage> {
         D: 'static,
         T: 'static,
     {
+        #[cfg(any(debug_assertions, leptos_debuginfo))]
+        let defined_at = Location::caller();
         let inner = cfg!(feature = "effects").then(|| {
-            let (mut rx, owner, inner) = effect_base();
+            let (mut rx, owner, inner) = effect_base(
+                #[cfg(any(debug_assertions, leptos_debuginfo))]
+                defined_at,
+            );
             let mut first_run = true;
             let dep_value = Arc::new(RwLock::new(None::<D>));
             let watch_value = Arc::new(RwLock::new(None::<T>));
@@ -332,12 +534,30 @@ impl Effect<LocalStorage> {
 
                 async move {
                     while rx.next().await.is_some() {
-                        if !owner.paused()
+                        if !owner.paused_for(&subscriber)
                             && (subscriber.with_observer(|| {
                                 subscriber.update_if_necessary()
                             }) || first_run)
                         {
                             subscriber.clear_sources(&subscriber);
+                            #[cfg(any(debug_assertions, leptos_debuginfo))]
+                            let _updating = start_updating::<T>(
+                                CycleNodeKind::Effect,
+                                &subscriber,
+                                defined_at,
+                            );
+                            #[cfg(feature = "tracing")]
+                            let _span = effect_span(
+                                SpanKind::Effect,
+                                &subscriber,
+                                &owner,
+                                #[cfg(any(
+                                    debug_assertions,
+                                    leptos_debuginfo
+                                ))]
+                                defined_at,
+                            )
+                            .entered();
 
                             let old_dep_value = mem::take(
                                 &mut *dep_value.write().or_poisoned(),
@@ -383,6 +603,7 @@ impl Effect<SyncStorage> {
     ///
     /// This spawns a task that can be run on any thread. For an effect that will be spawned on
     /// the current thread, use [`new`](Effect::new).
+    #[track_caller]
     pub fn new_sync<T, M>(
         fun: impl EffectFunction<T, M> + Send + Sync + 'static,
     ) -> Self
@@ -400,13 +621,19 @@ impl Effect<SyncStorage> {
     /// that are read inside it change.
     ///
     /// This will run whether the `effects` feature is enabled or not.
+    #[track_caller]
     pub fn new_isomorphic<T, M>(
         mut fun: impl EffectFunction<T, M> + Send + Sync + 'static,
     ) -> Self
     where
         T: Send + Sync + 'static,
     {
-        let (mut rx, owner, inner) = effect_base();
+        #[cfg(any(debug_assertions, leptos_debuginfo))]
+        let defined_at = Location::caller();
+        let (mut rx, owner, inner) = effect_base(
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at,
+        );
         let mut first_run = true;
         let value = Arc::new(RwLock::new(None::<T>));
 
@@ -416,13 +643,28 @@ impl Effect<SyncStorage> {
 
             async move {
                 while rx.next().await.is_some() {
-                    if !owner.paused()
+                    if !owner.paused_for(&subscriber)
                         && (subscriber
                             .with_observer(|| subscriber.update_if_necessary())
                             || first_run)
                     {
                         first_run = false;
                         subscriber.clear_sources(&subscriber);
+                        #[cfg(any(debug_assertions, leptos_debuginfo))]
+                        let _updating = start_updating::<T>(
+                            CycleNodeKind::Effect,
+                            &subscriber,
+                            defined_at,
+                        );
+                        #[cfg(feature = "tracing")]
+                        let _span = effect_span(
+                            SpanKind::Effect,
+                            &subscriber,
+                            &owner,
+                            #[cfg(any(debug_assertions, leptos_debuginfo))]
+                            defined_at,
+                        )
+                        .entered();
 
                         let old_value =
                             mem::take(&mut *value.write().or_poisoned());
@@ -444,7 +686,62 @@ impl Effect<SyncStorage> {
         }
     }
 
+    /// Creates a new effect from an async function, which runs once on the next “tick”, and
+    /// then runs again when reactive values that it reads change. When they change, the
+    /// in-flight future is cancelled before the function is called again.
+    ///
+    /// This spawns a task that can be run on any thread. For an effect that will be spawned on
+    /// the current thread, use [`new_async`](Effect::new_async).
+    #[track_caller]
+    pub fn new_async_sync<Fut>(
+        fun: impl FnMut() -> Fut + Send + Sync + 'static,
+    ) -> Self
+    where
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        if !cfg!(feature = "effects") {
+            return Self { inner: None };
+        }
+
+        Self::new_async_isomorphic(fun)
+    }
+
+    /// Creates a new effect from an async function, which runs once on the next “tick”, and
+    /// then runs again when reactive values that it reads change. When they change, the
+    /// in-flight future is cancelled before the function is called again.
+    ///
+    /// This will run whether the `effects` feature is enabled or not.
+    #[track_caller]
+    pub fn new_async_isomorphic<Fut>(
+        fun: impl FnMut() -> Fut + Send + Sync + 'static,
+    ) -> Self
+    where
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        #[cfg(any(debug_assertions, leptos_debuginfo))]
+        let defined_at = Location::caller();
+        let (rx, owner, inner) = effect_base(
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at,
+        );
+        let subscriber = inner.to_any_subscriber();
+
+        crate::spawn(run_async_effect(
+            rx,
+            owner,
+            subscriber,
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at,
+            fun,
+        ));
+
+        Self {
+            inner: Some(ArenaItem::new_with_storage(Some(inner))),
+        }
+    }
+
     /// This is to [`Effect::watch`] what [`Effect::new_sync`] is to [`Effect::new`].
+    #[track_caller]
     pub fn watch_sync<D, T>(
         mut dependency_fn: impl FnMut() -> D + Send + Sync + 'static,
         mut handler: impl FnMut(&D, Option<&D>, Option<T>) -> T
@@ -457,7 +754,12 @@ impl Effect<SyncStorage> {
         D: Send + Sync + 'static,
         T: Send + Sync + 'static,
     {
-        let (mut rx, owner, inner) = effect_base();
+        #[cfg(any(debug_assertions, leptos_debuginfo))]
+        let defined_at = Location::caller();
+        let (mut rx, owner, inner) = effect_base(
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at,
+        );
         let mut first_run = true;
         let dep_value = Arc::new(RwLock::new(None::<D>));
         let watch_value = Arc::new(RwLock::new(None::<T>));
@@ -470,12 +772,30 @@ impl Effect<SyncStorage> {
 
                 async move {
                     while rx.next().await.is_some() {
-                        if !owner.paused()
+                        if !owner.paused_for(&subscriber)
                             && (subscriber.with_observer(|| {
                                 subscriber.update_if_necessary()
                             }) || first_run)
                         {
                             subscriber.clear_sources(&subscriber);
+                            #[cfg(any(debug_assertions, leptos_debuginfo))]
+                            let _updating = start_updating::<T>(
+                                CycleNodeKind::Effect,
+                                &subscriber,
+                                defined_at,
+                            );
+                            #[cfg(feature = "tracing")]
+                            let _span = effect_span(
+                                SpanKind::Effect,
+                                &subscriber,
+                                &owner,
+                                #[cfg(any(
+                                    debug_assertions,
+                                    leptos_debuginfo
+                                ))]
+                                defined_at,
+                            )
+                            .entered();
 
                             let old_dep_value = mem::take(
                                 &mut *dep_value.write().or_poisoned(),
@@ -183,6 +183,8 @@ pub fn batch<T>(f: impl FnOnce() -> T) -> T {
 }
 
 mod inner {
+    #[cfg(any(debug_assertions, leptos_debuginfo))]
+    use crate::graph::{CycleNode, CycleNodeKind};
     use crate::{
         graph::{
             AnySource, AnySubscriber, ReactiveNode, ReactiveNodeState,
@@ -263,7 +265,7 @@ mod inner {
             #[cfg(any(debug_assertions, leptos_debuginfo))]
             let defined_at = Location::caller();
 
-            Arc::new_cyclic(|weak| {
+            let inner = Arc::new_cyclic(|weak| {
                 let any_subscriber = AnySubscriber(
                     weak.as_ptr() as usize,
                     Weak::clone(weak) as Weak<dyn Subscriber + Send + Sync>,
@@ -282,7 +284,15 @@ mod inner {
                     sources: SourceSet::new(),
                     any_subscriber,
                 })
-            })
+            });
+            #[cfg(feature = "inspector")]
+            crate::inspector::register(
+                crate::inspector::NodeKind::ImmediateEffect,
+                &inner,
+                "ImmediateEffect",
+                Some(Location::caller()),
+            );
+            inner
         }
     }
 
@@ -302,7 +312,7 @@ mod inner {
             let state = {
                 let guard = self.read().or_poisoned();
 
-                if guard.owner.paused() {
+                if guard.owner.paused_for(&guard.any_subscriber) {
                     return false;
                 }
 
@@ -353,12 +363,36 @@ mod inner {
                     warn_excessive_recursion(&guard);
                 }
 
+                #[cfg(feature = "tracing")]
+                let _span = crate::trace::span(
+                    crate::trace::SpanKind::ImmediateEffect,
+                    any_subscriber.0,
+                    Some(&owner),
+                    guard.defined_at(),
+                )
+                .entered();
+
+                // an immediate effect may run again while it is running, so only effects that are
+                // running are recorded, to be reported as part of any cycle found meanwhile
+                #[cfg(any(debug_assertions, leptos_debuginfo))]
+                let running = crate::graph::start_running(
+                    any_subscriber.0,
+                    CycleNode {
+                        kind: CycleNodeKind::ImmediateEffect,
+                        value_type: std::any::type_name::<()>(),
+                        defined_at: guard.defined_at,
+                    },
+                );
+
                 drop(guard);
 
                 // We execute the effect.
                 // Note that *this could happen in parallel across threads*.
                 owner.with_cleanup(|| any_subscriber.with_observer(|| fun()));
 
+                #[cfg(any(debug_assertions, leptos_debuginfo))]
+                drop(running);
+
                 let mut guard = self.write().or_poisoned();
 
                 // This run has completed.
@@ -1,3 +1,7 @@
+#[cfg(any(debug_assertions, leptos_debuginfo))]
+use crate::graph::{CycleNode, CycleNodeKind, UpdatingGuard};
+#[cfg(feature = "inspector")]
+use crate::inspector::NodeKind;
 use crate::{
     channel::Sender,
     graph::{
@@ -5,8 +9,14 @@ use crate::{
         ToAnySubscriber,
     },
 };
+#[cfg(feature = "tracing")]
+use crate::{owner::Owner, trace::SpanKind};
 use or_poisoned::OrPoisoned;
+#[cfg(any(debug_assertions, leptos_debuginfo))]
+use std::panic::Location;
 use std::sync::{Arc, RwLock, Weak};
+#[cfg(feature = "tracing")]
+use tracing::Span;
 
 /// Handles internal subscription logic for effects.
 #[derive(Debug)]
@@ -16,6 +26,64 @@ pub struct EffectInner {
     pub(crate) sources: SourceSet,
 }
 
+/// Marks that an effect has started running, until the guard is dropped, so that it is reported
+/// as part of any cycle found while it runs.
+#[cfg(any(debug_assertions, leptos_debuginfo))]
+pub(crate) fn start_updating<T>(
+    kind: CycleNodeKind,
+    subscriber: &AnySubscriber,
+    defined_at: &'static Location<'static>,
+) -> UpdatingGuard {
+    crate::graph::start_updating(
+        subscriber.0,
+        CycleNode {
+            kind,
+            value_type: std::any::type_name::<T>(),
+            defined_at,
+        },
+    )
+}
+
+/// Creates the span for one run of an effect.
+#[cfg(feature = "tracing")]
+pub(crate) fn effect_span(
+    kind: SpanKind,
+    subscriber: &AnySubscriber,
+    owner: &Owner,
+    #[cfg(any(debug_assertions, leptos_debuginfo))]
+    defined_at: &'static Location<'static>,
+) -> Span {
+    crate::trace::span(
+        kind,
+        subscriber.0,
+        Some(owner),
+        #[cfg(any(debug_assertions, leptos_debuginfo))]
+        Some(defined_at),
+        #[cfg(not(any(debug_assertions, leptos_debuginfo)))]
+        None,
+    )
+}
+
+/// Registers an effect with the inspector.
+#[cfg(feature = "inspector")]
+pub(crate) fn register_effect(
+    kind: NodeKind,
+    inner: &Arc<RwLock<EffectInner>>,
+    type_name: &'static str,
+    #[cfg(any(debug_assertions, leptos_debuginfo))]
+    defined_at: &'static Location<'static>,
+) {
+    crate::inspector::register(
+        kind,
+        inner,
+        type_name,
+        #[cfg(any(debug_assertions, leptos_debuginfo))]
+        Some(defined_at),
+        #[cfg(not(any(debug_assertions, leptos_debuginfo)))]
+        None,
+    );
+}
+
 impl ToAnySubscriber for Arc<RwLock<EffectInner>> {
     fn to_any_subscriber(&self) -> AnySubscriber {
         AnySubscriber(
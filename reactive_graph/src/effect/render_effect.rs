@@ -7,8 +7,16 @@ use crate::{
     },
     owner::Owner,
 };
+#[cfg(feature = "tracing")]
+use crate::{effect::inner::effect_span, trace::SpanKind};
+#[cfg(feature = "inspector")]
+use crate::{effect::inner::register_effect, inspector::NodeKind};
+#[cfg(any(debug_assertions, leptos_debuginfo))]
+use crate::{effect::inner::start_updating, graph::CycleNodeKind};
 use futures::StreamExt;
 use or_poisoned::OrPoisoned;
+#[cfg(any(debug_assertions, leptos_debuginfo))]
+use std::panic::Location;
 #[cfg(feature = "subsecond")]
 use std::sync::Mutex;
 use std::{
@@ -59,6 +67,7 @@ where
     T: 'static,
 {
     /// Creates a new render effect, which immediately runs `fun`.
+    #[track_caller]
     pub fn new(fun: impl FnMut(Option<T>) -> T + 'static) -> Self {
         #[cfg(feature = "subsecond")]
         let (hot_fn_ptr, fun) = {
@@ -83,10 +92,13 @@ where
             None,
             #[cfg(feature = "subsecond")]
             hot_fn_ptr,
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            Location::caller(),
         )
     }
 
     /// Creates a new render effect with an initial value.
+    #[track_caller]
     pub fn new_with_value(
         fun: impl FnMut(Option<T>) -> T + 'static,
         initial_value: Option<T>,
@@ -114,6 +126,8 @@ where
             initial_value,
             #[cfg(feature = "subsecond")]
             hot_fn_ptr,
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            Location::caller(),
         )
     }
 
@@ -130,6 +144,8 @@ where
         Self::new_with_async_value_erased(
             Box::new(fun),
             Box::pin(value.into_future()),
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            Location::caller(),
         )
         .await
     }
@@ -142,6 +158,8 @@ where
         #[allow(unused)]
         #[cfg(feature = "subsecond")]
         hot_fn_ptr: CurrentHotPtr,
+        #[cfg(any(debug_assertions, leptos_debuginfo))]
+        defined_at: &'static Location<'static>,
     ) -> Self {
         // codegen optimisation:
         fn prep() -> (Owner, Arc<RwLock<EffectInner>>, crate::channel::Receiver)
@@ -157,12 +175,22 @@ where
         }
 
         let (owner, inner, mut rx) = prep();
+        #[cfg(feature = "inspector")]
+        register_effect(
+            NodeKind::RenderEffect,
+            &inner,
+            "RenderEffect",
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at,
+        );
 
         let value = Arc::new(RwLock::new(None::<T>));
 
         #[cfg(not(feature = "effects"))]
         {
             let _ = initial_value;
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            let _ = defined_at;
             let _ = owner;
             let _ = &mut rx;
             let _ = fun;
@@ -215,21 +243,58 @@ where
                 move |prev| fun.call((prev,))
             };
 
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            let updating = start_updating::<T>(
+                CycleNodeKind::RenderEffect,
+                &subscriber,
+                defined_at,
+            );
+            #[cfg(feature = "tracing")]
+            let span = effect_span(
+                SpanKind::RenderEffect,
+                &subscriber,
+                &owner,
+                #[cfg(any(debug_assertions, leptos_debuginfo))]
+                defined_at,
+            )
+            .entered();
             *value.write().or_poisoned() = Some(
                 owner.with(|| subscriber.with_observer(|| fun(initial_value))),
             );
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            drop(updating);
+            #[cfg(feature = "tracing")]
+            drop(span);
 
             any_spawner::Executor::spawn_local({
                 let value = Arc::clone(&value);
 
                 async move {
                     while rx.next().await.is_some() {
-                        if !owner.paused()
+                        if !owner.paused_for(&subscriber)
                             && subscriber.with_observer(|| {
                                 subscriber.update_if_necessary()
                             })
                         {
                             subscriber.clear_sources(&subscriber);
+                            #[cfg(any(debug_assertions, leptos_debuginfo))]
+                            let _updating = start_updating::<T>(
+                                CycleNodeKind::RenderEffect,
+                                &subscriber,
+                                defined_at,
+                            );
+                            #[cfg(feature = "tracing")]
+                            let _span = effect_span(
+                                SpanKind::RenderEffect,
+                                &subscriber,
+                                &owner,
+                                #[cfg(any(
+                                    debug_assertions,
+                                    leptos_debuginfo
+                                ))]
+                                defined_at,
+                            )
+                            .entered();
 
                             let old_value =
                                 mem::take(&mut *value.write().or_poisoned());
@@ -249,6 +314,8 @@ where
     async fn new_with_async_value_erased(
         mut fun: Box<dyn FnMut(Option<T>) -> T + 'static>,
         initial_value: Pin<Box<dyn Future<Output = T>>>,
+        #[cfg(any(debug_assertions, leptos_debuginfo))]
+        defined_at: &'static Location<'static>,
     ) -> Self {
         // codegen optimisation:
         fn prep() -> (Owner, Arc<RwLock<EffectInner>>, crate::channel::Receiver)
@@ -264,12 +331,22 @@ where
         }
 
         let (owner, inner, mut rx) = prep();
+        #[cfg(feature = "inspector")]
+        register_effect(
+            NodeKind::RenderEffect,
+            &inner,
+            "RenderEffect",
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at,
+        );
 
         let value = Arc::new(RwLock::new(None::<T>));
 
         #[cfg(not(feature = "effects"))]
         {
             drop(initial_value);
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            let _ = defined_at;
             let _ = owner;
             let _ = &mut rx;
             let _ = &mut fun;
@@ -291,12 +368,30 @@ where
 
                 async move {
                     while rx.next().await.is_some() {
-                        if !owner.paused()
+                        if !owner.paused_for(&subscriber)
                             && subscriber.with_observer(|| {
                                 subscriber.update_if_necessary()
                             })
                         {
                             subscriber.clear_sources(&subscriber);
+                            #[cfg(any(debug_assertions, leptos_debuginfo))]
+                            let _updating = start_updating::<T>(
+                                CycleNodeKind::RenderEffect,
+                                &subscriber,
+                                defined_at,
+                            );
+                            #[cfg(feature = "tracing")]
+                            let _span = effect_span(
+                                SpanKind::RenderEffect,
+                                &subscriber,
+                                &owner,
+                                #[cfg(any(
+                                    debug_assertions,
+                                    leptos_debuginfo
+                                ))]
+                                defined_at,
+                            )
+                            .entered();
 
                             let old_value =
                                 mem::take(&mut *value.write().or_poisoned());
@@ -332,6 +427,7 @@ where
     T: Send + Sync + 'static,
 {
     /// Creates a render effect that will run whether the `effects` feature is enabled or not.
+    #[track_caller]
     pub fn new_isomorphic(
         fun: impl FnMut(Option<T>) -> T + Send + Sync + 'static,
     ) -> Self {
@@ -342,6 +438,8 @@ where
 
         fn erased<T: Send + Sync + 'static>(
             mut fun: Box<dyn FnMut(Option<T>) -> T + Send + Sync + 'static>,
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: &'static Location<'static>,
         ) -> RenderEffect<T> {
             let (observer, mut rx) = channel();
             let value = Arc::new(RwLock::new(None::<T>));
@@ -351,9 +449,36 @@ where
                 observer,
                 sources: SourceSet::new(),
             }));
+            #[cfg(feature = "inspector")]
+            register_effect(
+                NodeKind::RenderEffect,
+                &inner,
+                "RenderEffect",
+                #[cfg(any(debug_assertions, leptos_debuginfo))]
+                defined_at,
+            );
 
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            let updating = start_updating::<T>(
+                CycleNodeKind::RenderEffect,
+                &inner.to_any_subscriber(),
+                defined_at,
+            );
+            #[cfg(feature = "tracing")]
+            let span = effect_span(
+                SpanKind::RenderEffect,
+                &inner.to_any_subscriber(),
+                &owner,
+                #[cfg(any(debug_assertions, leptos_debuginfo))]
+                defined_at,
+            )
+            .entered();
             let initial_value = owner
                 .with(|| inner.to_any_subscriber().with_observer(|| fun(None)));
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            drop(updating);
+            #[cfg(feature = "tracing")]
+            drop(span);
             *value.write().or_poisoned() = Some(initial_value);
 
             crate::spawn({
@@ -362,12 +487,30 @@ where
 
                 async move {
                     while rx.next().await.is_some() {
-                        if !owner.paused()
+                        if !owner.paused_for(&subscriber)
                             && subscriber.with_observer(|| {
                                 subscriber.update_if_necessary()
                             })
                         {
                             subscriber.clear_sources(&subscriber);
+                            #[cfg(any(debug_assertions, leptos_debuginfo))]
+                            let _updating = start_updating::<T>(
+                                CycleNodeKind::RenderEffect,
+                                &subscriber,
+                                defined_at,
+                            );
+                            #[cfg(feature = "tracing")]
+                            let _span = effect_span(
+                                SpanKind::RenderEffect,
+                                &subscriber,
+                                &owner,
+                                #[cfg(any(
+                                    debug_assertions,
+                                    leptos_debuginfo
+                                ))]
+                                defined_at,
+                            )
+                            .entered();
 
                             let old_value =
                                 mem::take(&mut *value.write().or_poisoned());
@@ -383,7 +526,11 @@ where
             RenderEffect { value, inner }
         }
 
-        erased(Box::new(fun))
+        erased(
+            Box::new(fun),
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            Location::caller(),
+        )
     }
 }
 
@@ -1,11 +1,15 @@
 //! Types that define the reactive graph itself. These are mostly internal, but can be used to
 //! create custom reactive primitives.
 
+mod cycle;
 mod node;
 mod sets;
 mod source;
 mod subscriber;
 
+#[cfg(any(debug_assertions, leptos_debuginfo))]
+pub(crate) use cycle::{start_running, start_updating, UpdatingGuard};
+pub use cycle::{take_cycle_error, CycleError, CycleNode, CycleNodeKind};
 pub use node::*;
 pub(crate) use sets::*;
 pub use source::*;
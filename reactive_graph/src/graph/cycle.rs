@@ -0,0 +1,158 @@
+use std::{
+    cell::RefCell,
+    error::Error,
+    fmt::{self, Display},
+    panic::Location,
+};
+
+#[cfg(any(debug_assertions, leptos_debuginfo))]
+thread_local! {
+    // the nodes that are currently being updated on this thread, outermost first
+    static UPDATING: RefCell<Vec<(usize, CycleNode)>> = const { RefCell::new(Vec::new()) };
+}
+
+thread_local! {
+    static LAST_CYCLE: RefCell<Option<CycleError>> = const { RefCell::new(None) };
+}
+
+/// The kind of reactive node that is part of a [`CycleError`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CycleNodeKind {
+    /// A [`Memo`](crate::computed::Memo) or [`ArcMemo`](crate::computed::ArcMemo).
+    Memo,
+    /// An [`Effect`](crate::effect::Effect).
+    Effect,
+    /// A [`RenderEffect`](crate::effect::RenderEffect).
+    RenderEffect,
+    /// An [`ImmediateEffect`](crate::effect::ImmediateEffect).
+    ImmediateEffect,
+}
+
+impl Display for CycleNodeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            CycleNodeKind::Memo => "memo",
+            CycleNodeKind::Effect => "effect",
+            CycleNodeKind::RenderEffect => "render effect",
+            CycleNodeKind::ImmediateEffect => "immediate effect",
+        })
+    }
+}
+
+/// A node that is part of a [`CycleError`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CycleNode {
+    /// The kind of node.
+    pub kind: CycleNodeKind,
+    /// The type of the value held by the node.
+    pub value_type: &'static str,
+    /// Where the node was created.
+    pub defined_at: &'static Location<'static>,
+}
+
+impl Display for CycleNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            CycleNodeKind::Memo => write!(
+                f,
+                "memo of `{}` defined at {}",
+                self.value_type, self.defined_at
+            ),
+            kind => write!(f, "{kind} defined at {}", self.defined_at),
+        }
+    }
+}
+
+/// A cycle in the reactive graph, in which a memo or effect depends on its own value, either
+/// directly or through other memos.
+///
+/// Updating a memo or running an effect that is part of a cycle would never finish, so it panics
+/// with this error as its message instead. The error is also saved, and can be retrieved with [`take_cycle_error`],
+/// which is useful for asserting on it in tests.
+///
+/// Cycles are only detected in debug builds (or with `--cfg leptos_debuginfo`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleError {
+    /// Each node in the cycle, starting with the node that was being updated when the cycle was
+    /// found, and ending with that same node again.
+    pub path: Vec<CycleNode>,
+}
+
+impl Display for CycleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "cycle detected in the reactive graph:")?;
+        for (idx, node) in self.path.iter().enumerate() {
+            if idx == 0 {
+                write!(f, "\n    {node}")?;
+            } else {
+                write!(f, "\n -> {node}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Error for CycleError {}
+
+/// Returns the most recent cycle detected on this thread, if any, and clears it.
+pub fn take_cycle_error() -> Option<CycleError> {
+    LAST_CYCLE.with_borrow_mut(Option::take)
+}
+
+/// Removes a node from the stack of nodes being updated when dropped.
+#[cfg(any(debug_assertions, leptos_debuginfo))]
+pub(crate) struct UpdatingGuard(());
+
+#[cfg(any(debug_assertions, leptos_debuginfo))]
+impl Drop for UpdatingGuard {
+    fn drop(&mut self) {
+        UPDATING.with_borrow_mut(Vec::pop);
+    }
+}
+
+/// Marks that the node with this id has started updating, until the guard is dropped.
+///
+/// # Panics
+/// Panics with a [`CycleError`] if the node is already being updated on this thread.
+#[cfg(any(debug_assertions, leptos_debuginfo))]
+pub(crate) fn start_updating(id: usize, node: CycleNode) -> UpdatingGuard {
+    start(id, node, true)
+}
+
+/// Marks that the node with this id has started running, until the guard is dropped.
+///
+/// Unlike [`start_updating`], this allows the node to run again while it is already running, as
+/// an [`ImmediateEffect`](crate::effect::ImmediateEffect) may, but still includes it in the path
+/// of any cycle found while it is running.
+#[cfg(any(debug_assertions, leptos_debuginfo))]
+pub(crate) fn start_running(id: usize, node: CycleNode) -> UpdatingGuard {
+    start(id, node, false)
+}
+
+#[cfg(any(debug_assertions, leptos_debuginfo))]
+fn start(id: usize, node: CycleNode, reentry_is_cycle: bool) -> UpdatingGuard {
+    let cycle = UPDATING.with_borrow_mut(|updating| {
+        let start = reentry_is_cycle
+            .then(|| updating.iter().position(|(other, _)| *other == id))
+            .flatten();
+        match start {
+            Some(start) => Some(CycleError {
+                path: updating[start..]
+                    .iter()
+                    .map(|(_, node)| *node)
+                    .chain(Some(node))
+                    .collect(),
+            }),
+            None => {
+                updating.push((id, node));
+                None
+            }
+        }
+    });
+    if let Some(cycle) = cycle {
+        LAST_CYCLE.with_borrow_mut(|last| *last = Some(cycle.clone()));
+        panic!("{cycle}");
+    }
+    UpdatingGuard(())
+}
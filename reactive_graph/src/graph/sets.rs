@@ -37,6 +37,8 @@ impl SourceSet {
     }
 
     pub fn clear_sources(&mut self, subscriber: &AnySubscriber) {
+        #[cfg(feature = "inspector")]
+        crate::inspector::clear_dependencies(subscriber.0);
         for source in self.take() {
             source.remove_subscriber(subscriber);
         }
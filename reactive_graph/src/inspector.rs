@@ -0,0 +1,467 @@
+//! An opt-in inspector for the reactive graph, enabled with the `inspector` feature.
+//!
+//! While the feature is enabled, every signal, trigger, memo, async derived value and effect
+//! registers itself with a global registry when it is created, and every dependency that is
+//! tracked between a source and a subscriber is recorded. At any point, a [`GraphSnapshot`] of
+//! the nodes that are still alive can be taken, and exported as JSON or as a Graphviz DOT graph.
+//!
+//! This is intended for debugging questions like “why did this effect rerun?”, and for asserting
+//! on the shape of the graph in tests. Recording every dependency has a cost, so the feature
+//! should not be enabled in production builds.
+//!
+//! ```rust
+//! # let owner = reactive_graph::owner::Owner::new(); owner.set();
+//! use reactive_graph::{
+//!     computed::ArcMemo, inspector, prelude::*, signal::ArcRwSignal,
+//! };
+//!
+//! let count = ArcRwSignal::new(1);
+//! inspector::label(&count, "count");
+//! let double = ArcMemo::new({
+//!     let count = count.clone();
+//!     move |_| count.get() * 2
+//! });
+//! inspector::label(&double, "double");
+//! assert_eq!(double.get(), 2);
+//!
+//! let snapshot = inspector::snapshot_owner(&owner);
+//! let count = snapshot.node_by_label("count").unwrap();
+//! let double = snapshot.node_by_label("double").unwrap();
+//! assert_eq!(count.subscribers, vec![double.id]);
+//! assert_eq!(double.sources, vec![count.id]);
+//!
+//! println!("{}", snapshot.to_dot());
+//! ```
+
+use crate::{
+    graph::{Observer, ToAnySource},
+    json::write_json_str,
+    owner::Owner,
+};
+use indexmap::IndexSet;
+use or_poisoned::OrPoisoned;
+use rustc_hash::{FxHashMap, FxHasher};
+use std::{
+    any::Any,
+    fmt::{self, Write},
+    hash::BuildHasherDefault,
+    panic::Location,
+    sync::{Arc, LazyLock, Mutex, Weak},
+};
+
+type FxIndexSet<T> = IndexSet<T, BuildHasherDefault<FxHasher>>;
+
+static REGISTRY: LazyLock<Mutex<Registry>> = LazyLock::new(Default::default);
+
+/// The type of a node in the reactive graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NodeKind {
+    /// A signal, like [`ArcRwSignal`](crate::signal::ArcRwSignal).
+    Signal,
+    /// A data-less signal, like [`ArcTrigger`](crate::signal::ArcTrigger).
+    Trigger,
+    /// A synchronous derived value, like [`ArcMemo`](crate::computed::ArcMemo).
+    Memo,
+    /// An asynchronous derived value, like [`ArcAsyncDerived`](crate::computed::ArcAsyncDerived).
+    AsyncDerived,
+    /// An [`Effect`](crate::effect::Effect).
+    Effect,
+    /// A [`RenderEffect`](crate::effect::RenderEffect).
+    RenderEffect,
+    /// An [`ImmediateEffect`](crate::effect::ImmediateEffect).
+    ImmediateEffect,
+}
+
+impl NodeKind {
+    /// Returns a short, lowercase name for this kind of node.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NodeKind::Signal => "signal",
+            NodeKind::Trigger => "trigger",
+            NodeKind::Memo => "memo",
+            NodeKind::AsyncDerived => "async_derived",
+            NodeKind::Effect => "effect",
+            NodeKind::RenderEffect => "render_effect",
+            NodeKind::ImmediateEffect => "immediate_effect",
+        }
+    }
+}
+
+impl fmt::Display for NodeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Default)]
+struct Registry {
+    next_seq: u64,
+    prune_at: usize,
+    nodes: FxHashMap<usize, NodeEntry>,
+    // subscriber id -> source ids, in the order they were first tracked
+    sources: FxHashMap<usize, FxIndexSet<usize>>,
+}
+
+struct NodeEntry {
+    seq: u64,
+    kind: NodeKind,
+    type_name: &'static str,
+    label: Option<String>,
+    defined_at: Option<&'static Location<'static>>,
+    // the owner under which the node was created, followed by its ancestors
+    owners: Vec<usize>,
+    handle: Weak<dyn Any + Send + Sync>,
+}
+
+impl NodeEntry {
+    fn is_alive(&self) -> bool {
+        self.handle.strong_count() > 0
+    }
+}
+
+impl Registry {
+    fn prune(&mut self) {
+        self.nodes.retain(|_, node| node.is_alive());
+        let nodes = &self.nodes;
+        self.sources.retain(|id, _| nodes.contains_key(id));
+        self.prune_at = (self.nodes.len() * 2).max(256);
+    }
+}
+
+/// Adds a newly-created reactive node to the registry.
+pub(crate) fn register<T: Send + Sync + 'static>(
+    kind: NodeKind,
+    node: &Arc<T>,
+    type_name: &'static str,
+    defined_at: Option<&'static Location<'static>>,
+) {
+    let id = Arc::as_ptr(node) as usize;
+    let owners = Owner::current()
+        .map(|owner| {
+            let mut owners = vec![owner.debug_id()];
+            owners.extend(owner.ancestry());
+            owners
+        })
+        .unwrap_or_default();
+    let handle = Arc::downgrade(node) as Weak<dyn Any + Send + Sync>;
+
+    let mut registry = REGISTRY.lock().or_poisoned();
+    if registry.nodes.len() >= registry.prune_at {
+        registry.prune();
+    }
+    let seq = registry.next_seq;
+    registry.next_seq += 1;
+    // if the address has been reused, any dependencies belonged to the previous node
+    registry.sources.remove(&id);
+    registry.nodes.insert(
+        id,
+        NodeEntry {
+            seq,
+            kind,
+            type_name,
+            label: None,
+            defined_at,
+            owners,
+            handle,
+        },
+    );
+}
+
+/// Records that `subscriber` has started tracking `source`.
+pub(crate) fn record_dependency(source: usize, subscriber: usize) {
+    let mut registry = REGISTRY.lock().or_poisoned();
+    if registry.nodes.contains_key(&subscriber) {
+        registry
+            .sources
+            .entry(subscriber)
+            .or_default()
+            .insert(source);
+    }
+}
+
+/// Records that `subscriber` has stopped tracking all of its sources.
+pub(crate) fn clear_dependencies(subscriber: usize) {
+    let mut registry = REGISTRY.lock().or_poisoned();
+    if let Some(sources) = registry.sources.get_mut(&subscriber) {
+        sources.clear();
+    }
+}
+
+fn set_label(id: usize, label: String) {
+    let mut registry = REGISTRY.lock().or_poisoned();
+    if let Some(node) = registry.nodes.get_mut(&id) {
+        node.label = Some(label);
+    }
+}
+
+/// Returns the label attached to the node with the given id, if any.
+#[cfg(feature = "tracing")]
+pub(crate) fn label_of(id: usize) -> Option<String> {
+    REGISTRY
+        .lock()
+        .or_poisoned()
+        .nodes
+        .get(&id)
+        .and_then(|node| node.label.clone())
+}
+
+/// Attaches a human-readable label to a signal, memo, or other reactive source, which will be
+/// included in any snapshots of the graph.
+pub fn label(source: &impl ToAnySource, label: impl Into<String>) {
+    set_label(source.to_any_source().0, label.into());
+}
+
+/// Attaches a human-readable label to the effect or memo that is currently running, which will be
+/// included in any snapshots of the graph.
+///
+/// This is useful for labeling effects, which cannot otherwise be referred to directly.
+pub fn label_observer(label: impl Into<String>) {
+    if let Some(observer) = Observer::get() {
+        set_label(observer.0, label.into());
+    }
+}
+
+/// Takes a snapshot of every live node in the reactive graph.
+pub fn snapshot() -> GraphSnapshot {
+    GraphSnapshot::new(None)
+}
+
+/// Takes a snapshot of every live node in the reactive graph that was created under the given
+/// owner, or any of its descendants.
+///
+/// Dependencies on nodes outside this part of the graph are not included.
+pub fn snapshot_owner(owner: &Owner) -> GraphSnapshot {
+    GraphSnapshot::new(Some(owner.debug_id()))
+}
+
+/// A snapshot of (part of) the reactive graph at a particular point in time.
+///
+/// Node and owner ids are local to the snapshot: they are assigned in the order in which the
+/// nodes were created, so that two snapshots of the same application state can be compared.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GraphSnapshot {
+    /// The owners of the nodes in the snapshot, parents before their children.
+    pub owners: Vec<OwnerSnapshot>,
+    /// The nodes in the snapshot, in the order they were created.
+    pub nodes: Vec<NodeSnapshot>,
+}
+
+/// An [`Owner`] in a [`GraphSnapshot`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OwnerSnapshot {
+    /// The id of this owner within the snapshot.
+    pub id: usize,
+    /// The id of this owner's parent, if it is included in the snapshot.
+    pub parent: Option<usize>,
+}
+
+/// A reactive node in a [`GraphSnapshot`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeSnapshot {
+    /// The id of this node within the snapshot.
+    pub id: usize,
+    /// The type of reactive node.
+    pub kind: NodeKind,
+    /// The Rust type name of the node.
+    pub type_name: &'static str,
+    /// The label set with [`label`] or [`label_observer`], if any.
+    pub label: Option<String>,
+    /// The location at which the node was created, if known.
+    pub defined_at: Option<String>,
+    /// The id of the owner under which this node was created, if any.
+    pub owner: Option<usize>,
+    /// The ids of the nodes this node depends on.
+    pub sources: Vec<usize>,
+    /// The ids of the nodes that depend on this node.
+    pub subscribers: Vec<usize>,
+}
+
+impl GraphSnapshot {
+    fn new(root_owner: Option<usize>) -> Self {
+        let mut registry = REGISTRY.lock().or_poisoned();
+        registry.prune();
+
+        let mut entries = registry
+            .nodes
+            .iter()
+            .filter(|(_, node)| match root_owner {
+                None => true,
+                Some(root) => node.owners.contains(&root),
+            })
+            .collect::<Vec<_>>();
+        entries.sort_by_key(|(_, node)| node.seq);
+
+        let ids = entries
+            .iter()
+            .enumerate()
+            .map(|(idx, (id, _))| (**id, idx))
+            .collect::<FxHashMap<_, _>>();
+
+        let mut owner_ids = FxHashMap::default();
+        let mut owners = Vec::new();
+        let mut nodes = Vec::with_capacity(entries.len());
+        for (id, node) in &entries {
+            // walk from the outermost included owner inward, so parents come first
+            let chain = match root_owner {
+                None => &node.owners[..],
+                Some(root) => {
+                    let root_idx = node
+                        .owners
+                        .iter()
+                        .position(|o| *o == root)
+                        .unwrap_or(0);
+                    &node.owners[..=root_idx]
+                }
+            };
+            let mut parent = None;
+            for owner in chain.iter().rev() {
+                let owner_id = *owner_ids.entry(*owner).or_insert_with(|| {
+                    owners.push(OwnerSnapshot {
+                        id: owners.len(),
+                        parent,
+                    });
+                    owners.len() - 1
+                });
+                parent = Some(owner_id);
+            }
+
+            let sources = registry
+                .sources
+                .get(id)
+                .map(|sources| {
+                    sources.iter().filter_map(|s| ids.get(s).copied()).collect()
+                })
+                .unwrap_or_default();
+
+            nodes.push(NodeSnapshot {
+                id: nodes.len(),
+                kind: node.kind,
+                type_name: node.type_name,
+                label: node.label.clone(),
+                defined_at: node.defined_at.map(ToString::to_string),
+                owner: parent,
+                sources,
+                subscribers: Vec::new(),
+            });
+        }
+
+        for idx in 0..nodes.len() {
+            for source in nodes[idx].sources.clone() {
+                nodes[source].subscribers.push(idx);
+            }
+        }
+
+        Self { owners, nodes }
+    }
+
+    /// Returns the first node with the given label, if any.
+    pub fn node_by_label(&self, label: &str) -> Option<&NodeSnapshot> {
+        self.nodes
+            .iter()
+            .find(|node| node.label.as_deref() == Some(label))
+    }
+
+    /// Serializes the snapshot as JSON.
+    pub fn to_json(&self) -> String {
+        let mut out = String::from("{\"owners\":[");
+        for (idx, owner) in self.owners.iter().enumerate() {
+            if idx > 0 {
+                out.push(',');
+            }
+            _ = write!(out, "{{\"id\":{},\"parent\":", owner.id);
+            write_json_option(&mut out, owner.parent);
+            out.push('}');
+        }
+        out.push_str("],\"nodes\":[");
+        for (idx, node) in self.nodes.iter().enumerate() {
+            if idx > 0 {
+                out.push(',');
+            }
+            _ = write!(out, "{{\"id\":{},\"kind\":", node.id);
+            write_json_str(&mut out, node.kind.as_str());
+            out.push_str(",\"type\":");
+            write_json_str(&mut out, node.type_name);
+            out.push_str(",\"label\":");
+            match &node.label {
+                Some(label) => write_json_str(&mut out, label),
+                None => out.push_str("null"),
+            }
+            out.push_str(",\"defined_at\":");
+            match &node.defined_at {
+                Some(defined_at) => write_json_str(&mut out, defined_at),
+                None => out.push_str("null"),
+            }
+            out.push_str(",\"owner\":");
+            write_json_option(&mut out, node.owner);
+            _ = write!(
+                out,
+                ",\"sources\":{:?},\"subscribers\":{:?}}}",
+                node.sources, node.subscribers
+            );
+        }
+        out.push_str("]}");
+        out
+    }
+
+    /// Serializes the snapshot as a Graphviz DOT graph, with an edge pointing from each source
+    /// to its subscribers, and nodes grouped into clusters by owner.
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph reactive_graph {\n");
+        let mut by_owner =
+            FxHashMap::<Option<usize>, Vec<&NodeSnapshot>>::default();
+        for node in &self.nodes {
+            by_owner.entry(node.owner).or_default().push(node);
+        }
+
+        fn write_node(out: &mut String, indent: &str, node: &NodeSnapshot) {
+            let name = node.label.as_deref().unwrap_or(node.kind.as_str());
+            let mut label = format!("{name} #{}", node.id);
+            if let Some(defined_at) = &node.defined_at {
+                _ = write!(label, "\\n{defined_at}");
+            }
+            let shape = match node.kind {
+                NodeKind::Signal | NodeKind::Trigger => "box",
+                NodeKind::Memo | NodeKind::AsyncDerived => "ellipse",
+                NodeKind::Effect
+                | NodeKind::RenderEffect
+                | NodeKind::ImmediateEffect => "diamond",
+            };
+            _ = writeln!(
+                out,
+                "{indent}n{} [label=\"{}\", shape={shape}];",
+                node.id,
+                label.replace('"', "\\\"")
+            );
+        }
+
+        if let Some(nodes) = by_owner.get(&None) {
+            for node in nodes {
+                write_node(&mut out, "  ", node);
+            }
+        }
+        for owner in &self.owners {
+            if let Some(nodes) = by_owner.get(&Some(owner.id)) {
+                _ = writeln!(out, "  subgraph cluster_owner_{} {{", owner.id);
+                _ = writeln!(out, "    label=\"owner #{}\";", owner.id);
+                for node in nodes {
+                    write_node(&mut out, "    ", node);
+                }
+                out.push_str("  }\n");
+            }
+        }
+        for node in &self.nodes {
+            for subscriber in &node.subscribers {
+                _ = writeln!(out, "  n{} -> n{subscriber};", node.id);
+            }
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+fn write_json_option(out: &mut String, value: Option<usize>) {
+    match value {
+        Some(value) => _ = write!(out, "{value}"),
+        None => out.push_str("null"),
+    }
+}
@@ -80,12 +80,18 @@ pub mod computed;
 pub mod diagnostics;
 pub mod effect;
 pub mod graph;
+#[cfg(feature = "inspector")]
+pub mod inspector;
+#[cfg(any(feature = "inspector", feature = "tracing"))]
+mod json;
 pub mod owner;
 pub mod send_wrapper_ext;
 #[cfg(feature = "serde")]
 mod serde;
 pub mod signal;
 mod trait_options;
+#[cfg(feature = "tracing")]
+pub mod trace;
 pub mod traits;
 pub mod transition;
 pub mod wrappers;
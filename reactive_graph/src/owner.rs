@@ -1,5 +1,6 @@
 //! The reactive ownership model, which manages effect cancellation, cleanups, and arena allocation.
 
+use crate::graph::{AnySubscriber, ReactiveNode};
 #[cfg(feature = "hydration")]
 use hydration_context::SharedContext;
 use or_poisoned::OrPoisoned;
@@ -22,9 +23,11 @@ use self::arena::Arena;
 pub use arc_stored_value::ArcStoredValue;
 #[cfg(feature = "sandboxed-arenas")]
 pub use arena::sandboxed::Sandboxed;
+#[cfg(feature = "leak-diagnostics")]
+pub(crate) use arena::ArenaHandle;
 #[cfg(feature = "sandboxed-arenas")]
 use arena::ArenaMap;
-use arena::NodeId;
+pub(crate) use arena::NodeId;
 pub use arena_item::*;
 pub use context::*;
 pub use storage::*;
@@ -158,6 +161,8 @@ impl Owner {
                 parent: parent.clone(),
                 nodes: Default::default(),
                 contexts: Default::default(),
+                #[cfg(any(debug_assertions, leptos_debuginfo))]
+                context_names: Default::default(),
                 cleanups: Default::default(),
                 children: Default::default(),
                 #[cfg(feature = "sandboxed-arenas")]
@@ -167,6 +172,7 @@ impl Owner {
                     .map(|parent| parent.read().or_poisoned().arena.clone())
                     .unwrap_or_default(),
                 paused: false,
+                missed: Vec::new(),
             })),
             #[cfg(feature = "hydration")]
             shared_context,
@@ -196,11 +202,14 @@ impl Owner {
                 parent: None,
                 nodes: Default::default(),
                 contexts: Default::default(),
+                #[cfg(any(debug_assertions, leptos_debuginfo))]
+                context_names: Default::default(),
                 cleanups: Default::default(),
                 children: Default::default(),
                 #[cfg(feature = "sandboxed-arenas")]
                 arena: Default::default(),
                 paused: false,
+                missed: Vec::new(),
             })),
             #[cfg(feature = "hydration")]
             shared_context,
@@ -240,11 +249,14 @@ impl Owner {
                 parent,
                 nodes: Default::default(),
                 contexts: Default::default(),
+                #[cfg(any(debug_assertions, leptos_debuginfo))]
+                context_names: Default::default(),
                 cleanups: Default::default(),
                 children: Default::default(),
                 #[cfg(feature = "sandboxed-arenas")]
                 arena,
                 paused,
+                missed: Vec::new(),
             })),
             #[cfg(feature = "hydration")]
             shared_context: self.shared_context.clone(),
@@ -460,17 +472,51 @@ impl Owner {
     /// All children will also be resumed.
     ///
     /// This will *not* cause side effects that were notified while paused to run, until they are
-    /// notified again by a source after being resumed.
+    /// notified again by a source after being resumed. To run them, use
+    /// [`Owner::resume_and_update`] instead.
     pub fn resume(&self) {
+        self.resume_inner();
+    }
+
+    /// Resumes side effects that have been paused by [`Owner::pause`], like [`Owner::resume`], and
+    /// then notifies any that were notified while paused, so that those whose sources have
+    /// changed run again and catch up.
+    ///
+    /// All children will also be resumed.
+    pub fn resume_and_update(&self) {
+        for subscriber in self.resume_inner() {
+            subscriber.mark_check();
+        }
+    }
+
+    /// Resumes this owner and its descendants, and returns the subscribers that were notified
+    /// while they were paused.
+    fn resume_inner(&self) -> Vec<AnySubscriber> {
+        let mut missed = Vec::new();
         let mut stack = Vec::with_capacity(16);
         stack.push(Arc::downgrade(&self.inner));
         while let Some(curr) = stack.pop() {
             if let Some(curr) = curr.upgrade() {
                 let mut curr = curr.write().or_poisoned();
                 curr.paused = false;
+                missed.append(&mut curr.missed);
                 stack.extend(curr.children.iter().map(Weak::clone));
             }
         }
+        missed
+    }
+
+    /// Whether this owner has been paused by [`Owner::pause`]. If it has, the subscriber is
+    /// recorded as having been notified while paused, for [`Owner::resume_and_update`].
+    pub(crate) fn paused_for(&self, subscriber: &AnySubscriber) -> bool {
+        if !self.paused() {
+            return false;
+        }
+        let mut inner = self.inner.write().or_poisoned();
+        if !inner.missed.contains(subscriber) {
+            inner.missed.push(subscriber.clone());
+        }
+        true
     }
 }
 
@@ -494,11 +540,16 @@ pub(crate) struct OwnerInner {
     pub parent: Option<Weak<RwLock<OwnerInner>>>,
     nodes: Vec<NodeId>,
     pub contexts: FxHashMap<TypeId, Box<dyn Any + Send + Sync>>,
+    // the type names of `contexts`, used to explain missing context
+    #[cfg(any(debug_assertions, leptos_debuginfo))]
+    pub context_names: FxHashMap<TypeId, &'static str>,
     pub cleanups: Vec<Box<dyn FnOnce() + Send + Sync>>,
     pub children: Vec<Weak<RwLock<OwnerInner>>>,
     #[cfg(feature = "sandboxed-arenas")]
     arena: Arc<RwLock<ArenaMap>>,
     paused: bool,
+    // subscribers that were notified while paused
+    missed: Vec<AnySubscriber>,
 }
 
 impl Debug for OwnerInner {
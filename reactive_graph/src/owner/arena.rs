@@ -115,6 +115,56 @@ impl Arena {
     }
 }
 
+/// Identifies the arena a node was stored in, so that it can be found again later.
+#[cfg(feature = "leak-diagnostics")]
+#[derive(Clone)]
+pub(crate) struct ArenaHandle {
+    #[cfg(feature = "sandboxed-arenas")]
+    arena: Option<Weak<RwLock<ArenaMap>>>,
+}
+
+#[cfg(feature = "leak-diagnostics")]
+impl ArenaHandle {
+    /// Returns a handle to the arena that is currently active.
+    pub fn current() -> Self {
+        Self {
+            #[cfg(feature = "sandboxed-arenas")]
+            arena: MAP.with_borrow(Clone::clone),
+        }
+    }
+
+    /// A unique identifier for this arena, while it is alive.
+    pub fn id(&self) -> usize {
+        #[cfg(feature = "sandboxed-arenas")]
+        {
+            self.arena
+                .as_ref()
+                .map(|arena| Weak::as_ptr(arena) as usize)
+                .unwrap_or_default()
+        }
+        #[cfg(not(feature = "sandboxed-arenas"))]
+        {
+            0
+        }
+    }
+
+    /// Whether the node is still stored in this arena.
+    pub fn contains(&self, node: NodeId) -> bool {
+        #[cfg(feature = "sandboxed-arenas")]
+        {
+            self.arena
+                .as_ref()
+                .and_then(Weak::upgrade)
+                .map(|arena| arena.read().or_poisoned().contains_key(node))
+                .unwrap_or(false)
+        }
+        #[cfg(not(feature = "sandboxed-arenas"))]
+        {
+            Arena::with(|arena| arena.contains_key(node))
+        }
+    }
+}
+
 #[cfg(feature = "sandboxed-arenas")]
 pub mod sandboxed {
     use super::{Arena, ArenaMap, MAP};
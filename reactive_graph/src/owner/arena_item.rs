@@ -57,6 +57,12 @@ where
                 owner.register(node);
             }
         });
+        #[cfg(feature = "leak-diagnostics")]
+        crate::diagnostics::register_node(
+            node,
+            std::any::type_name::<T>(),
+            std::panic::Location::caller(),
+        );
 
         Self {
             node,
@@ -102,7 +108,12 @@ impl<T, S: Storage<T>> ArenaItem<T, S> {
     /// Applies a function to a reference to the stored value and returns the result, or `None` if it has already been disposed.
     #[track_caller]
     pub fn try_with_value<U>(&self, fun: impl FnOnce(&T) -> U) -> Option<U> {
-        S::try_with(self.node, fun)
+        let value = S::try_with(self.node, fun);
+        #[cfg(feature = "leak-diagnostics")]
+        if value.is_none() {
+            crate::diagnostics::report_disposed_access(self.node);
+        }
+        value
     }
 
     /// Applies a function to a mutable reference to the stored value and returns the result, or `None` if it has already been disposed.
@@ -111,7 +122,12 @@ impl<T, S: Storage<T>> ArenaItem<T, S> {
         &self,
         fun: impl FnOnce(&mut T) -> U,
     ) -> Option<U> {
-        S::try_with_mut(self.node, fun)
+        let value = S::try_with_mut(self.node, fun);
+        #[cfg(feature = "leak-diagnostics")]
+        if value.is_none() {
+            crate::diagnostics::report_disposed_access(self.node);
+        }
+        value
     }
 }
 
@@ -119,7 +135,7 @@ impl<T: Clone, S: Storage<T>> ArenaItem<T, S> {
     /// Returns a clone of the stored value, or `None` if it has already been disposed.
     #[track_caller]
     pub fn try_get_value(&self) -> Option<T> {
-        S::try_with(self.node, Clone::clone)
+        self.try_with_value(Clone::clone)
     }
 }
 
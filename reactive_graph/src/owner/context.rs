@@ -4,14 +4,20 @@ use std::{
     any::{Any, TypeId},
     collections::VecDeque,
 };
+#[cfg(any(debug_assertions, leptos_debuginfo))]
+use std::{
+    fmt,
+    sync::{Arc, Weak},
+};
 
 impl Owner {
     fn provide_context<T: Send + Sync + 'static>(&self, value: T) {
-        self.inner
-            .write()
-            .or_poisoned()
-            .contexts
-            .insert(value.type_id(), Box::new(value));
+        let mut inner = self.inner.write().or_poisoned();
+        #[cfg(any(debug_assertions, leptos_debuginfo))]
+        inner
+            .context_names
+            .insert(TypeId::of::<T>(), std::any::type_name::<T>());
+        inner.contexts.insert(value.type_id(), Box::new(value));
     }
 
     fn use_context<T: Clone + 'static>(&self) -> Option<T> {
@@ -23,6 +29,8 @@ impl Owner {
         let mut inner = self.inner.write().or_poisoned();
         let contexts = &mut inner.contexts;
         if let Some(context) = contexts.remove(&ty) {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            inner.context_names.remove(&ty);
             context.downcast::<T>().ok().map(|n| *n)
         } else {
             let mut parent = inner.parent.as_ref().and_then(|p| p.upgrade());
@@ -33,6 +41,8 @@ impl Owner {
                 let downcast =
                     value.and_then(|context| context.downcast::<T>().ok());
                 if let Some(value) = downcast {
+                    #[cfg(any(debug_assertions, leptos_debuginfo))]
+                    this_parent.context_names.remove(&ty);
                     return Some(*value);
                 } else {
                     parent =
@@ -109,6 +119,75 @@ impl Owner {
             .unwrap_or_else(|| self.find_context_in_children())
     }
 
+    /// Explains why a context value of type `T` cannot be found from this owner: which owners
+    /// were searched, which context types each of them provides, and whether a value of type `T`
+    /// was provided by some other owner that is not an ancestor of this one, like one in a sibling
+    /// branch.
+    ///
+    /// Intended for debugging only. This is used by [`expect_context`] to explain a missing
+    /// context when it panics.
+    #[cfg(any(debug_assertions, leptos_debuginfo))]
+    pub fn missing_context<T: 'static>(&self) -> MissingContext {
+        let ty = TypeId::of::<T>();
+
+        // walk up to the root, in the same order that `use_context` searches
+        let mut searched = Vec::new();
+        let mut chain = Vec::new();
+        let mut curr = Some(Arc::clone(&self.inner));
+        while let Some(owner) = curr {
+            let inner = owner.read().or_poisoned();
+            let mut contexts =
+                inner.context_names.values().copied().collect::<Vec<_>>();
+            contexts.sort_unstable();
+            searched.push(SearchedOwner {
+                id: Arc::as_ptr(&owner) as usize,
+                contexts,
+            });
+            curr = inner.parent.as_ref().and_then(Weak::upgrade);
+            drop(inner);
+            chain.push(owner);
+        }
+
+        // then search down from the root for any other owner that provides it, remembering the
+        // last searched owner that each one descends from
+        let mut provided_elsewhere = Vec::new();
+        let mut to_search = VecDeque::new();
+        if let Some(root) = chain.last() {
+            to_search.push_back((Arc::downgrade(root), None));
+        }
+        while let Some((next, common_ancestor)) = to_search.pop_front() {
+            let Some(owner) = next.upgrade() else {
+                continue;
+            };
+            let id = Arc::as_ptr(&owner) as usize;
+            let is_searched = searched.iter().any(|owner| owner.id == id);
+            let inner = owner.read().or_poisoned();
+            if !is_searched && inner.contexts.contains_key(&ty) {
+                provided_elsewhere.push(ProvidedElsewhere {
+                    id,
+                    common_ancestor,
+                });
+            }
+            let common_ancestor = if is_searched {
+                Some(id)
+            } else {
+                common_ancestor
+            };
+            to_search.extend(
+                inner
+                    .children
+                    .iter()
+                    .map(|child| (Weak::clone(child), common_ancestor)),
+            );
+        }
+
+        MissingContext {
+            type_name: std::any::type_name::<T>(),
+            searched,
+            provided_elsewhere,
+        }
+    }
+
     fn find_context_in_children<T: Clone + 'static>(&self) -> Option<T> {
         let ty = TypeId::of::<T>();
         let inner = self.inner.read().or_poisoned();
@@ -132,6 +211,95 @@ impl Owner {
     }
 }
 
+/// Explains why a context value could not be found, as returned by [`Owner::missing_context`].
+///
+/// Its [`Display`](fmt::Display) implementation describes the owners that were searched, and
+/// any other owners that provide the value.
+#[cfg(any(debug_assertions, leptos_debuginfo))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingContext {
+    /// The name of the type of the context value.
+    pub type_name: &'static str,
+    /// The owners that were searched, beginning with the owner the context was requested from and
+    /// ending with its root.
+    pub searched: Vec<SearchedOwner>,
+    /// Owners that provide a value of this type, but are not among the searched owners.
+    pub provided_elsewhere: Vec<ProvidedElsewhere>,
+}
+
+/// An owner that was searched for a context value, as part of a [`MissingContext`].
+#[cfg(any(debug_assertions, leptos_debuginfo))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchedOwner {
+    /// The [`Owner::debug_id`] of the owner.
+    pub id: usize,
+    /// The type names of the context values that this owner provides.
+    pub contexts: Vec<&'static str>,
+}
+
+/// An owner that provides a context value, but is not an ancestor of the owner that it was
+/// requested from, as part of a [`MissingContext`].
+#[cfg(any(debug_assertions, leptos_debuginfo))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProvidedElsewhere {
+    /// The [`Owner::debug_id`] of the owner.
+    pub id: usize,
+    /// The [`Owner::debug_id`] of the nearest searched owner that this owner descends from.
+    ///
+    /// If this is the owner the context was requested from, the value was provided by one of its
+    /// descendants. Otherwise, it was provided in a sibling branch.
+    pub common_ancestor: Option<usize>,
+}
+
+#[cfg(any(debug_assertions, leptos_debuginfo))]
+impl fmt::Display for MissingContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "no context of type `{}` was found in these owners, searched \
+             from the current owner up to its root:",
+            self.type_name
+        )?;
+        for (idx, owner) in self.searched.iter().enumerate() {
+            write!(f, "  {:#x}", owner.id)?;
+            if idx == 0 {
+                f.write_str(" (current)")?;
+            }
+            if owner.contexts.is_empty() {
+                writeln!(f, ": no context")?;
+            } else {
+                writeln!(f, ": {}", owner.contexts.join(", "))?;
+            }
+        }
+        let current = self.searched.first().map(|owner| owner.id);
+        for elsewhere in &self.provided_elsewhere {
+            match elsewhere.common_ancestor {
+                Some(ancestor) if Some(ancestor) == current => writeln!(
+                    f,
+                    "`{}` is provided by {:#x}, a descendant of the current \
+                     owner: context is only visible to the owner that \
+                     provides it and its descendants",
+                    self.type_name, elsewhere.id
+                )?,
+                Some(ancestor) => writeln!(
+                    f,
+                    "`{}` is provided by {:#x}, in a sibling branch that \
+                     only shares the ancestor {ancestor:#x} with the current \
+                     owner: try providing it from {ancestor:#x} or above",
+                    self.type_name, elsewhere.id
+                )?,
+                None => writeln!(
+                    f,
+                    "`{}` is provided by {:#x}, which is not connected to the \
+                     current owner",
+                    self.type_name, elsewhere.id
+                )?,
+            }
+        }
+        Ok(())
+    }
+}
+
 /// Provides a context value of type `T` to the current reactive [`Owner`]
 /// and all of its descendants. This can be accessed using [`use_context`].
 ///
@@ -247,6 +415,8 @@ pub fn provide_context<T: Send + Sync + 'static>(value: T) {
 /// });
 /// # });
 /// ```
+///
+/// In debug builds, `Owner::missing_context` explains why a value could not be found.
 pub fn use_context<T: Clone + 'static>() -> Option<T> {
     Owner::current().and_then(|owner| owner.use_context())
 }
@@ -298,15 +468,26 @@ pub fn use_context<T: Clone + 'static>() -> Option<T> {
 /// ## Panics
 /// Panics if a context of this type is not found in the current reactive
 /// owner or its ancestors.
+///
+/// In debug builds, the panic message lists the owners that were searched along with the
+/// context types each of them provides, and any owner in another branch that provides this type.
 #[track_caller]
 pub fn expect_context<T: Clone + 'static>() -> T {
     let location = std::panic::Location::caller();
 
     use_context().unwrap_or_else(|| {
+        #[cfg(any(debug_assertions, leptos_debuginfo))]
+        let diagnostics = match Owner::current() {
+            Some(owner) => owner.missing_context::<T>().to_string(),
+            None => String::from("there is no current owner"),
+        };
+        #[cfg(not(any(debug_assertions, leptos_debuginfo)))]
+        let diagnostics = "";
         panic!(
-            "{:?} expected context of type {:?} to be present",
+            "{:?} expected context of type {:?} to be present\n{}",
             location,
-            std::any::type_name::<T>()
+            std::any::type_name::<T>(),
+            diagnostics
         )
     })
 }
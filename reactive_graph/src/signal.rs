@@ -1,26 +1,40 @@
 //! Reactive primitives for root values that can be changed, notifying other nodes in the reactive
 //! graph.
 
+mod arc_history;
 mod arc_read;
 mod arc_rw;
+mod arc_signal_map;
+mod arc_signal_vec;
 mod arc_trigger;
 mod arc_write;
+mod diff_stream;
 pub mod guards;
+mod history;
 mod mapped;
 mod read;
 mod rw;
+mod signal_map;
+mod signal_vec;
 mod subscriber_traits;
 mod trigger;
 mod write;
 
 use crate::owner::LocalStorage;
+pub use arc_history::*;
 pub use arc_read::*;
 pub use arc_rw::*;
+pub use arc_signal_map::*;
+pub use arc_signal_vec::*;
 pub use arc_trigger::*;
 pub use arc_write::*;
+pub use diff_stream::DiffStream;
+pub use history::*;
 pub use mapped::*;
 pub use read::*;
 pub use rw::*;
+pub use signal_map::*;
+pub use signal_vec::*;
 pub use trigger::*;
 pub use write::*;
 
@@ -0,0 +1,398 @@
+use super::{
+    guards::{Plain, ReadGuard, UntrackedWriteGuard, WriteGuard},
+    subscriber_traits::AsSubscriberSet,
+    ArcTrigger,
+};
+use crate::{
+    graph::{ReactiveNode, SubscriberSet},
+    traits::{
+        DefinedAt, IsDisposed, Notify, ReadUntracked, Track, UntrackableGuard,
+        Write,
+    },
+};
+use core::fmt::{Debug, Formatter, Result};
+use guardian::ArcRwLockWriteGuardian;
+use or_poisoned::OrPoisoned;
+use std::{
+    collections::VecDeque,
+    hash::Hash,
+    mem,
+    panic::Location,
+    sync::{Arc, Mutex, RwLock},
+};
+
+/// The number of undo steps kept by [`ArcHistorySignal::new`].
+pub const DEFAULT_HISTORY_CAPACITY: usize = 100;
+
+/// A reference-counted signal that records its previous values, allowing changes to be undone
+/// and redone.
+///
+/// This behaves like an [`ArcRwSignal`](super::ArcRwSignal): it implements the same
+/// [`Read`](crate::traits::Read), [`Write`](crate::traits::Write), and
+/// [`Update`](crate::traits::Update) traits. In addition, each write that notifies subscribers
+/// stores a clone of the previous value in a bounded history, which can be restored with
+/// [`undo`](ArcHistorySignal::undo) and [`redo`](ArcHistorySignal::redo).
+///
+/// Writes that do not notify subscribers (like [`write_untracked`](crate::traits::Write::write_untracked),
+/// or an [`maybe_update`](crate::traits::Update::maybe_update) that returns `false`) do not
+/// create a history entry.
+///
+/// This is a reference-counted signal, which is `Clone` but not `Copy`.
+/// For arena-allocated `Copy` signals, use [`HistorySignal`](super::HistorySignal).
+///
+/// ## Examples
+///
+/// ```
+/// # use reactive_graph::prelude::*;
+/// # use reactive_graph::signal::*; let owner = reactive_graph::owner::Owner::new(); owner.set();
+/// let text = ArcHistorySignal::new(String::new());
+/// assert!(!text.can_undo());
+///
+/// text.set("Hello".to_string());
+/// text.update(|text| text.push_str(", world!"));
+/// assert_eq!(text.get(), "Hello, world!");
+///
+/// text.undo();
+/// assert_eq!(text.get(), "Hello");
+/// text.undo();
+/// assert_eq!(text.get(), "");
+/// assert!(!text.can_undo());
+///
+/// text.redo();
+/// assert_eq!(text.get(), "Hello");
+///
+/// // several writes can be grouped into a single history entry
+/// text.batch(|| {
+///     text.update(|text| text.push('!'));
+///     text.update(|text| text.push('!'));
+/// });
+/// assert_eq!(text.get(), "Hello!!");
+/// text.undo();
+/// assert_eq!(text.get(), "Hello");
+/// ```
+pub struct ArcHistorySignal<T> {
+    #[cfg(any(debug_assertions, leptos_debuginfo))]
+    pub(crate) defined_at: &'static Location<'static>,
+    pub(crate) value: Arc<RwLock<T>>,
+    pub(crate) inner: Arc<RwLock<SubscriberSet>>,
+    pub(crate) history: Arc<RwLock<History<T>>>,
+    pub(crate) history_changed: ArcTrigger,
+}
+
+pub(crate) struct History<T> {
+    capacity: usize,
+    undo: VecDeque<T>,
+    redo: Vec<T>,
+    // Some(has_recorded) while a batch is active
+    batch: Option<bool>,
+}
+
+impl<T> History<T> {
+    fn record(&mut self, previous: T) {
+        self.redo.clear();
+        match self.batch {
+            Some(true) => {}
+            _ => {
+                if self.batch.is_some() {
+                    self.batch = Some(true);
+                }
+                if self.capacity == 0 {
+                    return;
+                }
+                if self.undo.len() == self.capacity {
+                    self.undo.pop_front();
+                }
+                self.undo.push_back(previous);
+            }
+        }
+    }
+}
+
+impl<T> Clone for ArcHistorySignal<T> {
+    #[track_caller]
+    fn clone(&self) -> Self {
+        Self {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: self.defined_at,
+            value: Arc::clone(&self.value),
+            inner: Arc::clone(&self.inner),
+            history: Arc::clone(&self.history),
+            history_changed: self.history_changed.clone(),
+        }
+    }
+}
+
+impl<T> Debug for ArcHistorySignal<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_struct("ArcHistorySignal")
+            .field("type", &std::any::type_name::<T>())
+            .field("value", &Arc::as_ptr(&self.value))
+            .finish()
+    }
+}
+
+impl<T> PartialEq for ArcHistorySignal<T> {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.value, &other.value)
+    }
+}
+
+impl<T> Eq for ArcHistorySignal<T> {}
+
+impl<T> Hash for ArcHistorySignal<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        std::ptr::hash(&Arc::as_ptr(&self.value), state);
+    }
+}
+
+impl<T> Default for ArcHistorySignal<T>
+where
+    T: Default,
+{
+    #[track_caller]
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T> ArcHistorySignal<T> {
+    /// Creates a new signal, taking the initial value as its argument, which keeps up to
+    /// [`DEFAULT_HISTORY_CAPACITY`] undo steps.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "trace", skip_all)
+    )]
+    #[track_caller]
+    pub fn new(value: T) -> Self {
+        Self::with_capacity(value, DEFAULT_HISTORY_CAPACITY)
+    }
+
+    /// Creates a new signal, taking the initial value as its argument, which keeps up to
+    /// `capacity` undo steps. Once the history is full, the oldest entry is discarded.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "trace", skip_all)
+    )]
+    #[track_caller]
+    pub fn with_capacity(value: T, capacity: usize) -> Self {
+        let inner = Arc::new(RwLock::new(SubscriberSet::new()));
+        #[cfg(feature = "inspector")]
+        crate::inspector::register(
+            crate::inspector::NodeKind::Signal,
+            &inner,
+            std::any::type_name::<Self>(),
+            Some(Location::caller()),
+        );
+        Self {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: Location::caller(),
+            value: Arc::new(RwLock::new(value)),
+            inner,
+            history: Arc::new(RwLock::new(History {
+                capacity,
+                undo: VecDeque::new(),
+                redo: Vec::new(),
+                batch: None,
+            })),
+            history_changed: ArcTrigger::new(),
+        }
+    }
+
+    /// Whether there are any changes that can be undone.
+    ///
+    /// This is reactive: calling it inside an effect or memo will subscribe to changes in the
+    /// history.
+    #[track_caller]
+    pub fn can_undo(&self) -> bool {
+        self.history_changed.track();
+        !self.history.read().or_poisoned().undo.is_empty()
+    }
+
+    /// Whether there are any undone changes that can be redone.
+    ///
+    /// This is reactive: calling it inside an effect or memo will subscribe to changes in the
+    /// history.
+    #[track_caller]
+    pub fn can_redo(&self) -> bool {
+        self.history_changed.track();
+        !self.history.read().or_poisoned().redo.is_empty()
+    }
+
+    /// Restores the value from before the most recent change, and notifies subscribers.
+    ///
+    /// Returns `false` if there was nothing to undo.
+    pub fn undo(&self) -> bool {
+        self.step(
+            |history| history.undo.pop_back(),
+            |history, value| history.redo.push(value),
+        )
+    }
+
+    /// Reapplies the most recently undone change, and notifies subscribers.
+    ///
+    /// Returns `false` if there was nothing to redo.
+    pub fn redo(&self) -> bool {
+        self.step(
+            |history| history.redo.pop(),
+            |history, value| history.undo.push_back(value),
+        )
+    }
+
+    fn step(
+        &self,
+        take: impl FnOnce(&mut History<T>) -> Option<T>,
+        put: impl FnOnce(&mut History<T>, T),
+    ) -> bool {
+        {
+            let mut history = self.history.write().or_poisoned();
+            let Some(mut value) = take(&mut history) else {
+                return false;
+            };
+            mem::swap(&mut *self.value.write().or_poisoned(), &mut value);
+            put(&mut history, value);
+            // an undo or redo ends the current group of batched changes
+            if history.batch.is_some() {
+                history.batch = Some(false);
+            }
+        }
+        self.mark_dirty();
+        self.history_changed.notify();
+        true
+    }
+
+    /// Discards all undo and redo steps, without changing the current value.
+    pub fn clear_history(&self) {
+        {
+            let mut history = self.history.write().or_poisoned();
+            history.undo.clear();
+            history.redo.clear();
+        }
+        self.history_changed.notify();
+    }
+
+    /// Runs the given function, grouping any changes made to this signal inside it into a single
+    /// history entry, so that they are undone and redone together.
+    ///
+    /// Nested batches have no additional effect.
+    pub fn batch<U>(&self, fun: impl FnOnce() -> U) -> U {
+        struct EndBatchOnDrop<'a, T>(Option<&'a RwLock<History<T>>>);
+        impl<T> Drop for EndBatchOnDrop<'_, T> {
+            fn drop(&mut self) {
+                if let Some(history) = self.0 {
+                    history.write().or_poisoned().batch = None;
+                }
+            }
+        }
+
+        let _end_batch = {
+            let mut history = self.history.write().or_poisoned();
+            if history.batch.is_none() {
+                history.batch = Some(false);
+                EndBatchOnDrop(Some(&self.history))
+            } else {
+                // Nested batching has no effect.
+                EndBatchOnDrop(None)
+            }
+        };
+        fun()
+    }
+
+    pub(crate) fn history_write_guard(
+        &self,
+    ) -> Option<WriteGuard<PendingEntry<T>, ArcRwLockWriteGuardian<T>>>
+    where
+        T: Clone + 'static,
+    {
+        let guard =
+            ArcRwLockWriteGuardian::take(Arc::clone(&self.value)).ok()?;
+        let previous = (*guard).clone();
+        Some(WriteGuard::new(
+            PendingEntry {
+                signal: self.clone(),
+                previous: Mutex::new(Some(previous)),
+            },
+            guard,
+        ))
+    }
+}
+
+/// The previous value of an [`ArcHistorySignal`], which is added to its history when a write
+/// guard notifies subscribers.
+#[doc(hidden)]
+pub struct PendingEntry<T> {
+    signal: ArcHistorySignal<T>,
+    previous: Mutex<Option<T>>,
+}
+
+impl<T> Debug for PendingEntry<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_struct("PendingEntry").finish_non_exhaustive()
+    }
+}
+
+impl<T> Notify for PendingEntry<T> {
+    fn notify(&self) {
+        if let Some(previous) = self.previous.lock().or_poisoned().take() {
+            self.signal.history.write().or_poisoned().record(previous);
+            self.signal.history_changed.notify();
+        }
+        self.signal.notify();
+    }
+}
+
+impl<T> DefinedAt for ArcHistorySignal<T> {
+    #[inline(always)]
+    fn defined_at(&self) -> Option<&'static Location<'static>> {
+        #[cfg(any(debug_assertions, leptos_debuginfo))]
+        {
+            Some(self.defined_at)
+        }
+        #[cfg(not(any(debug_assertions, leptos_debuginfo)))]
+        {
+            None
+        }
+    }
+}
+
+impl<T> IsDisposed for ArcHistorySignal<T> {
+    #[inline(always)]
+    fn is_disposed(&self) -> bool {
+        false
+    }
+}
+
+impl<T> AsSubscriberSet for ArcHistorySignal<T> {
+    type Output = Arc<RwLock<SubscriberSet>>;
+
+    #[inline(always)]
+    fn as_subscriber_set(&self) -> Option<Self::Output> {
+        Some(Arc::clone(&self.inner))
+    }
+}
+
+impl<T: 'static> ReadUntracked for ArcHistorySignal<T> {
+    type Value = ReadGuard<T, Plain<T>>;
+
+    fn try_read_untracked(&self) -> Option<Self::Value> {
+        Plain::try_new(Arc::clone(&self.value)).map(ReadGuard::new)
+    }
+}
+
+impl<T> Notify for ArcHistorySignal<T> {
+    fn notify(&self) {
+        self.mark_dirty();
+    }
+}
+
+impl<T: Clone + 'static> Write for ArcHistorySignal<T> {
+    type Value = T;
+
+    fn try_write(&self) -> Option<impl UntrackableGuard<Target = Self::Value>> {
+        self.history_write_guard()
+    }
+
+    #[allow(refining_impl_trait)]
+    fn try_write_untracked(&self) -> Option<UntrackedWriteGuard<Self::Value>> {
+        UntrackedWriteGuard::try_new(Arc::clone(&self.value))
+    }
+}
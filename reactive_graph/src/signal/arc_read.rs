@@ -1,12 +1,17 @@
 use super::{
+    arc_signal,
     guards::{Plain, ReadGuard},
     subscriber_traits::AsSubscriberSet,
 };
 use crate::{
     graph::SubscriberSet,
-    traits::{DefinedAt, IntoInner, IsDisposed, ReadUntracked},
+    traits::{
+        drive_stream, DefinedAt, IntoInner, IsDisposed, ReadUntracked, Set,
+    },
 };
+use any_spawner::Executor;
 use core::fmt::{Debug, Formatter, Result};
+use futures::Stream;
 use std::{
     hash::Hash,
     panic::Location,
@@ -84,15 +89,74 @@ impl<T> Debug for ArcReadSignal<T> {
 impl<T: Default> Default for ArcReadSignal<T> {
     #[track_caller]
     fn default() -> Self {
+        let inner = Arc::new(RwLock::new(SubscriberSet::new()));
+        #[cfg(feature = "inspector")]
+        crate::inspector::register(
+            crate::inspector::NodeKind::Signal,
+            &inner,
+            std::any::type_name::<Self>(),
+            Some(Location::caller()),
+        );
         Self {
             #[cfg(any(debug_assertions, leptos_debuginfo))]
             defined_at: Location::caller(),
             value: Arc::new(RwLock::new(T::default())),
-            inner: Arc::new(RwLock::new(SubscriberSet::new())),
+            inner,
         }
     }
 }
 
+impl<T> ArcReadSignal<T>
+where
+    T: Send + Sync + 'static,
+{
+    /// Creates a signal that starts with the `initial` value, and is then set to each value of
+    /// the stream, which is driven on the current executor.
+    ///
+    /// The stream is only polled for its next value once the previous value has been set, and
+    /// stops being polled when it ends or when the current owner is cleaned up. Unlike
+    /// [`FromStream::from_stream`](crate::traits::FromStream::from_stream), the value is not
+    /// wrapped in an `Option`.
+    ///
+    /// ```
+    /// # use reactive_graph::prelude::*; use reactive_graph::signal::*;
+    /// # any_spawner::Executor::init_test_executor();
+    /// # let owner = reactive_graph::owner::Owner::new(); owner.set();
+    /// let (tx, rx) = futures::channel::mpsc::unbounded();
+    /// let message = ArcReadSignal::from_stream_with_initial(rx, "connecting");
+    /// assert_eq!(message.get_untracked(), "connecting");
+    ///
+    /// tx.unbounded_send("hello").unwrap();
+    /// # any_spawner::TestExecutor::run_until_stalled();
+    /// assert_eq!(message.get_untracked(), "hello");
+    /// ```
+    #[track_caller]
+    pub fn from_stream_with_initial(
+        stream: impl Stream<Item = T> + Send + 'static,
+        initial: T,
+    ) -> Self {
+        let (read, write) = arc_signal(initial);
+        crate::spawn(drive_stream(stream, move |value| write.set(value)));
+        read
+    }
+
+    /// Creates a signal that starts with the `initial` value, and is then set to each value of
+    /// the stream, which is driven on the current thread.
+    ///
+    /// See [`from_stream_with_initial`](ArcReadSignal::from_stream_with_initial).
+    #[track_caller]
+    pub fn from_stream_with_initial_unsync(
+        stream: impl Stream<Item = T> + 'static,
+        initial: T,
+    ) -> Self {
+        let (read, write) = arc_signal(initial);
+        Executor::spawn_local(drive_stream(stream, move |value| {
+            write.set(value)
+        }));
+        read
+    }
+}
+
 impl<T> PartialEq for ArcReadSignal<T> {
     fn eq(&self, other: &Self) -> bool {
         Arc::ptr_eq(&self.value, &other.value)
@@ -0,0 +1,346 @@
+use super::{
+    diff_stream::{DiffSenders, DiffStream},
+    guards::{Plain, ReadGuard},
+    subscriber_traits::AsSubscriberSet,
+};
+use crate::{
+    graph::{ReactiveNode, SubscriberSet},
+    traits::{DefinedAt, IsDisposed, Notify, ReadUntracked},
+};
+use core::fmt::{Debug, Formatter, Result};
+use indexmap::IndexMap;
+use or_poisoned::OrPoisoned;
+use std::{
+    hash::Hash,
+    panic::Location,
+    sync::{Arc, RwLock},
+};
+
+/// A single structural change to an [`ArcSignalMap`] or [`SignalMap`](super::SignalMap).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MapDiff<K, V> {
+    /// The whole map was replaced with these entries, in order.
+    Replace {
+        /// The new entries.
+        entries: Vec<(K, V)>,
+    },
+    /// A new entry was added to the end of the map.
+    Insert {
+        /// The key of the new entry.
+        key: K,
+        /// The value of the new entry.
+        value: V,
+    },
+    /// The value of an existing entry was replaced, without changing its position.
+    Update {
+        /// The key of the entry.
+        key: K,
+        /// The new value.
+        value: V,
+    },
+    /// An entry was removed, shifting all entries after it.
+    Remove {
+        /// The key of the removed entry.
+        key: K,
+    },
+    /// All entries were removed.
+    Clear,
+}
+
+impl<K, V> MapDiff<K, V>
+where
+    K: Hash + Eq,
+{
+    /// Applies this change to an [`IndexMap`], which should be in the state the map was in
+    /// before the change was made.
+    pub fn apply(self, map: &mut IndexMap<K, V>) {
+        match self {
+            MapDiff::Replace { entries } => {
+                *map = entries.into_iter().collect()
+            }
+            MapDiff::Insert { key, value } | MapDiff::Update { key, value } => {
+                map.insert(key, value);
+            }
+            MapDiff::Remove { key } => {
+                map.shift_remove(&key);
+            }
+            MapDiff::Clear => map.clear(),
+        }
+    }
+}
+
+/// A reference-counted signal that holds a map, and records each structural change made to it.
+///
+/// Entries are kept in the order they were inserted. Reading it works like reading an
+/// [`ArcRwSignal<IndexMap<K, V>>`](super::ArcRwSignal): it can be tracked, and is notified
+/// whenever the map changes. Unlike a signal, it can only be changed through methods like
+/// [`insert`](ArcSignalMap::insert) and [`remove`](ArcSignalMap::remove). Each of these
+/// records a [`MapDiff`], which can be received from a [`DiffStream`] created with
+/// [`diffs`](ArcSignalMap::diffs).
+///
+/// This is a reference-counted signal, which is `Clone` but not `Copy`.
+/// For arena-allocated `Copy` signals, use [`SignalMap`](super::SignalMap).
+///
+/// ## Examples
+///
+/// ```
+/// # use reactive_graph::prelude::*;
+/// # use reactive_graph::signal::*; let owner = reactive_graph::owner::Owner::new(); owner.set();
+/// let users = ArcSignalMap::new();
+/// let mut diffs = users.diffs();
+///
+/// users.insert(1, "Alice");
+/// users.insert(1, "Alicia");
+/// users.remove(&1);
+/// assert!(users.read().is_empty());
+///
+/// assert_eq!(diffs.try_next(), Some(MapDiff::Replace { entries: vec![] }));
+/// assert_eq!(diffs.try_next(), Some(MapDiff::Insert { key: 1, value: "Alice" }));
+/// assert_eq!(diffs.try_next(), Some(MapDiff::Update { key: 1, value: "Alicia" }));
+/// assert_eq!(diffs.try_next(), Some(MapDiff::Remove { key: 1 }));
+/// ```
+pub struct ArcSignalMap<K, V> {
+    #[cfg(any(debug_assertions, leptos_debuginfo))]
+    pub(crate) defined_at: &'static Location<'static>,
+    pub(crate) value: Arc<RwLock<IndexMap<K, V>>>,
+    pub(crate) inner: Arc<RwLock<SubscriberSet>>,
+    pub(crate) senders: DiffSenders<MapDiff<K, V>>,
+}
+
+impl<K, V> Clone for ArcSignalMap<K, V> {
+    #[track_caller]
+    fn clone(&self) -> Self {
+        Self {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: self.defined_at,
+            value: Arc::clone(&self.value),
+            inner: Arc::clone(&self.inner),
+            senders: self.senders.clone(),
+        }
+    }
+}
+
+impl<K, V> Debug for ArcSignalMap<K, V> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_struct("ArcSignalMap")
+            .field("key", &std::any::type_name::<K>())
+            .field("value", &std::any::type_name::<V>())
+            .field("map", &Arc::as_ptr(&self.value))
+            .finish()
+    }
+}
+
+impl<K, V> PartialEq for ArcSignalMap<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.value, &other.value)
+    }
+}
+
+impl<K, V> Eq for ArcSignalMap<K, V> {}
+
+impl<K, V> Hash for ArcSignalMap<K, V> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        std::ptr::hash(&Arc::as_ptr(&self.value), state);
+    }
+}
+
+impl<K, V> Default for ArcSignalMap<K, V> {
+    #[track_caller]
+    fn default() -> Self {
+        Self::from_map(IndexMap::new())
+    }
+}
+
+impl<K, V> ArcSignalMap<K, V> {
+    /// Creates a new, empty map.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "trace", skip_all)
+    )]
+    #[track_caller]
+    pub fn new() -> Self {
+        Self::from_map(IndexMap::new())
+    }
+
+    /// Creates a new map, taking the initial entries as its argument.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "trace", skip_all)
+    )]
+    #[track_caller]
+    pub fn from_map(map: IndexMap<K, V>) -> Self {
+        let inner = Arc::new(RwLock::new(SubscriberSet::new()));
+        #[cfg(feature = "inspector")]
+        crate::inspector::register(
+            crate::inspector::NodeKind::Signal,
+            &inner,
+            std::any::type_name::<Self>(),
+            Some(Location::caller()),
+        );
+        Self {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: Location::caller(),
+            value: Arc::new(RwLock::new(map)),
+            inner,
+            senders: DiffSenders::default(),
+        }
+    }
+}
+
+impl<K, V> ArcSignalMap<K, V>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+{
+    /// Creates a stream of the changes made to this map.
+    ///
+    /// The stream begins with a [`MapDiff::Replace`] containing the current entries, followed by
+    /// each change made after this is called.
+    pub fn diffs(&self) -> DiffStream<MapDiff<K, V>> {
+        // hold the lock, so that no change can be made between the snapshot and subscribing
+        let value = self.value.read().or_poisoned();
+        self.senders.subscribe(MapDiff::Replace {
+            entries: value
+                .iter()
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect(),
+        })
+    }
+
+    /// Inserts a value for the given key, and returns the previous value, if there was one.
+    ///
+    /// A new key is added to the end of the map, and is recorded as a [`MapDiff::Insert`]. The
+    /// value of an existing key is replaced in place, and is recorded as a [`MapDiff::Update`].
+    #[track_caller]
+    pub fn insert(&self, key: K, value: V) -> Option<V> {
+        let previous = {
+            let mut map = self.value.write().or_poisoned();
+            let listening = self.senders.is_listening();
+            let diff = listening.then(|| (key.clone(), value.clone()));
+            let previous = map.insert(key, value);
+            if let Some((key, value)) = diff {
+                self.senders.send(|| {
+                    if previous.is_some() {
+                        MapDiff::Update { key, value }
+                    } else {
+                        MapDiff::Insert { key, value }
+                    }
+                });
+            }
+            previous
+        };
+        self.notify();
+        previous
+    }
+
+    /// Updates the value for the given key in place, if it exists, and returns whether it did.
+    #[track_caller]
+    pub fn update(&self, key: &K, fun: impl FnOnce(&mut V)) -> bool {
+        let updated = {
+            let mut map = self.value.write().or_poisoned();
+            match map.get_mut(key) {
+                Some(value) => {
+                    fun(value);
+                    self.senders.send(|| MapDiff::Update {
+                        key: key.clone(),
+                        value: value.clone(),
+                    });
+                    true
+                }
+                None => false,
+            }
+        };
+        if updated {
+            self.notify();
+        }
+        updated
+    }
+
+    /// Removes the entry for the given key, and returns its value, if it existed.
+    #[track_caller]
+    pub fn remove(&self, key: &K) -> Option<V> {
+        let removed = {
+            let mut map = self.value.write().or_poisoned();
+            let removed = map.shift_remove(key);
+            if removed.is_some() {
+                self.senders.send(|| MapDiff::Remove { key: key.clone() });
+            }
+            removed
+        };
+        if removed.is_some() {
+            self.notify();
+        }
+        removed
+    }
+
+    /// Removes all entries from the map.
+    #[track_caller]
+    pub fn clear(&self) {
+        {
+            let mut map = self.value.write().or_poisoned();
+            map.clear();
+            self.senders.send(|| MapDiff::Clear);
+        }
+        self.notify();
+    }
+
+    /// Replaces all entries in the map.
+    #[track_caller]
+    pub fn replace(&self, entries: IndexMap<K, V>) {
+        {
+            let mut map = self.value.write().or_poisoned();
+            self.senders.send(|| MapDiff::Replace {
+                entries: entries
+                    .iter()
+                    .map(|(key, value)| (key.clone(), value.clone()))
+                    .collect(),
+            });
+            *map = entries;
+        }
+        self.notify();
+    }
+}
+
+impl<K, V> DefinedAt for ArcSignalMap<K, V> {
+    #[inline(always)]
+    fn defined_at(&self) -> Option<&'static Location<'static>> {
+        #[cfg(any(debug_assertions, leptos_debuginfo))]
+        {
+            Some(self.defined_at)
+        }
+        #[cfg(not(any(debug_assertions, leptos_debuginfo)))]
+        {
+            None
+        }
+    }
+}
+
+impl<K, V> IsDisposed for ArcSignalMap<K, V> {
+    #[inline(always)]
+    fn is_disposed(&self) -> bool {
+        false
+    }
+}
+
+impl<K, V> AsSubscriberSet for ArcSignalMap<K, V> {
+    type Output = Arc<RwLock<SubscriberSet>>;
+
+    #[inline(always)]
+    fn as_subscriber_set(&self) -> Option<Self::Output> {
+        Some(Arc::clone(&self.inner))
+    }
+}
+
+impl<K: 'static, V: 'static> ReadUntracked for ArcSignalMap<K, V> {
+    type Value = ReadGuard<IndexMap<K, V>, Plain<IndexMap<K, V>>>;
+
+    fn try_read_untracked(&self) -> Option<Self::Value> {
+        Plain::try_new(Arc::clone(&self.value)).map(ReadGuard::new)
+    }
+}
+
+impl<K, V> Notify for ArcSignalMap<K, V> {
+    fn notify(&self) {
+        self.mark_dirty();
+    }
+}
@@ -0,0 +1,424 @@
+use super::{
+    diff_stream::{DiffSenders, DiffStream},
+    guards::{Plain, ReadGuard},
+    subscriber_traits::AsSubscriberSet,
+};
+use crate::{
+    graph::{ReactiveNode, SubscriberSet},
+    traits::{DefinedAt, IsDisposed, Notify, ReadUntracked},
+};
+use core::fmt::{Debug, Formatter, Result};
+use or_poisoned::OrPoisoned;
+use std::{
+    hash::Hash,
+    panic::Location,
+    sync::{Arc, RwLock, RwLockWriteGuard},
+};
+
+/// A single structural change to an [`ArcSignalVec`] or [`SignalVec`](super::SignalVec).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VecDiff<T> {
+    /// The whole list was replaced with these values.
+    Replace {
+        /// The new values.
+        values: Vec<T>,
+    },
+    /// A value was inserted at this index, shifting all values after it to the right.
+    InsertAt {
+        /// The index of the new value.
+        index: usize,
+        /// The new value.
+        value: T,
+    },
+    /// The value at this index was replaced.
+    UpdateAt {
+        /// The index of the value.
+        index: usize,
+        /// The new value.
+        value: T,
+    },
+    /// The value at this index was removed, shifting all values after it to the left.
+    RemoveAt {
+        /// The index of the removed value.
+        index: usize,
+    },
+    /// The value at `old_index` was removed, and then inserted again at `new_index`.
+    Move {
+        /// The index the value was moved from.
+        old_index: usize,
+        /// The index the value was moved to.
+        new_index: usize,
+    },
+    /// A value was added to the end of the list.
+    Push {
+        /// The new value.
+        value: T,
+    },
+    /// The last value was removed.
+    Pop,
+    /// All values were removed.
+    Clear,
+}
+
+impl<T> VecDiff<T> {
+    /// Applies this change to a `Vec`, which should be in the state the list was in before the
+    /// change was made.
+    ///
+    /// # Panics
+    /// Panics if an index in the change is out of bounds.
+    pub fn apply(self, vec: &mut Vec<T>) {
+        match self {
+            VecDiff::Replace { values } => *vec = values,
+            VecDiff::InsertAt { index, value } => vec.insert(index, value),
+            VecDiff::UpdateAt { index, value } => vec[index] = value,
+            VecDiff::RemoveAt { index } => {
+                vec.remove(index);
+            }
+            VecDiff::Move {
+                old_index,
+                new_index,
+            } => {
+                let value = vec.remove(old_index);
+                vec.insert(new_index, value);
+            }
+            VecDiff::Push { value } => vec.push(value),
+            VecDiff::Pop => {
+                vec.pop();
+            }
+            VecDiff::Clear => vec.clear(),
+        }
+    }
+
+    /// Describes why this change cannot be applied to a list of length `len`, if an index in it
+    /// is out of bounds.
+    fn out_of_bounds(&self, len: usize) -> Option<String> {
+        match self {
+            VecDiff::InsertAt { index, .. } if *index > len => Some(format!(
+                "insertion index (is {index}) should be <= len (is {len})"
+            )),
+            VecDiff::UpdateAt { index, .. } | VecDiff::RemoveAt { index } => {
+                index_out_of_bounds(*index, len)
+            }
+            VecDiff::Move {
+                old_index,
+                new_index,
+            } => index_out_of_bounds(*old_index, len)
+                .or_else(|| index_out_of_bounds(*new_index, len)),
+            _ => None,
+        }
+    }
+}
+
+fn index_out_of_bounds(index: usize, len: usize) -> Option<String> {
+    (index >= len).then(|| {
+        format!(
+            "index out of bounds: the len is {len} but the index is {index}"
+        )
+    })
+}
+
+/// A reference-counted signal that holds a list, and records each structural change made to it.
+///
+/// Reading it works like reading an [`ArcRwSignal<Vec<T>>`](super::ArcRwSignal): it can be
+/// tracked, and is notified whenever the list changes. Unlike a signal, it can only be changed
+/// through methods like [`push`](ArcSignalVec::push), [`insert`](ArcSignalVec::insert),
+/// [`remove`](ArcSignalVec::remove), and [`move_item`](ArcSignalVec::move_item). Each of these
+/// records a [`VecDiff`], which can be received from a [`DiffStream`] created with
+/// [`diffs`](ArcSignalVec::diffs). This allows a consumer, like a rendered list, to apply exactly
+/// the same changes, rather than comparing the whole old and new lists to find them.
+///
+/// This is a reference-counted signal, which is `Clone` but not `Copy`.
+/// For arena-allocated `Copy` signals, use [`SignalVec`](super::SignalVec).
+///
+/// ## Examples
+///
+/// ```
+/// # use reactive_graph::prelude::*;
+/// # use reactive_graph::signal::*; let owner = reactive_graph::owner::Owner::new(); owner.set();
+/// let list = ArcSignalVec::new(vec![1, 2]);
+/// let mut diffs = list.diffs();
+///
+/// list.push(3);
+/// list.move_item(2, 0);
+/// assert_eq!(list.get(), vec![3, 1, 2]);
+///
+/// assert_eq!(diffs.try_next(), Some(VecDiff::Replace { values: vec![1, 2] }));
+/// assert_eq!(diffs.try_next(), Some(VecDiff::Push { value: 3 }));
+/// assert_eq!(
+///     diffs.try_next(),
+///     Some(VecDiff::Move { old_index: 2, new_index: 0 })
+/// );
+/// assert_eq!(diffs.try_next(), None);
+/// ```
+pub struct ArcSignalVec<T> {
+    #[cfg(any(debug_assertions, leptos_debuginfo))]
+    pub(crate) defined_at: &'static Location<'static>,
+    pub(crate) value: Arc<RwLock<Vec<T>>>,
+    pub(crate) inner: Arc<RwLock<SubscriberSet>>,
+    pub(crate) senders: DiffSenders<VecDiff<T>>,
+}
+
+impl<T> Clone for ArcSignalVec<T> {
+    #[track_caller]
+    fn clone(&self) -> Self {
+        Self {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: self.defined_at,
+            value: Arc::clone(&self.value),
+            inner: Arc::clone(&self.inner),
+            senders: self.senders.clone(),
+        }
+    }
+}
+
+impl<T> Debug for ArcSignalVec<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_struct("ArcSignalVec")
+            .field("type", &std::any::type_name::<T>())
+            .field("value", &Arc::as_ptr(&self.value))
+            .finish()
+    }
+}
+
+impl<T> PartialEq for ArcSignalVec<T> {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.value, &other.value)
+    }
+}
+
+impl<T> Eq for ArcSignalVec<T> {}
+
+impl<T> Hash for ArcSignalVec<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        std::ptr::hash(&Arc::as_ptr(&self.value), state);
+    }
+}
+
+impl<T> Default for ArcSignalVec<T> {
+    #[track_caller]
+    fn default() -> Self {
+        Self::new(Vec::new())
+    }
+}
+
+impl<T> ArcSignalVec<T> {
+    /// Creates a new list, taking the initial values as its argument.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "trace", skip_all)
+    )]
+    #[track_caller]
+    pub fn new(values: Vec<T>) -> Self {
+        let inner = Arc::new(RwLock::new(SubscriberSet::new()));
+        #[cfg(feature = "inspector")]
+        crate::inspector::register(
+            crate::inspector::NodeKind::Signal,
+            &inner,
+            std::any::type_name::<Self>(),
+            Some(Location::caller()),
+        );
+        Self {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: Location::caller(),
+            value: Arc::new(RwLock::new(values)),
+            inner,
+            senders: DiffSenders::default(),
+        }
+    }
+}
+
+impl<T: Clone> ArcSignalVec<T> {
+    /// Creates a stream of the changes made to this list.
+    ///
+    /// The stream begins with a [`VecDiff::Replace`] containing the current values, followed by
+    /// each change made after this is called.
+    pub fn diffs(&self) -> DiffStream<VecDiff<T>> {
+        // hold the lock, so that no change can be made between the snapshot and subscribing
+        let value = self.value.read().or_poisoned();
+        self.senders.subscribe(VecDiff::Replace {
+            values: value.clone(),
+        })
+    }
+
+    /// Applies a change to the list, records it, and notifies subscribers.
+    ///
+    /// # Panics
+    /// Panics if an index in the change is out of bounds.
+    #[track_caller]
+    pub fn apply(&self, diff: VecDiff<T>) {
+        {
+            let mut value = self.write_checked(|len| diff.out_of_bounds(len));
+            // applying the change consumes it, so it is cloned first if anyone will receive it
+            let recorded = self.senders.is_listening().then(|| diff.clone());
+            diff.apply(&mut value);
+            if let Some(recorded) = recorded {
+                self.senders.send(|| recorded);
+            }
+        }
+        self.notify();
+    }
+
+    /// Adds a value to the end of the list.
+    #[track_caller]
+    pub fn push(&self, value: T) {
+        self.apply(VecDiff::Push { value });
+    }
+
+    /// Removes the last value from the list, and returns it, or `None` if it is empty.
+    #[track_caller]
+    pub fn pop(&self) -> Option<T> {
+        let popped = {
+            let mut value = self.value.write().or_poisoned();
+            let popped = value.pop();
+            if popped.is_some() {
+                self.senders.send(|| VecDiff::Pop);
+            }
+            popped
+        };
+        if popped.is_some() {
+            self.notify();
+        }
+        popped
+    }
+
+    /// Inserts a value at `index`, shifting all values after it to the right.
+    ///
+    /// # Panics
+    /// Panics if `index > len`.
+    #[track_caller]
+    pub fn insert(&self, index: usize, value: T) {
+        self.apply(VecDiff::InsertAt { index, value });
+    }
+
+    /// Removes and returns the value at `index`, shifting all values after it to the left.
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds.
+    #[track_caller]
+    pub fn remove(&self, index: usize) -> T {
+        let removed = {
+            let mut value =
+                self.write_checked(|len| index_out_of_bounds(index, len));
+            let removed = value.remove(index);
+            self.senders.send(|| VecDiff::RemoveAt { index });
+            removed
+        };
+        self.notify();
+        removed
+    }
+
+    /// Replaces the value at `index`.
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds.
+    #[track_caller]
+    pub fn set_at(&self, index: usize, value: T) {
+        self.apply(VecDiff::UpdateAt { index, value });
+    }
+
+    /// Updates the value at `index` in place. This is recorded as a [`VecDiff::UpdateAt`] with
+    /// the new value.
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds.
+    #[track_caller]
+    pub fn update_at(&self, index: usize, fun: impl FnOnce(&mut T)) {
+        {
+            let mut value =
+                self.write_checked(|len| index_out_of_bounds(index, len));
+            let item = &mut value[index];
+            fun(item);
+            self.senders.send(|| VecDiff::UpdateAt {
+                index,
+                value: item.clone(),
+            });
+        }
+        self.notify();
+    }
+
+    /// Moves the value at `old_index` so that it is at `new_index`, shifting the values between
+    /// them.
+    ///
+    /// # Panics
+    /// Panics if either index is out of bounds.
+    #[track_caller]
+    pub fn move_item(&self, old_index: usize, new_index: usize) {
+        self.apply(VecDiff::Move {
+            old_index,
+            new_index,
+        });
+    }
+
+    /// Removes all values from the list.
+    #[track_caller]
+    pub fn clear(&self) {
+        self.apply(VecDiff::Clear);
+    }
+
+    /// Replaces all values in the list.
+    #[track_caller]
+    pub fn replace(&self, values: Vec<T>) {
+        self.apply(VecDiff::Replace { values });
+    }
+
+    /// Locks the list for writing, or panics with the message returned by `out_of_bounds`, given
+    /// its length.
+    ///
+    /// The lock is released before panicking, so that an invalid index does not poison it.
+    #[track_caller]
+    fn write_checked(
+        &self,
+        out_of_bounds: impl FnOnce(usize) -> Option<String>,
+    ) -> RwLockWriteGuard<'_, Vec<T>> {
+        let value = self.value.write().or_poisoned();
+        if let Some(message) = out_of_bounds(value.len()) {
+            drop(value);
+            panic!("{message}");
+        }
+        value
+    }
+}
+
+impl<T> DefinedAt for ArcSignalVec<T> {
+    #[inline(always)]
+    fn defined_at(&self) -> Option<&'static Location<'static>> {
+        #[cfg(any(debug_assertions, leptos_debuginfo))]
+        {
+            Some(self.defined_at)
+        }
+        #[cfg(not(any(debug_assertions, leptos_debuginfo)))]
+        {
+            None
+        }
+    }
+}
+
+impl<T> IsDisposed for ArcSignalVec<T> {
+    #[inline(always)]
+    fn is_disposed(&self) -> bool {
+        false
+    }
+}
+
+impl<T> AsSubscriberSet for ArcSignalVec<T> {
+    type Output = Arc<RwLock<SubscriberSet>>;
+
+    #[inline(always)]
+    fn as_subscriber_set(&self) -> Option<Self::Output> {
+        Some(Arc::clone(&self.inner))
+    }
+}
+
+impl<T: 'static> ReadUntracked for ArcSignalVec<T> {
+    type Value = ReadGuard<Vec<T>, Plain<Vec<T>>>;
+
+    fn try_read_untracked(&self) -> Option<Self::Value> {
+        Plain::try_new(Arc::clone(&self.value)).map(ReadGuard::new)
+    }
+}
+
+impl<T> Notify for ArcSignalVec<T> {
+    fn notify(&self) {
+        self.mark_dirty();
+    }
+}
@@ -0,0 +1,91 @@
+use futures::{
+    channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender},
+    Stream,
+};
+use or_poisoned::OrPoisoned;
+use std::{
+    fmt::{Debug, Formatter, Result},
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+
+/// A stream of the structural changes made to a reactive collection, like
+/// [`ArcSignalVec`](super::ArcSignalVec) or [`ArcSignalMap`](super::ArcSignalMap).
+///
+/// The first item is always a snapshot of the whole collection at the time the stream was
+/// created. Each item after that describes a single change. The stream ends when every
+/// reference to the collection has been dropped.
+pub struct DiffStream<D> {
+    rx: UnboundedReceiver<D>,
+}
+
+impl<D> DiffStream<D> {
+    /// Returns the next change that has already been made, without waiting for one.
+    ///
+    /// Returns `None` if no changes are waiting, or if the collection has been dropped.
+    pub fn try_next(&mut self) -> Option<D> {
+        self.rx.try_next().ok().flatten()
+    }
+}
+
+impl<D> Debug for DiffStream<D> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_struct("DiffStream")
+            .field("type", &std::any::type_name::<D>())
+            .finish()
+    }
+}
+
+impl<D> Stream for DiffStream<D> {
+    type Item = D;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.rx).poll_next(cx)
+    }
+}
+
+/// The senders for every open [`DiffStream`] of a collection.
+pub(crate) struct DiffSenders<D>(Arc<Mutex<Vec<UnboundedSender<D>>>>);
+
+impl<D> Clone for DiffSenders<D> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<D> Default for DiffSenders<D> {
+    fn default() -> Self {
+        Self(Default::default())
+    }
+}
+
+impl<D: Clone> DiffSenders<D> {
+    /// Opens a new stream, which begins with the given snapshot.
+    pub fn subscribe(&self, snapshot: D) -> DiffStream<D> {
+        let (tx, rx) = unbounded();
+        _ = tx.unbounded_send(snapshot);
+        self.0.lock().or_poisoned().push(tx);
+        DiffStream { rx }
+    }
+
+    /// Whether any streams have been opened, which may still be listening.
+    pub fn is_listening(&self) -> bool {
+        !self.0.lock().or_poisoned().is_empty()
+    }
+
+    /// Sends a change to every open stream, dropping any that have been closed.
+    ///
+    /// The change is only created if there is at least one open stream.
+    pub fn send(&self, diff: impl FnOnce() -> D) {
+        let mut senders = self.0.lock().or_poisoned();
+        if senders.is_empty() {
+            return;
+        }
+        let diff = diff();
+        senders.retain(|tx| tx.unbounded_send(diff.clone()).is_ok());
+    }
+}
@@ -0,0 +1,359 @@
+use super::{
+    guards::{Plain, ReadGuard, UntrackedWriteGuard},
+    subscriber_traits::AsSubscriberSet,
+    ArcHistorySignal,
+};
+use crate::{
+    graph::{ReactiveNode, SubscriberSet},
+    owner::{ArenaItem, FromLocal, LocalStorage, Storage, SyncStorage},
+    traits::{
+        DefinedAt, Dispose, IsDisposed, Notify, ReadUntracked,
+        UntrackableGuard, Write,
+    },
+    unwrap_signal,
+};
+use core::fmt::Debug;
+use std::{
+    hash::Hash,
+    panic::Location,
+    sync::{Arc, RwLock},
+};
+
+/// An arena-allocated signal that records its previous values, allowing changes to be undone
+/// and redone.
+///
+/// This behaves like an [`RwSignal`](super::RwSignal): it implements the same
+/// [`Read`](crate::traits::Read), [`Write`](crate::traits::Write), and
+/// [`Update`](crate::traits::Update) traits. In addition, each write that notifies subscribers
+/// stores a clone of the previous value in a bounded history, which can be restored with
+/// [`undo`](HistorySignal::undo) and [`redo`](HistorySignal::redo).
+///
+/// This is an arena-allocated signal, which is `Copy` and is disposed when its reactive
+/// [`Owner`](crate::owner::Owner) cleans up. For a reference-counted signal that lives
+/// as long as a reference to it is alive, see [`ArcHistorySignal`].
+///
+/// ## Examples
+///
+/// ```
+/// # use reactive_graph::prelude::*;
+/// # use reactive_graph::signal::*; let owner = reactive_graph::owner::Owner::new(); owner.set();
+/// let count = HistorySignal::new(0);
+///
+/// count.set(1);
+/// count.set(2);
+/// assert_eq!(count.get(), 2);
+///
+/// count.undo();
+/// assert_eq!(count.get(), 1);
+/// assert!(count.can_redo());
+///
+/// // a new change discards anything that could have been redone
+/// count.set(5);
+/// assert!(!count.can_redo());
+/// ```
+pub struct HistorySignal<T, S = SyncStorage> {
+    #[cfg(any(debug_assertions, leptos_debuginfo))]
+    defined_at: &'static Location<'static>,
+    inner: ArenaItem<ArcHistorySignal<T>, S>,
+}
+
+impl<T, S> Dispose for HistorySignal<T, S> {
+    fn dispose(self) {
+        self.inner.dispose()
+    }
+}
+
+impl<T> HistorySignal<T>
+where
+    T: Send + Sync + 'static,
+{
+    /// Creates a new signal, taking the initial value as its argument, which keeps up to
+    /// [`DEFAULT_HISTORY_CAPACITY`](super::DEFAULT_HISTORY_CAPACITY) undo steps.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "trace", skip_all)
+    )]
+    #[track_caller]
+    pub fn new(value: T) -> Self {
+        Self::new_with_storage(value)
+    }
+
+    /// Creates a new signal, taking the initial value as its argument, which keeps up to
+    /// `capacity` undo steps. Once the history is full, the oldest entry is discarded.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "trace", skip_all)
+    )]
+    #[track_caller]
+    pub fn with_capacity(value: T, capacity: usize) -> Self {
+        Self {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: Location::caller(),
+            inner: ArenaItem::new_with_storage(
+                ArcHistorySignal::with_capacity(value, capacity),
+            ),
+        }
+    }
+}
+
+impl<T, S> HistorySignal<T, S>
+where
+    T: 'static,
+    S: Storage<ArcHistorySignal<T>>,
+{
+    /// Creates a new signal with the given arena storage method.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "trace", skip_all)
+    )]
+    #[track_caller]
+    pub fn new_with_storage(value: T) -> Self {
+        Self {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: Location::caller(),
+            inner: ArenaItem::new_with_storage(ArcHistorySignal::new(value)),
+        }
+    }
+
+    /// Whether there are any changes that can be undone.
+    ///
+    /// This is reactive: calling it inside an effect or memo will subscribe to changes in the
+    /// history.
+    #[track_caller]
+    pub fn can_undo(&self) -> bool {
+        self.inner
+            .try_with_value(|inner| inner.can_undo())
+            .unwrap_or_else(unwrap_signal!(self))
+    }
+
+    /// Whether there are any undone changes that can be redone.
+    ///
+    /// This is reactive: calling it inside an effect or memo will subscribe to changes in the
+    /// history.
+    #[track_caller]
+    pub fn can_redo(&self) -> bool {
+        self.inner
+            .try_with_value(|inner| inner.can_redo())
+            .unwrap_or_else(unwrap_signal!(self))
+    }
+
+    /// Restores the value from before the most recent change, and notifies subscribers.
+    ///
+    /// Returns `false` if there was nothing to undo, or the signal has been disposed.
+    pub fn undo(&self) -> bool {
+        self.inner
+            .try_get_value()
+            .map(|inner| inner.undo())
+            .unwrap_or(false)
+    }
+
+    /// Reapplies the most recently undone change, and notifies subscribers.
+    ///
+    /// Returns `false` if there was nothing to redo, or the signal has been disposed.
+    pub fn redo(&self) -> bool {
+        self.inner
+            .try_get_value()
+            .map(|inner| inner.redo())
+            .unwrap_or(false)
+    }
+
+    /// Discards all undo and redo steps, without changing the current value.
+    pub fn clear_history(&self) {
+        if let Some(inner) = self.inner.try_get_value() {
+            inner.clear_history();
+        }
+    }
+
+    /// Runs the given function, grouping any changes made to this signal inside it into a single
+    /// history entry, so that they are undone and redone together.
+    ///
+    /// Nested batches have no additional effect.
+    #[track_caller]
+    pub fn batch<U>(&self, fun: impl FnOnce() -> U) -> U {
+        let inner = self
+            .inner
+            .try_get_value()
+            .unwrap_or_else(unwrap_signal!(self));
+        inner.batch(fun)
+    }
+}
+
+impl<T> HistorySignal<T, LocalStorage>
+where
+    T: 'static,
+{
+    /// Creates a new signal, taking the initial value as its argument. Unlike
+    /// [`HistorySignal::new`], this pins the value to the current thread. Accessing it from any
+    /// other thread will panic.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "trace", skip_all)
+    )]
+    #[track_caller]
+    pub fn new_local(value: T) -> Self {
+        Self::new_with_storage(value)
+    }
+}
+
+impl<T, S> Copy for HistorySignal<T, S> {}
+
+impl<T, S> Clone for HistorySignal<T, S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, S> Debug for HistorySignal<T, S>
+where
+    S: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HistorySignal")
+            .field("type", &std::any::type_name::<T>())
+            .field("store", &self.inner)
+            .finish()
+    }
+}
+
+impl<T, S> Default for HistorySignal<T, S>
+where
+    T: Default + 'static,
+    S: Storage<ArcHistorySignal<T>>,
+{
+    #[track_caller]
+    fn default() -> Self {
+        Self::new_with_storage(T::default())
+    }
+}
+
+impl<T, S> PartialEq for HistorySignal<T, S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+}
+
+impl<T, S> Eq for HistorySignal<T, S> {}
+
+impl<T, S> Hash for HistorySignal<T, S> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.inner.hash(state);
+    }
+}
+
+impl<T, S> DefinedAt for HistorySignal<T, S> {
+    fn defined_at(&self) -> Option<&'static Location<'static>> {
+        #[cfg(any(debug_assertions, leptos_debuginfo))]
+        {
+            Some(self.defined_at)
+        }
+        #[cfg(not(any(debug_assertions, leptos_debuginfo)))]
+        {
+            None
+        }
+    }
+}
+
+impl<T: 'static, S> IsDisposed for HistorySignal<T, S> {
+    fn is_disposed(&self) -> bool {
+        self.inner.is_disposed()
+    }
+}
+
+impl<T, S> AsSubscriberSet for HistorySignal<T, S>
+where
+    S: Storage<ArcHistorySignal<T>>,
+{
+    type Output = Arc<RwLock<SubscriberSet>>;
+
+    fn as_subscriber_set(&self) -> Option<Self::Output> {
+        self.inner
+            .try_with_value(|inner| inner.as_subscriber_set())
+            .flatten()
+    }
+}
+
+impl<T, S> ReadUntracked for HistorySignal<T, S>
+where
+    T: 'static,
+    S: Storage<ArcHistorySignal<T>>,
+{
+    type Value = ReadGuard<T, Plain<T>>;
+
+    fn try_read_untracked(&self) -> Option<Self::Value> {
+        self.inner
+            .try_get_value()
+            .map(|inner| inner.read_untracked())
+    }
+}
+
+impl<T, S> Notify for HistorySignal<T, S>
+where
+    S: Storage<ArcHistorySignal<T>>,
+{
+    fn notify(&self) {
+        self.mark_dirty();
+    }
+}
+
+impl<T, S> Write for HistorySignal<T, S>
+where
+    T: Clone + 'static,
+    S: Storage<ArcHistorySignal<T>>,
+{
+    type Value = T;
+
+    fn try_write(&self) -> Option<impl UntrackableGuard<Target = Self::Value>> {
+        self.inner
+            .try_with_value(|inner| inner.history_write_guard())
+            .flatten()
+    }
+
+    #[allow(refining_impl_trait)]
+    fn try_write_untracked(&self) -> Option<UntrackedWriteGuard<Self::Value>> {
+        self.inner
+            .try_with_value(|inner| inner.try_write_untracked())
+            .flatten()
+    }
+}
+
+impl<T> From<ArcHistorySignal<T>> for HistorySignal<T>
+where
+    T: Send + Sync + 'static,
+{
+    #[track_caller]
+    fn from(value: ArcHistorySignal<T>) -> Self {
+        HistorySignal {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: Location::caller(),
+            inner: ArenaItem::new_with_storage(value),
+        }
+    }
+}
+
+impl<T> FromLocal<ArcHistorySignal<T>> for HistorySignal<T, LocalStorage>
+where
+    T: 'static,
+{
+    #[track_caller]
+    fn from_local(value: ArcHistorySignal<T>) -> Self {
+        HistorySignal {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: Location::caller(),
+            inner: ArenaItem::new_with_storage(value),
+        }
+    }
+}
+
+impl<T, S> From<HistorySignal<T, S>> for ArcHistorySignal<T>
+where
+    T: 'static,
+    S: Storage<ArcHistorySignal<T>>,
+{
+    #[track_caller]
+    fn from(value: HistorySignal<T, S>) -> Self {
+        value
+            .inner
+            .try_get_value()
+            .unwrap_or_else(unwrap_signal!(value))
+    }
+}
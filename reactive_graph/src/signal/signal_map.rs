@@ -0,0 +1,322 @@
+use super::{
+    guards::{Plain, ReadGuard},
+    subscriber_traits::AsSubscriberSet,
+    ArcSignalMap, DiffStream, MapDiff,
+};
+use crate::{
+    graph::{ReactiveNode, SubscriberSet},
+    owner::{ArenaItem, FromLocal, LocalStorage, Storage, SyncStorage},
+    traits::{DefinedAt, Dispose, IsDisposed, Notify, ReadUntracked},
+    unwrap_signal,
+};
+use core::fmt::Debug;
+use indexmap::IndexMap;
+use std::{
+    hash::Hash,
+    panic::Location,
+    sync::{Arc, RwLock},
+};
+
+/// An arena-allocated signal that holds a map, and records each structural change made to it.
+///
+/// This works like an [`ArcSignalMap`]: it can be read like a signal containing an
+/// [`IndexMap<K, V>`], but can only be changed through methods like
+/// [`insert`](SignalMap::insert) and [`remove`](SignalMap::remove), each of which records a
+/// [`MapDiff`] that can be received from a [`DiffStream`] created with
+/// [`diffs`](SignalMap::diffs).
+///
+/// This is an arena-allocated signal, which is `Copy` and is disposed when its reactive
+/// [`Owner`](crate::owner::Owner) cleans up. For a reference-counted signal that lives
+/// as long as a reference to it is alive, see [`ArcSignalMap`].
+///
+/// ## Examples
+///
+/// ```
+/// # use reactive_graph::prelude::*;
+/// # use reactive_graph::signal::*; let owner = reactive_graph::owner::Owner::new(); owner.set();
+/// let scores = SignalMap::new();
+///
+/// scores.insert("alice", 1);
+/// scores.insert("bob", 2);
+/// scores.update(&"alice", |score| *score += 10);
+///
+/// assert_eq!(scores.read().get("alice"), Some(&11));
+/// assert_eq!(scores.read().keys().collect::<Vec<_>>(), vec![&"alice", &"bob"]);
+/// ```
+pub struct SignalMap<K, V, S = SyncStorage> {
+    #[cfg(any(debug_assertions, leptos_debuginfo))]
+    defined_at: &'static Location<'static>,
+    inner: ArenaItem<ArcSignalMap<K, V>, S>,
+}
+
+impl<K, V, S> Dispose for SignalMap<K, V, S> {
+    fn dispose(self) {
+        self.inner.dispose()
+    }
+}
+
+impl<K, V> SignalMap<K, V>
+where
+    K: Send + Sync + 'static,
+    V: Send + Sync + 'static,
+{
+    /// Creates a new, empty map.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "trace", skip_all)
+    )]
+    #[track_caller]
+    pub fn new() -> Self {
+        Self::new_with_storage(IndexMap::new())
+    }
+
+    /// Creates a new map, taking the initial entries as its argument.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "trace", skip_all)
+    )]
+    #[track_caller]
+    pub fn from_map(map: IndexMap<K, V>) -> Self {
+        Self::new_with_storage(map)
+    }
+}
+
+impl<K, V> SignalMap<K, V, LocalStorage>
+where
+    K: 'static,
+    V: 'static,
+{
+    /// Creates a new map, taking the initial entries as its argument. Unlike
+    /// [`SignalMap::from_map`], this pins the value to the current thread. Accessing it from any
+    /// other thread will panic.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "trace", skip_all)
+    )]
+    #[track_caller]
+    pub fn new_local(map: IndexMap<K, V>) -> Self {
+        Self::new_with_storage(map)
+    }
+}
+
+impl<K, V, S> SignalMap<K, V, S>
+where
+    K: 'static,
+    V: 'static,
+    S: Storage<ArcSignalMap<K, V>>,
+{
+    /// Creates a new map with the given arena storage method.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "trace", skip_all)
+    )]
+    #[track_caller]
+    pub fn new_with_storage(map: IndexMap<K, V>) -> Self {
+        Self {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: Location::caller(),
+            inner: ArenaItem::new_with_storage(ArcSignalMap::from_map(map)),
+        }
+    }
+
+    #[track_caller]
+    fn arc(&self) -> ArcSignalMap<K, V> {
+        self.inner
+            .try_get_value()
+            .unwrap_or_else(unwrap_signal!(self))
+    }
+}
+
+impl<K, V, S> SignalMap<K, V, S>
+where
+    K: Hash + Eq + Clone + 'static,
+    V: Clone + 'static,
+    S: Storage<ArcSignalMap<K, V>>,
+{
+    /// Creates a stream of the changes made to this map.
+    ///
+    /// The stream begins with a [`MapDiff::Replace`] containing the current entries, followed by
+    /// each change made after this is called.
+    #[track_caller]
+    pub fn diffs(&self) -> DiffStream<MapDiff<K, V>> {
+        self.arc().diffs()
+    }
+
+    /// Inserts a value for the given key, and returns the previous value, if there was one.
+    ///
+    /// A new key is added to the end of the map, and is recorded as a [`MapDiff::Insert`]. The
+    /// value of an existing key is replaced in place, and is recorded as a [`MapDiff::Update`].
+    #[track_caller]
+    pub fn insert(&self, key: K, value: V) -> Option<V> {
+        self.arc().insert(key, value)
+    }
+
+    /// Updates the value for the given key in place, if it exists, and returns whether it did.
+    #[track_caller]
+    pub fn update(&self, key: &K, fun: impl FnOnce(&mut V)) -> bool {
+        self.arc().update(key, fun)
+    }
+
+    /// Removes the entry for the given key, and returns its value, if it existed.
+    #[track_caller]
+    pub fn remove(&self, key: &K) -> Option<V> {
+        self.arc().remove(key)
+    }
+
+    /// Removes all entries from the map.
+    #[track_caller]
+    pub fn clear(&self) {
+        self.arc().clear()
+    }
+
+    /// Replaces all entries in the map.
+    #[track_caller]
+    pub fn replace(&self, entries: IndexMap<K, V>) {
+        self.arc().replace(entries)
+    }
+}
+
+impl<K, V, S> Copy for SignalMap<K, V, S> {}
+
+impl<K, V, S> Clone for SignalMap<K, V, S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K, V, S> Debug for SignalMap<K, V, S>
+where
+    S: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SignalMap")
+            .field("key", &std::any::type_name::<K>())
+            .field("value", &std::any::type_name::<V>())
+            .field("store", &self.inner)
+            .finish()
+    }
+}
+
+impl<K, V, S> Default for SignalMap<K, V, S>
+where
+    K: 'static,
+    V: 'static,
+    S: Storage<ArcSignalMap<K, V>>,
+{
+    #[track_caller]
+    fn default() -> Self {
+        Self::new_with_storage(IndexMap::new())
+    }
+}
+
+impl<K, V, S> PartialEq for SignalMap<K, V, S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+}
+
+impl<K, V, S> Eq for SignalMap<K, V, S> {}
+
+impl<K, V, S> Hash for SignalMap<K, V, S> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.inner.hash(state);
+    }
+}
+
+impl<K, V, S> DefinedAt for SignalMap<K, V, S> {
+    fn defined_at(&self) -> Option<&'static Location<'static>> {
+        #[cfg(any(debug_assertions, leptos_debuginfo))]
+        {
+            Some(self.defined_at)
+        }
+        #[cfg(not(any(debug_assertions, leptos_debuginfo)))]
+        {
+            None
+        }
+    }
+}
+
+impl<K: 'static, V: 'static, S> IsDisposed for SignalMap<K, V, S> {
+    fn is_disposed(&self) -> bool {
+        self.inner.is_disposed()
+    }
+}
+
+impl<K, V, S> AsSubscriberSet for SignalMap<K, V, S>
+where
+    S: Storage<ArcSignalMap<K, V>>,
+{
+    type Output = Arc<RwLock<SubscriberSet>>;
+
+    fn as_subscriber_set(&self) -> Option<Self::Output> {
+        self.inner
+            .try_with_value(|inner| inner.as_subscriber_set())
+            .flatten()
+    }
+}
+
+impl<K, V, S> ReadUntracked for SignalMap<K, V, S>
+where
+    K: 'static,
+    V: 'static,
+    S: Storage<ArcSignalMap<K, V>>,
+{
+    type Value = ReadGuard<IndexMap<K, V>, Plain<IndexMap<K, V>>>;
+
+    fn try_read_untracked(&self) -> Option<Self::Value> {
+        self.inner
+            .try_get_value()
+            .map(|inner| inner.read_untracked())
+    }
+}
+
+impl<K, V, S> Notify for SignalMap<K, V, S>
+where
+    S: Storage<ArcSignalMap<K, V>>,
+{
+    fn notify(&self) {
+        self.mark_dirty();
+    }
+}
+
+impl<K, V> From<ArcSignalMap<K, V>> for SignalMap<K, V>
+where
+    K: Send + Sync + 'static,
+    V: Send + Sync + 'static,
+{
+    #[track_caller]
+    fn from(value: ArcSignalMap<K, V>) -> Self {
+        SignalMap {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: Location::caller(),
+            inner: ArenaItem::new_with_storage(value),
+        }
+    }
+}
+
+impl<K, V> FromLocal<ArcSignalMap<K, V>> for SignalMap<K, V, LocalStorage>
+where
+    K: 'static,
+    V: 'static,
+{
+    #[track_caller]
+    fn from_local(value: ArcSignalMap<K, V>) -> Self {
+        SignalMap {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: Location::caller(),
+            inner: ArenaItem::new_with_storage(value),
+        }
+    }
+}
+
+impl<K, V, S> From<SignalMap<K, V, S>> for ArcSignalMap<K, V>
+where
+    K: 'static,
+    V: 'static,
+    S: Storage<ArcSignalMap<K, V>>,
+{
+    #[track_caller]
+    fn from(value: SignalMap<K, V, S>) -> Self {
+        value.arc()
+    }
+}
@@ -0,0 +1,353 @@
+use super::{
+    guards::{Plain, ReadGuard},
+    subscriber_traits::AsSubscriberSet,
+    ArcSignalVec, DiffStream, VecDiff,
+};
+use crate::{
+    graph::{ReactiveNode, SubscriberSet},
+    owner::{ArenaItem, FromLocal, LocalStorage, Storage, SyncStorage},
+    traits::{DefinedAt, Dispose, IsDisposed, Notify, ReadUntracked},
+    unwrap_signal,
+};
+use core::fmt::Debug;
+use std::{
+    hash::Hash,
+    panic::Location,
+    sync::{Arc, RwLock},
+};
+
+/// An arena-allocated signal that holds a list, and records each structural change made to it.
+///
+/// This works like an [`ArcSignalVec`]: it can be read like a signal containing a `Vec<T>`,
+/// but can only be changed through methods like [`push`](SignalVec::push),
+/// [`insert`](SignalVec::insert), [`remove`](SignalVec::remove), and
+/// [`move_item`](SignalVec::move_item), each of which records a [`VecDiff`] that can be received
+/// from a [`DiffStream`] created with [`diffs`](SignalVec::diffs).
+///
+/// This is an arena-allocated signal, which is `Copy` and is disposed when its reactive
+/// [`Owner`](crate::owner::Owner) cleans up. For a reference-counted signal that lives
+/// as long as a reference to it is alive, see [`ArcSignalVec`].
+///
+/// ## Examples
+///
+/// ```
+/// # use reactive_graph::prelude::*;
+/// # use reactive_graph::signal::*; let owner = reactive_graph::owner::Owner::new(); owner.set();
+/// let todos = SignalVec::new(vec!["write docs"]);
+/// let mut diffs = todos.diffs();
+///
+/// todos.insert(0, "write code");
+/// todos.remove(1);
+/// assert_eq!(todos.get(), vec!["write code"]);
+///
+/// // a consumer can keep its own copy in sync by applying the changes
+/// let mut copy = Vec::new();
+/// while let Some(diff) = diffs.try_next() {
+///     diff.apply(&mut copy);
+/// }
+/// assert_eq!(copy, vec!["write code"]);
+/// ```
+pub struct SignalVec<T, S = SyncStorage> {
+    #[cfg(any(debug_assertions, leptos_debuginfo))]
+    defined_at: &'static Location<'static>,
+    inner: ArenaItem<ArcSignalVec<T>, S>,
+}
+
+impl<T, S> Dispose for SignalVec<T, S> {
+    fn dispose(self) {
+        self.inner.dispose()
+    }
+}
+
+impl<T> SignalVec<T>
+where
+    T: Send + Sync + 'static,
+{
+    /// Creates a new list, taking the initial values as its argument.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "trace", skip_all)
+    )]
+    #[track_caller]
+    pub fn new(values: Vec<T>) -> Self {
+        Self::new_with_storage(values)
+    }
+}
+
+impl<T> SignalVec<T, LocalStorage>
+where
+    T: 'static,
+{
+    /// Creates a new list, taking the initial values as its argument. Unlike
+    /// [`SignalVec::new`], this pins the value to the current thread. Accessing it from any other
+    /// thread will panic.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "trace", skip_all)
+    )]
+    #[track_caller]
+    pub fn new_local(values: Vec<T>) -> Self {
+        Self::new_with_storage(values)
+    }
+}
+
+impl<T, S> SignalVec<T, S>
+where
+    T: 'static,
+    S: Storage<ArcSignalVec<T>>,
+{
+    /// Creates a new list with the given arena storage method.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "trace", skip_all)
+    )]
+    #[track_caller]
+    pub fn new_with_storage(values: Vec<T>) -> Self {
+        Self {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: Location::caller(),
+            inner: ArenaItem::new_with_storage(ArcSignalVec::new(values)),
+        }
+    }
+
+    #[track_caller]
+    fn arc(&self) -> ArcSignalVec<T> {
+        self.inner
+            .try_get_value()
+            .unwrap_or_else(unwrap_signal!(self))
+    }
+}
+
+impl<T, S> SignalVec<T, S>
+where
+    T: Clone + 'static,
+    S: Storage<ArcSignalVec<T>>,
+{
+    /// Creates a stream of the changes made to this list.
+    ///
+    /// The stream begins with a [`VecDiff::Replace`] containing the current values, followed by
+    /// each change made after this is called.
+    #[track_caller]
+    pub fn diffs(&self) -> DiffStream<VecDiff<T>> {
+        self.arc().diffs()
+    }
+
+    /// Applies a change to the list, records it, and notifies subscribers.
+    ///
+    /// # Panics
+    /// Panics if an index in the change is out of bounds.
+    #[track_caller]
+    pub fn apply(&self, diff: VecDiff<T>) {
+        self.arc().apply(diff)
+    }
+
+    /// Adds a value to the end of the list.
+    #[track_caller]
+    pub fn push(&self, value: T) {
+        self.arc().push(value)
+    }
+
+    /// Removes the last value from the list, and returns it, or `None` if it is empty.
+    #[track_caller]
+    pub fn pop(&self) -> Option<T> {
+        self.arc().pop()
+    }
+
+    /// Inserts a value at `index`, shifting all values after it to the right.
+    ///
+    /// # Panics
+    /// Panics if `index > len`.
+    #[track_caller]
+    pub fn insert(&self, index: usize, value: T) {
+        self.arc().insert(index, value)
+    }
+
+    /// Removes and returns the value at `index`, shifting all values after it to the left.
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds.
+    #[track_caller]
+    pub fn remove(&self, index: usize) -> T {
+        self.arc().remove(index)
+    }
+
+    /// Replaces the value at `index`.
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds.
+    #[track_caller]
+    pub fn set_at(&self, index: usize, value: T) {
+        self.arc().set_at(index, value)
+    }
+
+    /// Updates the value at `index` in place. This is recorded as a [`VecDiff::UpdateAt`] with
+    /// the new value.
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds.
+    #[track_caller]
+    pub fn update_at(&self, index: usize, fun: impl FnOnce(&mut T)) {
+        self.arc().update_at(index, fun)
+    }
+
+    /// Moves the value at `old_index` so that it is at `new_index`, shifting the values between
+    /// them.
+    ///
+    /// # Panics
+    /// Panics if either index is out of bounds.
+    #[track_caller]
+    pub fn move_item(&self, old_index: usize, new_index: usize) {
+        self.arc().move_item(old_index, new_index)
+    }
+
+    /// Removes all values from the list.
+    #[track_caller]
+    pub fn clear(&self) {
+        self.arc().clear()
+    }
+
+    /// Replaces all values in the list.
+    #[track_caller]
+    pub fn replace(&self, values: Vec<T>) {
+        self.arc().replace(values)
+    }
+}
+
+impl<T, S> Copy for SignalVec<T, S> {}
+
+impl<T, S> Clone for SignalVec<T, S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, S> Debug for SignalVec<T, S>
+where
+    S: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SignalVec")
+            .field("type", &std::any::type_name::<T>())
+            .field("store", &self.inner)
+            .finish()
+    }
+}
+
+impl<T, S> Default for SignalVec<T, S>
+where
+    T: 'static,
+    S: Storage<ArcSignalVec<T>>,
+{
+    #[track_caller]
+    fn default() -> Self {
+        Self::new_with_storage(Vec::new())
+    }
+}
+
+impl<T, S> PartialEq for SignalVec<T, S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+}
+
+impl<T, S> Eq for SignalVec<T, S> {}
+
+impl<T, S> Hash for SignalVec<T, S> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.inner.hash(state);
+    }
+}
+
+impl<T, S> DefinedAt for SignalVec<T, S> {
+    fn defined_at(&self) -> Option<&'static Location<'static>> {
+        #[cfg(any(debug_assertions, leptos_debuginfo))]
+        {
+            Some(self.defined_at)
+        }
+        #[cfg(not(any(debug_assertions, leptos_debuginfo)))]
+        {
+            None
+        }
+    }
+}
+
+impl<T: 'static, S> IsDisposed for SignalVec<T, S> {
+    fn is_disposed(&self) -> bool {
+        self.inner.is_disposed()
+    }
+}
+
+impl<T, S> AsSubscriberSet for SignalVec<T, S>
+where
+    S: Storage<ArcSignalVec<T>>,
+{
+    type Output = Arc<RwLock<SubscriberSet>>;
+
+    fn as_subscriber_set(&self) -> Option<Self::Output> {
+        self.inner
+            .try_with_value(|inner| inner.as_subscriber_set())
+            .flatten()
+    }
+}
+
+impl<T, S> ReadUntracked for SignalVec<T, S>
+where
+    T: 'static,
+    S: Storage<ArcSignalVec<T>>,
+{
+    type Value = ReadGuard<Vec<T>, Plain<Vec<T>>>;
+
+    fn try_read_untracked(&self) -> Option<Self::Value> {
+        self.inner
+            .try_get_value()
+            .map(|inner| inner.read_untracked())
+    }
+}
+
+impl<T, S> Notify for SignalVec<T, S>
+where
+    S: Storage<ArcSignalVec<T>>,
+{
+    fn notify(&self) {
+        self.mark_dirty();
+    }
+}
+
+impl<T> From<ArcSignalVec<T>> for SignalVec<T>
+where
+    T: Send + Sync + 'static,
+{
+    #[track_caller]
+    fn from(value: ArcSignalVec<T>) -> Self {
+        SignalVec {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: Location::caller(),
+            inner: ArenaItem::new_with_storage(value),
+        }
+    }
+}
+
+impl<T> FromLocal<ArcSignalVec<T>> for SignalVec<T, LocalStorage>
+where
+    T: 'static,
+{
+    #[track_caller]
+    fn from_local(value: ArcSignalVec<T>) -> Self {
+        SignalVec {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: Location::caller(),
+            inner: ArenaItem::new_with_storage(value),
+        }
+    }
+}
+
+impl<T, S> From<SignalVec<T, S>> for ArcSignalVec<T>
+where
+    T: 'static,
+    S: Storage<ArcSignalVec<T>>,
+{
+    #[track_caller]
+    fn from(value: SignalVec<T, S>) -> Self {
+        value.arc()
+    }
+}
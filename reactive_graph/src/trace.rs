@@ -0,0 +1,420 @@
+//! Structured spans for reactive updates, enabled with the `tracing` feature.
+//!
+//! While the feature is enabled, every memo recompute, effect run and async derived fetch
+//! (including resource fetches) runs inside a [`tracing`] span at the `TRACE` level. Each span is
+//! named after the kind of node (`memo`, `effect`, `render_effect`, `immediate_effect` or
+//! `async_derived`), and records:
+//! - `node`: the id of the node, which matches the node ids in an
+//!   [`inspector`](crate::inspector) snapshot
+//! - `label`: the label attached with [`inspector::label`](crate::inspector::label), if the
+//!   `inspector` feature is also enabled
+//! - `owner`: the [`Owner::debug_id`] of the owner the node runs in
+//! - `defined_at`: where the node was created, in debug builds
+//!
+//! These spans can be consumed by any `tracing` subscriber. The built-in
+//! [`ChromeTraceCollector`] records them in the Chrome Trace Event format, so that a profile of a
+//! slow interaction can be opened in a trace viewer like `chrome://tracing` or Perfetto.
+//!
+//! ```rust
+//! # let owner = reactive_graph::owner::Owner::new(); owner.set();
+//! use reactive_graph::{
+//!     computed::ArcMemo, prelude::*, signal::ArcRwSignal,
+//!     trace::ChromeTraceCollector,
+//! };
+//!
+//! let collector = ChromeTraceCollector::new();
+//! tracing::subscriber::with_default(collector.clone(), || {
+//!     let count = ArcRwSignal::new(1);
+//!     let double = ArcMemo::new({
+//!         let count = count.clone();
+//!         move |_| count.get() * 2
+//!     });
+//!     assert_eq!(double.get(), 2);
+//! });
+//!
+//! let json = collector.to_json();
+//! assert!(json.contains(r#""name":"memo""#));
+//! # let path = std::env::temp_dir().join("reactive_graph_trace_doctest.json");
+//! collector.write_to(&path).unwrap();
+//! # std::fs::remove_file(path).unwrap();
+//! ```
+
+use crate::owner::Owner;
+use std::panic::Location;
+use tracing::{field, Span};
+
+/// The kind of work a span covers.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum SpanKind {
+    Memo,
+    Effect,
+    RenderEffect,
+    ImmediateEffect,
+    AsyncDerived,
+}
+
+/// Creates the span for one run of the reactive node with the given id.
+pub(crate) fn span(
+    kind: SpanKind,
+    node: usize,
+    owner: Option<&Owner>,
+    defined_at: Option<&'static Location<'static>>,
+) -> Span {
+    let owner = owner.map(Owner::debug_id);
+    let defined_at = defined_at.map(field::display);
+
+    // span names must be known statically
+    macro_rules! span {
+        ($name:literal) => {
+            tracing::trace_span!(
+                $name,
+                node,
+                label = field::Empty,
+                owner,
+                defined_at
+            )
+        };
+    }
+    let span = match kind {
+        SpanKind::Memo => span!("memo"),
+        SpanKind::Effect => span!("effect"),
+        SpanKind::RenderEffect => span!("render_effect"),
+        SpanKind::ImmediateEffect => span!("immediate_effect"),
+        SpanKind::AsyncDerived => span!("async_derived"),
+    };
+
+    // only look up the label if something is listening
+    #[cfg(feature = "inspector")]
+    if !span.is_disabled() {
+        if let Some(label) = crate::inspector::label_of(node) {
+            span.record("label", label);
+        }
+    }
+
+    span
+}
+
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+pub use collector::ChromeTraceCollector;
+
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+mod collector {
+    use crate::json::write_json_str;
+    use or_poisoned::OrPoisoned;
+    use rustc_hash::FxHashMap;
+    use std::{
+        cell::Cell,
+        fmt::{self, Write},
+        io,
+        path::Path,
+        sync::{
+            atomic::{AtomicU64, AtomicUsize, Ordering},
+            Arc, Mutex,
+        },
+        time::Instant,
+    };
+    use tracing::{
+        field::{Field, Visit},
+        span::{Attributes, Id, Record},
+        Event, Level, Metadata, Subscriber,
+    };
+
+    /// A [`tracing`] subscriber that records spans and events in the
+    /// [Chrome Trace Event format](https://docs.google.com/document/d/1CvAClvFfyA5R-PhYUmn5OOQtYMH4h6I0nSsKchNAySU),
+    /// which can be opened in `chrome://tracing`, [Perfetto](https://ui.perfetto.dev), or
+    /// another trace viewer.
+    ///
+    /// Each time a span is entered and exited, a duration event is recorded on the current
+    /// thread, with the span's fields as its arguments. Events are recorded as instant events.
+    ///
+    /// Clones of a collector share the same trace, so one clone can be installed as the
+    /// subscriber while another is used to export the trace.
+    ///
+    /// See the [module-level documentation](super) for an example.
+    #[derive(Clone)]
+    pub struct ChromeTraceCollector {
+        inner: Arc<CollectorInner>,
+    }
+
+    struct CollectorInner {
+        start: Instant,
+        max_level: Level,
+        next_id: AtomicU64,
+        spans: Mutex<FxHashMap<u64, SpanData>>,
+        // each event, already encoded as a JSON object
+        events: Mutex<Vec<String>>,
+    }
+
+    struct SpanData {
+        name: &'static str,
+        target: &'static str,
+        // the span's fields, encoded as the members of a JSON object
+        args: String,
+        refs: usize,
+    }
+
+    impl fmt::Debug for ChromeTraceCollector {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("ChromeTraceCollector")
+                .field("max_level", &self.inner.max_level)
+                .field("events", &self.inner.events.lock().or_poisoned().len())
+                .finish()
+        }
+    }
+
+    impl Default for ChromeTraceCollector {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl ChromeTraceCollector {
+        /// Creates a collector that records every span and event, starting the clock for the
+        /// trace's timestamps.
+        pub fn new() -> Self {
+            Self::with_max_level(Level::TRACE)
+        }
+
+        /// Creates a collector that only records spans and events at or above the given level.
+        ///
+        /// The spans for reactive updates are at the `TRACE` level.
+        pub fn with_max_level(max_level: Level) -> Self {
+            Self {
+                inner: Arc::new(CollectorInner {
+                    start: Instant::now(),
+                    max_level,
+                    next_id: AtomicU64::new(1),
+                    spans: Default::default(),
+                    events: Default::default(),
+                }),
+            }
+        }
+
+        /// Encodes the events recorded so far as a Chrome trace.
+        pub fn to_json(&self) -> String {
+            let events = self.inner.events.lock().or_poisoned();
+            let mut out = String::from("{\"traceEvents\":[");
+            for (i, event) in events.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(event);
+            }
+            out.push_str("]}");
+            out
+        }
+
+        /// Writes the events recorded so far to the file at `path` as a Chrome trace.
+        pub fn write_to(&self, path: impl AsRef<Path>) -> io::Result<()> {
+            std::fs::write(path, self.to_json())
+        }
+
+        /// Discards the events recorded so far.
+        pub fn clear(&self) {
+            self.inner.events.lock().or_poisoned().clear();
+        }
+
+        fn push_event(
+            &self,
+            ph: char,
+            name: &str,
+            target: &str,
+            args: Option<&str>,
+        ) {
+            let ts = self.inner.start.elapsed().as_nanos() as f64 / 1000.0;
+            let mut event = String::from("{\"name\":");
+            write_json_str(&mut event, name);
+            event.push_str(",\"cat\":");
+            write_json_str(&mut event, target);
+            _ = write!(
+                event,
+                ",\"ph\":\"{ph}\",\"ts\":{ts:.3},\"pid\":1,\"tid\":{}",
+                thread_id()
+            );
+            if ph == 'i' {
+                // instant events are scoped to their thread
+                event.push_str(",\"s\":\"t\"");
+            }
+            if let Some(args) = args {
+                _ = write!(event, ",\"args\":{{{args}}}");
+            }
+            event.push('}');
+            self.inner.events.lock().or_poisoned().push(event);
+        }
+    }
+
+    /// A small, stable id for the current thread, as trace viewers expect.
+    fn thread_id() -> usize {
+        static NEXT: AtomicUsize = AtomicUsize::new(1);
+        thread_local! {
+            static ID: Cell<usize> = const { Cell::new(0) };
+        }
+        ID.with(|id| {
+            if id.get() == 0 {
+                id.set(NEXT.fetch_add(1, Ordering::Relaxed));
+            }
+            id.get()
+        })
+    }
+
+    impl Subscriber for ChromeTraceCollector {
+        fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+            *metadata.level() <= self.inner.max_level
+        }
+
+        fn max_level_hint(
+            &self,
+        ) -> Option<tracing::level_filters::LevelFilter> {
+            Some(self.inner.max_level.into())
+        }
+
+        fn new_span(&self, attrs: &Attributes<'_>) -> Id {
+            let id = self.inner.next_id.fetch_add(1, Ordering::Relaxed);
+            let mut args = JsonArgs::default();
+            attrs.record(&mut args);
+            self.inner.spans.lock().or_poisoned().insert(
+                id,
+                SpanData {
+                    name: attrs.metadata().name(),
+                    target: attrs.metadata().target(),
+                    args: args.0,
+                    refs: 1,
+                },
+            );
+            Id::from_u64(id)
+        }
+
+        fn record(&self, span: &Id, values: &Record<'_>) {
+            if let Some(data) = self
+                .inner
+                .spans
+                .lock()
+                .or_poisoned()
+                .get_mut(&span.into_u64())
+            {
+                let mut args = JsonArgs(std::mem::take(&mut data.args));
+                values.record(&mut args);
+                data.args = args.0;
+            }
+        }
+
+        fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            let mut args = JsonArgs::default();
+            event.record(&mut args);
+            let metadata = event.metadata();
+            self.push_event(
+                'i',
+                metadata.name(),
+                metadata.target(),
+                Some(&args.0),
+            );
+        }
+
+        fn enter(&self, span: &Id) {
+            let span = self
+                .inner
+                .spans
+                .lock()
+                .or_poisoned()
+                .get(&span.into_u64())
+                .map(|data| (data.name, data.target, data.args.clone()));
+            if let Some((name, target, args)) = span {
+                self.push_event('B', name, target, Some(&args));
+            }
+        }
+
+        fn exit(&self, span: &Id) {
+            let span = self
+                .inner
+                .spans
+                .lock()
+                .or_poisoned()
+                .get(&span.into_u64())
+                .map(|data| (data.name, data.target));
+            if let Some((name, target)) = span {
+                self.push_event('E', name, target, None);
+            }
+        }
+
+        fn clone_span(&self, span: &Id) -> Id {
+            if let Some(data) = self
+                .inner
+                .spans
+                .lock()
+                .or_poisoned()
+                .get_mut(&span.into_u64())
+            {
+                data.refs += 1;
+            }
+            span.clone()
+        }
+
+        fn try_close(&self, span: Id) -> bool {
+            let mut spans = self.inner.spans.lock().or_poisoned();
+            let id = span.into_u64();
+            match spans.get_mut(&id) {
+                Some(data) if data.refs > 1 => {
+                    data.refs -= 1;
+                    false
+                }
+                Some(_) => {
+                    spans.remove(&id);
+                    true
+                }
+                None => false,
+            }
+        }
+    }
+
+    /// Encodes the fields of a span or event as the members of a JSON object.
+    #[derive(Default)]
+    struct JsonArgs(String);
+
+    impl JsonArgs {
+        fn key(&mut self, field: &Field) {
+            if !self.0.is_empty() {
+                self.0.push(',');
+            }
+            write_json_str(&mut self.0, field.name());
+            self.0.push(':');
+        }
+    }
+
+    impl Visit for JsonArgs {
+        fn record_f64(&mut self, field: &Field, value: f64) {
+            self.key(field);
+            if value.is_finite() {
+                _ = write!(self.0, "{value}");
+            } else {
+                self.0.push_str("null");
+            }
+        }
+
+        fn record_i64(&mut self, field: &Field, value: i64) {
+            self.key(field);
+            _ = write!(self.0, "{value}");
+        }
+
+        fn record_u64(&mut self, field: &Field, value: u64) {
+            self.key(field);
+            _ = write!(self.0, "{value}");
+        }
+
+        fn record_bool(&mut self, field: &Field, value: bool) {
+            self.key(field);
+            _ = write!(self.0, "{value}");
+        }
+
+        fn record_str(&mut self, field: &Field, value: &str) {
+            self.key(field);
+            write_json_str(&mut self.0, value);
+        }
+
+        fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+            self.key(field);
+            write_json_str(&mut self.0, &format!("{value:?}"));
+        }
+    }
+}
@@ -50,6 +50,7 @@
 
 pub use crate::trait_options::*;
 use crate::{
+    channel::channel,
     effect::Effect,
     graph::{Observer, Source, Subscriber, ToAnySource},
     owner::Owner,
@@ -57,9 +58,13 @@ use crate::{
 };
 use any_spawner::Executor;
 use futures::{Stream, StreamExt};
+use or_poisoned::OrPoisoned;
 use std::{
+    future::Future,
     ops::{Deref, DerefMut},
     panic::Location,
+    sync::{Arc, Mutex},
+    task::Poll,
 };
 
 #[doc(hidden)]
@@ -115,7 +120,10 @@ impl<T: Source + ToAnySource + DefinedAt> Track for T {
         }
 
         if let Some(subscriber) = Observer::get() {
-            subscriber.add_source(self.to_any_source());
+            let source = self.to_any_source();
+            #[cfg(feature = "inspector")]
+            crate::inspector::record_dependency(source.0, subscriber.0);
+            subscriber.add_source(source);
             self.add_subscriber(subscriber);
         } else {
             #[cfg(all(debug_assertions, feature = "effects"))]
@@ -568,6 +576,55 @@ pub trait ToStream<T> {
     /// Panics if you try to access a signal that is owned by a reactive node that has been disposed.
     #[track_caller]
     fn to_stream(&self) -> impl Stream<Item = T> + Send;
+
+    /// Generates a [`Stream`] that emits the latest value of the signal whenever it changes.
+    ///
+    /// Unlike [`to_stream`](ToStream::to_stream), this does not buffer every value: if the signal
+    /// changes several times before the consumer is ready for the next item, the stream skips to
+    /// the newest value. The stream ends when the current owner is cleaned up.
+    #[track_caller]
+    fn to_stream_latest(&self) -> impl Stream<Item = T> + Send
+    where
+        Self: Clone + Get<Value = T> + Send + Sync + 'static,
+        T: Send + 'static,
+    {
+        let latest = Arc::new(Mutex::new(None));
+        let (tx, mut rx) = channel();
+        let tx = Arc::new(Mutex::new(Some(tx)));
+
+        // dropping the sender ends the stream, once the latest value has been taken
+        Owner::on_cleanup({
+            let tx = Arc::clone(&tx);
+            move || drop(tx.lock().or_poisoned().take())
+        });
+
+        Effect::new_isomorphic({
+            let this = self.clone();
+            let latest = Arc::clone(&latest);
+            move |_| {
+                if let Some(value) = this.try_get() {
+                    *latest.lock().or_poisoned() = Some(value);
+                    if let Some(tx) = tx.lock().or_poisoned().as_mut() {
+                        tx.notify();
+                    }
+                }
+            }
+        });
+
+        futures::stream::poll_fn(move |cx| loop {
+            match rx.poll_next_unpin(cx) {
+                Poll::Ready(Some(())) => {
+                    if let Some(value) = latest.lock().or_poisoned().take() {
+                        return Poll::Ready(Some(value));
+                    }
+                }
+                Poll::Ready(None) => {
+                    return Poll::Ready(latest.lock().or_poisoned().take())
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        })
+    }
 }
 
 impl<S> ToStream<S::Value> for S
@@ -594,6 +651,9 @@ where
 }
 
 /// Allows creating a signal from an async [`Stream`].
+///
+/// The stream stops being polled when it ends, or when the owner that was current when the
+/// signal was created is cleaned up.
 pub trait FromStream<T> {
     /// Creates a signal that contains the latest value of the stream.
     #[track_caller]
@@ -611,27 +671,35 @@ where
 {
     fn from_stream(stream: impl Stream<Item = T> + Send + 'static) -> Self {
         let (read, write) = arc_signal(None);
-        let mut stream = Box::pin(stream);
-        crate::spawn(async move {
-            while let Some(value) = stream.next().await {
-                write.set(Some(value));
-            }
-        });
+        crate::spawn(drive_stream(stream, move |value| write.set(Some(value))));
         read.into()
     }
 
     fn from_stream_unsync(stream: impl Stream<Item = T> + 'static) -> Self {
         let (read, write) = arc_signal(None);
-        let mut stream = Box::pin(stream);
-        Executor::spawn_local(async move {
-            while let Some(value) = stream.next().await {
-                write.set(Some(value));
-            }
-        });
+        Executor::spawn_local(drive_stream(stream, move |value| {
+            write.set(Some(value))
+        }));
         read.into()
     }
 }
 
+/// Calls `set` with each value of the stream, until the stream ends or the current owner is
+/// cleaned up.
+pub(crate) fn drive_stream<T>(
+    stream: impl Stream<Item = T>,
+    mut set: impl FnMut(T),
+) -> impl Future<Output = ()> {
+    let (stream, handle) = futures::stream::abortable(stream);
+    Owner::on_cleanup(move || handle.abort());
+    async move {
+        let mut stream = std::pin::pin!(stream);
+        while let Some(value) = stream.next().await {
+            set(value);
+        }
+    }
+}
+
 /// Checks whether a signal has already been disposed.
 pub trait IsDisposed {
     /// If `true`, the signal cannot be accessed without a panic.
@@ -663,6 +731,8 @@ pub fn panic_getting_disposed_signal(
     defined_at: Option<&'static Location<'static>>,
     location: &'static Location<'static>,
 ) -> String {
+    #[cfg(feature = "leak-diagnostics")]
+    crate::diagnostics::warn_disposed_access(location);
     if let Some(defined_at) = defined_at {
         format!(
             "At {location}, you tried to access a reactive value which was \
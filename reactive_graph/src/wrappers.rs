@@ -4,10 +4,11 @@
 pub mod read {
     use crate::{
         computed::{ArcMemo, Memo},
+        effect::ImmediateEffect,
         graph::untrack,
         owner::{
-            ArcStoredValue, ArenaItem, FromLocal, LocalStorage, Storage,
-            SyncStorage,
+            on_cleanup, ArcStoredValue, ArenaItem, FromLocal, LocalStorage,
+            Storage, SyncStorage,
         },
         signal::{
             guards::{Mapped, Plain, ReadGuard},
@@ -15,17 +16,25 @@ pub mod read {
             ReadSignal, RwSignal,
         },
         traits::{
-            DefinedAt, Dispose, Get, Read, ReadUntracked, ReadValue, Track,
+            DefinedAt, Dispose, Get, GetUntracked, Read, ReadUntracked,
+            ReadValue, Set, Track,
         },
         unwrap_signal,
     };
+    use any_spawner::Executor;
+    use futures::future::{AbortHandle, Abortable};
+    use or_poisoned::OrPoisoned;
     use send_wrapper::SendWrapper;
     use std::{
         borrow::Borrow,
         fmt::Display,
         ops::Deref,
         panic::Location,
-        sync::{Arc, RwLock},
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc, Mutex, RwLock,
+        },
+        time::Duration,
     };
 
     /// Possibilities for the inner type of a [`Signal`].
@@ -662,6 +671,196 @@ pub mod read {
         }
     }
 
+    impl<T> Signal<T>
+    where
+        T: Clone + Send + Sync + 'static,
+    {
+        /// Creates a read-only signal that follows this one, but only updates once this signal
+        /// has stopped changing for the given `duration`. Each change restarts the wait, and only
+        /// the most recent value is kept.
+        ///
+        /// This is useful for things like search boxes, where a request should only be made once
+        /// the user has stopped typing.
+        ///
+        /// Timers use [`Executor::sleep`](any_spawner::Executor::sleep), so this works with any
+        /// executor, on the server and in the browser. Pending timers are cancelled, and this
+        /// signal stops following changes, when the current [`Owner`](crate::owner::Owner) is
+        /// cleaned up.
+        ///
+        /// ```rust
+        /// # use reactive_graph::prelude::*;
+        /// # use reactive_graph::signal::*; let owner = reactive_graph::owner::Owner::new(); owner.set();
+        /// # use reactive_graph::wrappers::read::Signal;
+        /// # use any_spawner::{Executor, TestExecutor};
+        /// # use std::time::Duration;
+        /// # Executor::init_test_executor().unwrap();
+        /// let (query, set_query) = signal(String::new());
+        /// let debounced = Signal::<String>::from(query).debounced(Duration::from_millis(300));
+        ///
+        /// set_query.set("l".to_string());
+        /// # TestExecutor::advance(Duration::from_millis(100));
+        /// set_query.set("le".to_string());
+        /// # TestExecutor::advance(Duration::from_millis(100));
+        /// set_query.set("leptos".to_string());
+        /// // (100ms later...)
+        /// # TestExecutor::advance(Duration::from_millis(100));
+        /// assert_eq!(debounced.get(), "");
+        ///
+        /// // (300ms after the last change...)
+        /// # TestExecutor::advance(Duration::from_millis(200));
+        /// assert_eq!(debounced.get(), "leptos");
+        /// ```
+        #[track_caller]
+        pub fn debounced(self, duration: Duration) -> Signal<T> {
+            let value = ArcRwSignal::new(self.get_untracked());
+            let pending = Arc::new(Mutex::new(None::<AbortHandle>));
+
+            let first_run = AtomicBool::new(true);
+            let effect = ImmediateEffect::new_isomorphic({
+                let value = value.clone();
+                let pending = Arc::clone(&pending);
+                move || {
+                    let next = self.get();
+                    if first_run.swap(false, Ordering::Relaxed) {
+                        return;
+                    }
+
+                    // each change cancels the timer started by the previous one
+                    let (handle, registration) = AbortHandle::new_pair();
+                    if let Some(prev) =
+                        pending.lock().or_poisoned().replace(handle)
+                    {
+                        prev.abort();
+                    }
+                    let value = value.clone();
+                    crate::spawn(async move {
+                        let sleep = Executor::sleep(duration);
+                        if Abortable::new(sleep, registration).await.is_ok() {
+                            value.set(next);
+                        }
+                    });
+                }
+            });
+
+            on_cleanup(move || {
+                effect.dispose();
+                if let Some(pending) = pending.lock().or_poisoned().take() {
+                    pending.abort();
+                }
+            });
+
+            Signal::from(value.read_only())
+        }
+
+        /// Creates a read-only signal that follows this one, but updates at most once in each
+        /// period of the given `duration`.
+        ///
+        /// The first change is applied immediately. Changes made during the following `duration`
+        /// are held back, and the most recent of them is applied when it ends, which starts a new
+        /// period. This is useful for things like resize or scroll handlers, which can fire much
+        /// more often than the page needs to respond to them.
+        ///
+        /// Timers use [`Executor::sleep`](any_spawner::Executor::sleep), so this works with any
+        /// executor, on the server and in the browser. Pending timers are cancelled, and this
+        /// signal stops following changes, when the current [`Owner`](crate::owner::Owner) is
+        /// cleaned up.
+        ///
+        /// ```rust
+        /// # use reactive_graph::prelude::*;
+        /// # use reactive_graph::signal::*; let owner = reactive_graph::owner::Owner::new(); owner.set();
+        /// # use reactive_graph::wrappers::read::Signal;
+        /// # use any_spawner::{Executor, TestExecutor};
+        /// # use std::time::Duration;
+        /// # Executor::init_test_executor().unwrap();
+        /// let (width, set_width) = signal(800);
+        /// let throttled = Signal::<i32>::from(width).throttled(Duration::from_millis(100));
+        ///
+        /// set_width.set(790);
+        /// assert_eq!(throttled.get(), 790);
+        ///
+        /// set_width.set(780);
+        /// set_width.set(770);
+        /// assert_eq!(throttled.get(), 790);
+        ///
+        /// // (100ms later...)
+        /// # TestExecutor::advance(Duration::from_millis(100));
+        /// assert_eq!(throttled.get(), 770);
+        /// ```
+        #[track_caller]
+        pub fn throttled(self, duration: Duration) -> Signal<T> {
+            struct Throttle<T> {
+                // whether a period is currently running
+                waiting: bool,
+                // the most recent value held back during the current period
+                trailing: Option<T>,
+                timer: Option<AbortHandle>,
+            }
+
+            let value = ArcRwSignal::new(self.get_untracked());
+            let state = Arc::new(Mutex::new(Throttle {
+                waiting: false,
+                trailing: None,
+                timer: None,
+            }));
+
+            let first_run = AtomicBool::new(true);
+            let effect = ImmediateEffect::new_isomorphic({
+                let value = value.clone();
+                let state = Arc::clone(&state);
+                move || {
+                    let next = self.get();
+                    if first_run.swap(false, Ordering::Relaxed) {
+                        return;
+                    }
+
+                    let mut lock = state.lock().or_poisoned();
+                    if lock.waiting {
+                        lock.trailing = Some(next);
+                        return;
+                    }
+                    lock.waiting = true;
+                    let (handle, registration) = AbortHandle::new_pair();
+                    lock.timer = Some(handle);
+                    drop(lock);
+
+                    value.set(next);
+
+                    let value = value.clone();
+                    let state = Arc::clone(&state);
+                    let periods = async move {
+                        loop {
+                            Executor::sleep(duration).await;
+                            let mut lock = state.lock().or_poisoned();
+                            match lock.trailing.take() {
+                                Some(next) => {
+                                    drop(lock);
+                                    value.set(next);
+                                }
+                                None => {
+                                    lock.waiting = false;
+                                    lock.timer = None;
+                                    break;
+                                }
+                            }
+                        }
+                    };
+                    crate::spawn(async move {
+                        _ = Abortable::new(periods, registration).await;
+                    });
+                }
+            });
+
+            on_cleanup(move || {
+                effect.dispose();
+                if let Some(timer) = state.lock().or_poisoned().timer.take() {
+                    timer.abort();
+                }
+            });
+
+            Signal::from(value.read_only())
+        }
+    }
+
     impl<T> Signal<T, LocalStorage>
     where
         T: 'static,
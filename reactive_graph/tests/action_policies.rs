@@ -0,0 +1,309 @@
+use any_spawner::{Executor, TestExecutor};
+use futures::channel::oneshot;
+use reactive_graph::{
+    actions::{ArcAction, ArcMultiAction, ConcurrencyPolicy, RetryPolicy},
+    owner::Owner,
+    traits::GetUntracked,
+};
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+type Responses = Arc<Mutex<Vec<(i32, oneshot::Sender<Result<i32, ()>>)>>>;
+
+// an action function whose futures resolve when a response is sent
+fn respond_later(
+    responses: &Responses,
+) -> impl Fn(&i32) -> oneshot::Receiver<Result<i32, ()>> + Send + Sync + 'static
+{
+    let responses = Arc::clone(responses);
+    move |n| {
+        let (tx, rx) = oneshot::channel();
+        responses.lock().unwrap().push((*n, tx));
+        rx
+    }
+}
+
+fn action_with(
+    policy: ConcurrencyPolicy,
+) -> (ArcAction<i32, Result<i32, ()>>, Responses) {
+    let responses = Responses::default();
+    let respond = respond_later(&responses);
+    let action = ArcAction::new(move |n: &i32| {
+        let rx = respond(n);
+        async move { rx.await.unwrap_or(Err(())) }
+    })
+    .with_concurrency(policy);
+    (action, responses)
+}
+
+fn multi_action_with(
+    policy: ConcurrencyPolicy,
+) -> (ArcMultiAction<i32, Result<i32, ()>>, Responses) {
+    let responses = Responses::default();
+    let respond = respond_later(&responses);
+    let action = ArcMultiAction::new(move |n: &i32| {
+        let rx = respond(n);
+        async move { rx.await.unwrap_or(Err(())) }
+    })
+    .with_concurrency(policy);
+    (action, responses)
+}
+
+// responds to the oldest call that is still waiting, and returns its input
+fn respond(responses: &Responses, result: Result<i32, ()>) -> i32 {
+    let (n, tx) = responses.lock().unwrap().remove(0);
+    _ = tx.send(result);
+    TestExecutor::run_until_stalled();
+    n
+}
+
+#[test]
+fn dropping_the_abort_handle_does_not_abort_the_dispatch() {
+    _ = Executor::init_test_executor();
+    let owner = Owner::new();
+    owner.set();
+
+    let (action, responses) = action_with(ConcurrencyPolicy::Concurrent);
+    let handle = action.dispatch(1);
+    TestExecutor::run_until_stalled();
+    drop(handle);
+    TestExecutor::run_until_stalled();
+    assert!(action.pending().get_untracked());
+
+    respond(&responses, Ok(1));
+    assert_eq!(action.value().get_untracked(), Some(Ok(1)));
+
+    // aborting explicitly still drops the result
+    let handle = action.dispatch(2);
+    TestExecutor::run_until_stalled();
+    handle.abort();
+    TestExecutor::run_until_stalled();
+    assert!(!action.pending().get_untracked());
+    assert_eq!(action.value().get_untracked(), Some(Ok(1)));
+}
+
+#[test]
+fn queue_runs_dispatches_one_at_a_time() {
+    _ = Executor::init_test_executor();
+    let owner = Owner::new();
+    owner.set();
+
+    let (action, responses) = action_with(ConcurrencyPolicy::Queue);
+    action.dispatch(1);
+    action.dispatch(2);
+    action.dispatch(3);
+    TestExecutor::run_until_stalled();
+    assert!(action.pending().get_untracked());
+    assert_eq!(responses.lock().unwrap().len(), 1);
+
+    assert_eq!(respond(&responses, Ok(1)), 1);
+    assert_eq!(action.version().get_untracked(), 1);
+    assert_eq!(action.value().get_untracked(), Some(Ok(1)));
+    assert!(action.pending().get_untracked());
+
+    assert_eq!(respond(&responses, Ok(2)), 2);
+    assert_eq!(respond(&responses, Ok(3)), 3);
+    assert_eq!(action.version().get_untracked(), 3);
+    assert_eq!(action.value().get_untracked(), Some(Ok(3)));
+    assert!(!action.pending().get_untracked());
+}
+
+#[test]
+fn latest_wins_cancels_older_dispatches() {
+    _ = Executor::init_test_executor();
+    let owner = Owner::new();
+    owner.set();
+
+    let (action, responses) = action_with(ConcurrencyPolicy::LatestWins);
+    action.dispatch(1);
+    action.dispatch(2);
+    TestExecutor::run_until_stalled();
+    assert!(action.pending().get_untracked());
+
+    // the first dispatch was canceled, so its response is ignored
+    respond(&responses, Ok(1));
+    assert_eq!(action.version().get_untracked(), 0);
+    assert!(action.pending().get_untracked());
+
+    respond(&responses, Ok(2));
+    assert_eq!(action.version().get_untracked(), 1);
+    assert_eq!(action.value().get_untracked(), Some(Ok(2)));
+    assert!(!action.pending().get_untracked());
+}
+
+#[test]
+fn drop_while_pending_ignores_dispatches_until_done() {
+    _ = Executor::init_test_executor();
+    let owner = Owner::new();
+    owner.set();
+
+    let (action, responses) = action_with(ConcurrencyPolicy::DropWhilePending);
+    action.dispatch(1);
+    action.dispatch(2);
+    TestExecutor::run_until_stalled();
+    assert_eq!(responses.lock().unwrap().len(), 1);
+
+    respond(&responses, Ok(1));
+    assert_eq!(action.version().get_untracked(), 1);
+    assert!(!action.pending().get_untracked());
+    assert!(responses.lock().unwrap().is_empty());
+
+    action.dispatch(3);
+    TestExecutor::run_until_stalled();
+    assert_eq!(respond(&responses, Ok(3)), 3);
+    assert_eq!(action.version().get_untracked(), 2);
+}
+
+#[test]
+fn queue_latest_runs_only_the_most_recent_waiting_dispatch() {
+    _ = Executor::init_test_executor();
+    let owner = Owner::new();
+    owner.set();
+
+    let (action, responses) = action_with(ConcurrencyPolicy::QueueLatest);
+    action.dispatch(1);
+    action.dispatch(2);
+    action.dispatch(3);
+    TestExecutor::run_until_stalled();
+    assert_eq!(responses.lock().unwrap().len(), 1);
+
+    assert_eq!(respond(&responses, Ok(1)), 1);
+    assert!(action.pending().get_untracked());
+    assert_eq!(respond(&responses, Ok(3)), 3);
+    assert!(responses.lock().unwrap().is_empty());
+    assert_eq!(action.version().get_untracked(), 2);
+    assert_eq!(action.value().get_untracked(), Some(Ok(3)));
+    assert!(!action.pending().get_untracked());
+}
+
+#[test]
+fn retry_calls_the_action_again_until_it_succeeds() {
+    _ = Executor::init_test_executor();
+    let owner = Owner::new();
+    owner.set();
+
+    let (action, responses) = action_with(ConcurrencyPolicy::Concurrent);
+    let action = action.with_retry(RetryPolicy::exponential(2, Duration::ZERO));
+    action.dispatch(7);
+    TestExecutor::run_until_stalled();
+
+    assert_eq!(respond(&responses, Err(())), 7);
+    assert!(action.pending().get_untracked());
+    assert_eq!(action.version().get_untracked(), 0);
+
+    assert_eq!(respond(&responses, Ok(7)), 7);
+    assert_eq!(action.version().get_untracked(), 1);
+    assert_eq!(action.value().get_untracked(), Some(Ok(7)));
+    assert!(!action.pending().get_untracked());
+}
+
+#[test]
+fn retry_gives_up_after_max_retries() {
+    _ = Executor::init_test_executor();
+    let owner = Owner::new();
+    owner.set();
+
+    let (action, responses) = action_with(ConcurrencyPolicy::Concurrent);
+    let action = action.with_retry(RetryPolicy::exponential(1, Duration::ZERO));
+    action.dispatch(7);
+    TestExecutor::run_until_stalled();
+
+    respond(&responses, Err(()));
+    respond(&responses, Err(()));
+    assert!(responses.lock().unwrap().is_empty());
+    assert_eq!(action.version().get_untracked(), 1);
+    assert_eq!(action.value().get_untracked(), Some(Err(())));
+    assert!(!action.pending().get_untracked());
+}
+
+#[test]
+fn multi_action_queue_runs_submissions_one_at_a_time() {
+    _ = Executor::init_test_executor();
+    let owner = Owner::new();
+    owner.set();
+
+    let (action, responses) = multi_action_with(ConcurrencyPolicy::Queue);
+    action.dispatch(1);
+    action.dispatch(2);
+    TestExecutor::run_until_stalled();
+    assert_eq!(responses.lock().unwrap().len(), 1);
+
+    assert_eq!(respond(&responses, Ok(1)), 1);
+    assert_eq!(respond(&responses, Ok(2)), 2);
+    let values = action
+        .submissions()
+        .get_untracked()
+        .iter()
+        .map(|sub| sub.value().get_untracked())
+        .collect::<Vec<_>>();
+    assert_eq!(values, [Some(Ok(1)), Some(Ok(2))]);
+}
+
+#[test]
+fn multi_action_latest_wins_cancels_older_submissions() {
+    _ = Executor::init_test_executor();
+    let owner = Owner::new();
+    owner.set();
+
+    let (action, responses) = multi_action_with(ConcurrencyPolicy::LatestWins);
+    action.dispatch(1);
+    action.dispatch(2);
+    TestExecutor::run_until_stalled();
+
+    let submissions = action.submissions().get_untracked();
+    assert!(submissions[0].canceled().get_untracked());
+    assert!(!submissions[0].pending().get_untracked());
+
+    respond(&responses, Ok(1));
+    respond(&responses, Ok(2));
+    assert_eq!(submissions[0].value().get_untracked(), None);
+    assert_eq!(submissions[1].value().get_untracked(), Some(Ok(2)));
+}
+
+#[test]
+fn multi_action_drop_while_pending_ignores_submissions_until_done() {
+    _ = Executor::init_test_executor();
+    let owner = Owner::new();
+    owner.set();
+
+    let (action, responses) =
+        multi_action_with(ConcurrencyPolicy::DropWhilePending);
+    action.dispatch(1);
+    action.dispatch(2);
+    TestExecutor::run_until_stalled();
+    assert_eq!(action.submissions().get_untracked().len(), 1);
+    assert_eq!(respond(&responses, Ok(1)), 1);
+    assert!(responses.lock().unwrap().is_empty());
+}
+
+#[test]
+fn multi_action_retry_calls_the_action_again_until_it_succeeds() {
+    _ = Executor::init_test_executor();
+    let owner = Owner::new();
+    owner.set();
+
+    let (action, responses) = multi_action_with(ConcurrencyPolicy::Concurrent);
+    let action = action.with_retry(RetryPolicy::exponential(2, Duration::ZERO));
+    action.dispatch(7);
+    TestExecutor::run_until_stalled();
+
+    assert_eq!(respond(&responses, Err(())), 7);
+    let submission = action.submissions().get_untracked()[0].clone();
+    assert!(submission.pending().get_untracked());
+
+    assert_eq!(respond(&responses, Ok(7)), 7);
+    assert_eq!(submission.value().get_untracked(), Some(Ok(7)));
+    assert!(!submission.pending().get_untracked());
+}
+
+#[test]
+fn retry_delay_grows_exponentially_up_to_the_maximum() {
+    let policy = RetryPolicy::exponential(5, Duration::from_millis(100))
+        .with_max_delay(Duration::from_millis(500));
+    assert_eq!(policy.delay(0), Duration::from_millis(100));
+    assert_eq!(policy.delay(1), Duration::from_millis(200));
+    assert_eq!(policy.delay(2), Duration::from_millis(400));
+    assert_eq!(policy.delay(3), Duration::from_millis(500));
+}
@@ -1,11 +1,12 @@
 use any_spawner::Executor;
+use futures::channel::mpsc;
 use reactive_graph::{
     computed::{ArcAsyncDerived, AsyncDerived},
     owner::Owner,
     signal::RwSignal,
     traits::{Get, Read, Set, With, WithUntracked},
 };
-use std::future::pending;
+use std::{future::pending, sync::Mutex};
 
 #[tokio::test]
 async fn arc_async_derived_calculates_eagerly() {
@@ -137,3 +138,78 @@ async fn async_derived_with_initial() {
     signal2.set(1);
     assert_eq!(derived.await, 2);
 }
+
+#[tokio::test]
+async fn async_derived_stream_holds_latest_item() {
+    _ = Executor::init_tokio();
+    let owner = Owner::new();
+    owner.set();
+
+    let (tx, rx) = mpsc::unbounded();
+    let rx = Mutex::new(Some(rx));
+    let value = ArcAsyncDerived::new_stream(move || {
+        rx.lock()
+            .unwrap()
+            .take()
+            .expect("the stream is created once")
+    });
+    assert_eq!(value.get(), None);
+
+    tx.unbounded_send(1).unwrap();
+    assert_eq!(value.clone().await, Some(1));
+
+    tx.unbounded_send(2).unwrap();
+    tx.unbounded_send(3).unwrap();
+    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    assert_eq!(value.get(), Some(Some(3)));
+    // later items do not put the value back into a loading state
+    assert_eq!(value.clone().await, Some(3));
+}
+
+#[tokio::test]
+async fn async_derived_stream_restarts_when_dependencies_change() {
+    _ = Executor::init_tokio();
+    let owner = Owner::new();
+    owner.set();
+
+    let (tx_a, rx_a) = mpsc::unbounded();
+    let (tx_b, rx_b) = mpsc::unbounded();
+    let streams = Mutex::new([Some(rx_a), Some(rx_b)]);
+    let which = RwSignal::new(0);
+    let value = AsyncDerived::new_stream(move || {
+        let which = which.get();
+        streams.lock().unwrap()[which]
+            .take()
+            .expect("each stream is created once")
+    });
+
+    tx_a.unbounded_send("a1").unwrap();
+    assert_eq!(value.await, Some("a1"));
+    tx_a.unbounded_send("a2").unwrap();
+    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    assert_eq!(value.get(), Some(Some("a2")));
+
+    which.set(1);
+    Executor::tick().await;
+    // the previous stream is dropped
+    assert!(tx_a.unbounded_send("a3").is_err());
+    tx_b.unbounded_send("b1").unwrap();
+    assert_eq!(value.await, Some("b1"));
+    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    assert_eq!(value.get(), Some(Some("b1")));
+    tx_b.unbounded_send("b2").unwrap();
+    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    assert_eq!(value.get(), Some(Some("b2")));
+}
+
+#[tokio::test]
+async fn async_derived_stream_without_items_is_none() {
+    _ = Executor::init_tokio();
+    let owner = Owner::new();
+    owner.set();
+
+    let value = ArcAsyncDerived::new_stream(futures::stream::empty::<i32>);
+    assert_eq!(value.clone().await, None);
+    assert_eq!(value.get(), Some(None));
+    value.ready().await;
+}
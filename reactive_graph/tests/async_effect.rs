@@ -0,0 +1,141 @@
+#[cfg(feature = "effects")]
+pub mod imports {
+    pub use any_spawner::{Executor, TestExecutor};
+    pub use reactive_graph::{
+        effect::Effect, owner::Owner, prelude::*, signal::RwSignal,
+    };
+    pub use std::{
+        sync::{
+            atomic::{AtomicBool, AtomicUsize, Ordering},
+            Arc, Mutex,
+        },
+        time::Duration,
+    };
+
+    pub fn ms(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+}
+
+#[cfg(feature = "effects")]
+#[test]
+fn async_effect_cancels_in_flight_run() {
+    use imports::*;
+
+    _ = Executor::init_test_executor();
+    let owner = Owner::new();
+    owner.set();
+
+    let a = RwSignal::new(0);
+    let started = Arc::new(AtomicUsize::new(0));
+    let finished = Arc::new(Mutex::new(Vec::new()));
+
+    Effect::new_async_sync({
+        let started = Arc::clone(&started);
+        let finished = Arc::clone(&finished);
+        move || {
+            let started = Arc::clone(&started);
+            let finished = Arc::clone(&finished);
+            async move {
+                let value = a.get();
+                started.fetch_add(1, Ordering::Relaxed);
+                Executor::sleep(ms(100)).await;
+                finished.lock().unwrap().push(value);
+            }
+        }
+    });
+
+    TestExecutor::advance(ms(50));
+    assert_eq!(started.load(Ordering::Relaxed), 1);
+
+    a.set(1);
+    TestExecutor::advance(ms(50));
+    assert_eq!(started.load(Ordering::Relaxed), 2);
+    assert!(finished.lock().unwrap().is_empty());
+
+    TestExecutor::advance(ms(50));
+    assert_eq!(*finished.lock().unwrap(), vec![1]);
+}
+
+#[cfg(feature = "effects")]
+#[test]
+fn async_effect_tracks_reads_before_first_await() {
+    use imports::*;
+
+    _ = Executor::init_test_executor();
+    let owner = Owner::new();
+    owner.set();
+
+    let a = RwSignal::new(0);
+    let b = RwSignal::new(0);
+    let runs = Arc::new(AtomicUsize::new(0));
+
+    Effect::new_async({
+        let runs = Arc::clone(&runs);
+        move || {
+            let runs = Arc::clone(&runs);
+            async move {
+                runs.fetch_add(1, Ordering::Relaxed);
+                a.track();
+                Executor::sleep(ms(10)).await;
+                b.track();
+            }
+        }
+    });
+
+    TestExecutor::advance(ms(10));
+    assert_eq!(runs.load(Ordering::Relaxed), 1);
+
+    b.set(1);
+    TestExecutor::run_until_stalled();
+    assert_eq!(runs.load(Ordering::Relaxed), 1);
+
+    a.set(1);
+    TestExecutor::run_until_stalled();
+    assert_eq!(runs.load(Ordering::Relaxed), 2);
+}
+
+#[cfg(feature = "effects")]
+#[test]
+fn async_effect_is_cancelled_when_owner_is_cleaned_up() {
+    use imports::*;
+
+    struct SetOnDrop(Arc<AtomicBool>);
+
+    impl Drop for SetOnDrop {
+        fn drop(&mut self) {
+            self.0.store(true, Ordering::Relaxed);
+        }
+    }
+
+    _ = Executor::init_test_executor();
+    let owner = Owner::new();
+    owner.set();
+
+    let dropped = Arc::new(AtomicBool::new(false));
+    let finished = Arc::new(AtomicBool::new(false));
+    let child = owner.child();
+    child.with(|| {
+        Effect::new_async_isomorphic({
+            let dropped = Arc::clone(&dropped);
+            let finished = Arc::clone(&finished);
+            move || {
+                let guard = SetOnDrop(Arc::clone(&dropped));
+                let finished = Arc::clone(&finished);
+                async move {
+                    let _guard = guard;
+                    Executor::sleep(ms(100)).await;
+                    finished.store(true, Ordering::Relaxed);
+                }
+            }
+        });
+    });
+
+    TestExecutor::advance(ms(50));
+    assert!(!dropped.load(Ordering::Relaxed));
+
+    child.cleanup();
+    TestExecutor::advance(ms(100));
+    assert!(dropped.load(Ordering::Relaxed));
+    assert!(!finished.load(Ordering::Relaxed));
+}
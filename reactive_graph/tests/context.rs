@@ -0,0 +1,74 @@
+#![cfg(debug_assertions)]
+
+use reactive_graph::owner::{
+    expect_context, provide_context, use_context, Owner,
+};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+#[derive(Clone)]
+struct Theme;
+
+#[derive(Clone)]
+struct Locale;
+
+#[test]
+fn missing_context_lists_searched_owners() {
+    let root = Owner::new();
+    root.set();
+    provide_context(Locale);
+    let child = root.child();
+    child.with(|| provide_context(7usize));
+
+    let missing = child.missing_context::<Theme>();
+    assert_eq!(missing.type_name, std::any::type_name::<Theme>());
+    let ids = missing
+        .searched
+        .iter()
+        .map(|owner| owner.id)
+        .collect::<Vec<_>>();
+    assert_eq!(ids, [child.debug_id(), root.debug_id()]);
+    assert_eq!(missing.searched[0].contexts, ["usize"]);
+    assert_eq!(
+        missing.searched[1].contexts,
+        [std::any::type_name::<Locale>()]
+    );
+    assert!(missing.provided_elsewhere.is_empty());
+}
+
+#[test]
+fn missing_context_finds_value_in_sibling_branch() {
+    let root = Owner::new();
+    let parent = root.child();
+    let provider = parent.child();
+    let consumer = parent.child();
+    provider.with(|| provide_context(Theme));
+    assert!(consumer.with(use_context::<Theme>).is_none());
+
+    let missing = consumer.missing_context::<Theme>();
+    assert_eq!(missing.provided_elsewhere.len(), 1);
+    assert_eq!(missing.provided_elsewhere[0].id, provider.debug_id());
+    assert_eq!(
+        missing.provided_elsewhere[0].common_ancestor,
+        Some(parent.debug_id())
+    );
+    assert!(missing.to_string().contains("sibling branch"));
+}
+
+#[test]
+fn expect_context_panic_explains_missing_context() {
+    let root = Owner::new();
+    let provider = root.child();
+    let consumer = root.child();
+    provider.with(|| provide_context(Theme));
+
+    let panic = catch_unwind(AssertUnwindSafe(|| {
+        consumer.with(expect_context::<Theme>);
+    }))
+    .unwrap_err();
+    let message = panic.downcast_ref::<String>().unwrap();
+    assert!(message.contains("expected context of type"));
+    assert!(message.contains(&format!("{:#x} (current)", consumer.debug_id())));
+    assert!(
+        message.contains(&format!("provided by {:#x}", provider.debug_id()))
+    );
+}
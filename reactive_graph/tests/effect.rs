@@ -255,3 +255,53 @@ async fn paused_effect_pauses() {
         })
         .await
 }
+
+#[cfg(feature = "effects")]
+#[tokio::test]
+async fn paused_effect_catches_up_when_resumed_and_updated() {
+    use imports::*;
+    use reactive_graph::owner::StoredValue;
+
+    _ = Executor::init_tokio();
+    let owner = Owner::new();
+    owner.set();
+
+    task::LocalSet::new()
+        .run_until(async {
+            let a = RwSignal::new(-1);
+            let b = RwSignal::new(-1);
+            let seen = StoredValue::new(Vec::new());
+
+            // pausing a parent owner pauses the effect, too
+            let branch = Owner::new();
+            branch.with(|| {
+                Effect::new(move || {
+                    seen.write_value().push(a.get());
+                });
+            });
+            Effect::new(move || {
+                seen.write_value().push(b.get() * 10);
+            });
+
+            Executor::tick().await;
+            assert_eq!(seen.get_value(), vec![-1, -10]);
+
+            branch.pause();
+            a.set(1);
+            a.set(2);
+            Executor::tick().await;
+            assert_eq!(seen.get_value(), vec![-1, -10]);
+
+            // only the effect that missed a change runs, and only once
+            branch.resume_and_update();
+            Executor::tick().await;
+            assert_eq!(seen.get_value(), vec![-1, -10, 2]);
+
+            // an effect that was not notified while paused does not run
+            branch.pause();
+            branch.resume_and_update();
+            Executor::tick().await;
+            assert_eq!(seen.get_value(), vec![-1, -10, 2]);
+        })
+        .await
+}
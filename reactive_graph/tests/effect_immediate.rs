@@ -260,3 +260,30 @@ fn test_batch() {
 
     assert_eq!(values.get_value(), vec![(0, 0), (1, 0), (1, 1), (3, 3)]);
 }
+
+#[cfg(feature = "effects")]
+#[test]
+fn effects_can_read_a_memo_that_is_notifying_them() {
+    use imports::*;
+    use reactive_graph::computed::Memo;
+
+    let owner = Owner::new();
+    owner.set();
+
+    let count = RwSignal::new(0);
+    let double = Memo::new(move |_| count.get() * 2);
+
+    // the first effect updates the memo, which runs both effects synchronously while it is
+    // notifying its subscribers, and they read it again
+    let seen = Arc::new(RwLock::new(Vec::new()));
+    let _first = ImmediateEffect::new(move || {
+        double.get();
+    });
+    let _second = ImmediateEffect::new({
+        let seen = Arc::clone(&seen);
+        move || seen.write().unwrap().push(double.get())
+    });
+
+    count.set(1);
+    assert_eq!(*seen.read().unwrap(), vec![0, 2]);
+}
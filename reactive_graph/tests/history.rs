@@ -0,0 +1,118 @@
+use reactive_graph::{
+    computed::Memo,
+    owner::Owner,
+    signal::{ArcHistorySignal, HistorySignal},
+    traits::{Get, GetUntracked, Set, Update, Write},
+};
+
+#[test]
+fn undo_and_redo_arc_history_signal() {
+    let owner = Owner::new();
+    owner.set();
+
+    let a = ArcHistorySignal::new(0);
+    a.set(1);
+    a.update(|n| *n += 1);
+    assert_eq!(a.get(), 2);
+
+    assert!(a.undo());
+    assert_eq!(a.get(), 1);
+    assert!(a.undo());
+    assert_eq!(a.get(), 0);
+    assert!(!a.undo());
+    assert_eq!(a.get(), 0);
+
+    assert!(a.redo());
+    assert!(a.redo());
+    assert_eq!(a.get(), 2);
+    assert!(!a.redo());
+}
+
+#[test]
+fn new_change_clears_redo() {
+    let owner = Owner::new();
+    owner.set();
+
+    let a = HistorySignal::new(0);
+    a.set(1);
+    a.undo();
+    assert!(a.can_redo());
+    a.set(5);
+    assert!(!a.can_redo());
+    a.undo();
+    assert_eq!(a.get(), 0);
+}
+
+#[test]
+fn history_is_bounded() {
+    let owner = Owner::new();
+    owner.set();
+
+    let a = HistorySignal::with_capacity(0, 2);
+    for n in 1..=5 {
+        a.set(n);
+    }
+    assert!(a.undo());
+    assert!(a.undo());
+    assert!(!a.undo());
+    assert_eq!(a.get(), 3);
+}
+
+#[test]
+fn untracked_writes_are_not_recorded() {
+    let owner = Owner::new();
+    owner.set();
+
+    let a = HistorySignal::new(0);
+    a.maybe_update(|n| {
+        *n = 1;
+        false
+    });
+    *a.write_untracked() = 2;
+    assert!(!a.can_undo());
+    assert_eq!(a.get_untracked(), 2);
+}
+
+#[test]
+fn batch_groups_changes() {
+    let owner = Owner::new();
+    owner.set();
+
+    let a = HistorySignal::new(0);
+    a.set(1);
+    a.batch(|| {
+        a.set(2);
+        a.batch(|| a.set(3));
+        a.set(4);
+    });
+    assert_eq!(a.get(), 4);
+    a.undo();
+    assert_eq!(a.get(), 1);
+    a.redo();
+    assert_eq!(a.get(), 4);
+}
+
+#[test]
+fn can_undo_is_reactive() {
+    let owner = Owner::new();
+    owner.set();
+
+    let a = HistorySignal::new(0);
+    let can_undo = Memo::new(move |_| a.can_undo());
+    let can_redo = Memo::new(move |_| a.can_redo());
+    assert!(!can_undo.get());
+    assert!(!can_redo.get());
+
+    a.set(1);
+    assert!(can_undo.get());
+    assert!(!can_redo.get());
+
+    a.undo();
+    assert!(!can_undo.get());
+    assert!(can_redo.get());
+
+    a.redo();
+    a.clear_history();
+    assert!(!can_undo.get());
+    assert!(!can_redo.get());
+}
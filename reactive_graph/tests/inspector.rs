@@ -0,0 +1,91 @@
+#[cfg(feature = "inspector")]
+use reactive_graph::{
+    computed::ArcMemo,
+    inspector::{self, NodeKind},
+    owner::Owner,
+    prelude::*,
+    signal::{ArcRwSignal, RwSignal},
+};
+
+#[cfg(feature = "inspector")]
+#[test]
+fn snapshot_lists_nodes_and_dependencies() {
+    let owner = Owner::new();
+    owner.set();
+
+    let a = ArcRwSignal::new(1);
+    inspector::label(&a, "a");
+    let b = RwSignal::new(2);
+    inspector::label(&b, "b");
+    let sum = ArcMemo::new({
+        let a = a.clone();
+        move |_| a.get() + b.get()
+    });
+    inspector::label(&sum, "sum");
+    assert_eq!(sum.get(), 3);
+
+    let snapshot = inspector::snapshot_owner(&owner);
+    assert_eq!(snapshot.nodes.len(), 3);
+    let a_node = snapshot.node_by_label("a").unwrap();
+    let b_node = snapshot.node_by_label("b").unwrap();
+    let sum_node = snapshot.node_by_label("sum").unwrap();
+    assert_eq!(a_node.kind, NodeKind::Signal);
+    assert_eq!(sum_node.kind, NodeKind::Memo);
+    assert_eq!(sum_node.sources, vec![a_node.id, b_node.id]);
+    assert_eq!(a_node.subscribers, vec![sum_node.id]);
+    assert_eq!(b_node.subscribers, vec![sum_node.id]);
+    assert_eq!(a_node.owner, Some(0));
+    assert!(a_node
+        .defined_at
+        .as_deref()
+        .unwrap()
+        .contains("tests/inspector.rs"));
+}
+
+#[cfg(feature = "inspector")]
+#[test]
+fn snapshot_drops_disposed_nodes() {
+    let owner = Owner::new();
+    owner.set();
+
+    let child = owner.child();
+    child.with(|| {
+        let signal = RwSignal::new(0);
+        inspector::label(&signal, "child");
+    });
+    assert!(inspector::snapshot_owner(&owner)
+        .node_by_label("child")
+        .is_some());
+
+    child.cleanup();
+    assert!(inspector::snapshot_owner(&owner)
+        .node_by_label("child")
+        .is_none());
+}
+
+#[cfg(feature = "inspector")]
+#[test]
+fn snapshot_exports_json_and_dot() {
+    let owner = Owner::new();
+    owner.set();
+
+    let a = ArcRwSignal::new(1);
+    inspector::label(&a, "a \"quoted\"");
+    let double = ArcMemo::new({
+        let a = a.clone();
+        move |_| a.get() * 2
+    });
+    assert_eq!(double.get(), 2);
+
+    let snapshot = inspector::snapshot_owner(&owner);
+    let json = snapshot.to_json();
+    assert!(json.starts_with("{\"owners\":[{\"id\":0,\"parent\":null}]"));
+    assert!(json.contains("\"label\":\"a \\\"quoted\\\"\""));
+    assert!(json.contains("\"kind\":\"memo\""));
+    assert!(json.contains("\"sources\":[0],\"subscribers\":[]"));
+
+    let dot = snapshot.to_dot();
+    assert!(dot.starts_with("digraph reactive_graph {"));
+    assert!(dot.contains("subgraph cluster_owner_0"));
+    assert!(dot.contains("n0 -> n1;"));
+}
@@ -0,0 +1,84 @@
+#![cfg(feature = "leak-diagnostics")]
+
+use reactive_graph::{
+    diagnostics,
+    owner::{Owner, StoredValue},
+    signal::RwSignal,
+    traits::GetUntracked,
+};
+use std::panic::{AssertUnwindSafe, Location};
+
+#[test]
+fn cleanup_leaves_no_live_nodes() {
+    let owner = Owner::new();
+    let _child = owner.with(|| {
+        _ = RwSignal::new(0);
+        let child = Owner::new();
+        child.with(|| StoredValue::new("child"));
+        child
+    });
+    assert_eq!(diagnostics::live_nodes_owned_by(&owner).len(), 2);
+    assert_eq!(
+        diagnostics::live_node_counts().get(&Some(owner.debug_id())),
+        Some(&1)
+    );
+
+    owner.cleanup();
+    diagnostics::assert_no_live_nodes(&owner);
+}
+
+#[test]
+fn finds_nodes_created_after_cleanup() {
+    let owner = Owner::new();
+    owner.cleanup();
+
+    // e.g., a task that finishes after its owner has been cleaned up
+    let (_, line) =
+        owner.with(|| (RwSignal::new(0), Location::caller().line()));
+    let live = diagnostics::live_nodes_owned_by(&owner);
+    assert_eq!(live.len(), 1);
+    assert_eq!(live[0].defined_at.line(), line);
+    assert_eq!(live[0].owner(), Some(owner.debug_id()));
+
+    let result = std::panic::catch_unwind(AssertUnwindSafe(|| {
+        diagnostics::assert_no_live_nodes(&owner)
+    }));
+    assert!(result.is_err());
+}
+
+#[test]
+fn reports_access_after_disposal() {
+    let owner = Owner::new();
+    let signal = owner.with(|| RwSignal::new(0));
+    let defined_at = diagnostics::live_nodes_owned_by(&owner)[0].defined_at;
+    owner.cleanup();
+
+    // `try_` accessors are recorded quietly, and accessors that panic also warn
+    assert_eq!(signal.try_get_untracked(), None);
+    let result =
+        std::panic::catch_unwind(AssertUnwindSafe(|| signal.get_untracked()));
+    assert!(result.is_err());
+    let accesses = diagnostics::take_disposed_accesses()
+        .into_iter()
+        .filter(|access| access.node.defined_at == defined_at)
+        .count();
+    assert_eq!(accesses, 2);
+
+    // only the most recent accesses are kept until they are taken
+    for _ in 0..2000 {
+        assert_eq!(signal.try_get_untracked(), None);
+    }
+    assert_eq!(diagnostics::take_disposed_accesses().len(), 1024);
+
+    // creating enough new values prunes the records of disposed ones
+    let other = Owner::new();
+    other.with(|| {
+        for _ in 0..4096 {
+            _ = StoredValue::new(());
+        }
+    });
+    assert_eq!(signal.try_get_untracked(), None);
+    assert!(!diagnostics::take_disposed_accesses()
+        .iter()
+        .any(|access| access.node.defined_at == defined_at));
+}
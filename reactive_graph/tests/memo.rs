@@ -546,3 +546,47 @@ fn memo_updates_even_if_not_read_until_later() {
     println!("read memo 2");
     assert_eq!(second_memo.get(), false);
 }
+
+#[cfg(debug_assertions)]
+#[test]
+fn memo_cycle_is_reported_with_its_path() {
+    use reactive_graph::graph::take_cycle_error;
+    use std::{panic::AssertUnwindSafe, sync::OnceLock};
+
+    let owner = Owner::new();
+    owner.set();
+
+    let b_slot = Arc::new(OnceLock::<ArcMemo<i32>>::new());
+    let a = ArcMemo::new({
+        let b_slot = Arc::clone(&b_slot);
+        move |_| b_slot.get().map(|b| b.get()).unwrap_or_default() + 1
+    });
+    let b = ArcMemo::new({
+        let a = a.clone();
+        move |_| a.get() + 1
+    });
+    _ = b_slot.set(b.clone());
+
+    let payload = std::panic::catch_unwind(AssertUnwindSafe(|| a.get()))
+        .expect_err("reading a memo in a cycle should panic");
+
+    let cycle = take_cycle_error().expect("cycle should have been detected");
+    assert_eq!(payload.downcast_ref::<String>(), Some(&cycle.to_string()));
+    let path = cycle
+        .path
+        .iter()
+        .map(|node| node.defined_at)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        path,
+        vec![
+            a.defined_at().unwrap(),
+            b.defined_at().unwrap(),
+            a.defined_at().unwrap()
+        ]
+    );
+    assert!(cycle
+        .to_string()
+        .contains(&a.defined_at().unwrap().to_string()));
+    assert_eq!(take_cycle_error(), None);
+}
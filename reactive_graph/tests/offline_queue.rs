@@ -0,0 +1,204 @@
+use any_spawner::{Executor, TestExecutor};
+use futures::channel::oneshot;
+use reactive_graph::{
+    actions::{ArcMultiAction, MemoryStore, SubmissionState, SubmissionStore},
+    owner::Owner,
+    traits::GetUntracked,
+};
+use std::sync::{Arc, Mutex};
+
+type Responses<E = ()> =
+    Arc<Mutex<Vec<(i32, oneshot::Sender<Result<i32, E>>)>>>;
+
+fn action_with(
+    store: &MemoryStore<i32>,
+) -> (ArcMultiAction<i32, Result<i32, ()>>, Responses) {
+    let responses = Responses::default();
+    let action = ArcMultiAction::new({
+        let responses = Arc::clone(&responses);
+        move |n: &i32| {
+            let (tx, rx) = oneshot::channel();
+            responses.lock().unwrap().push((*n, tx));
+            async move { rx.await.unwrap_or(Err(())) }
+        }
+    })
+    .with_offline_queue(store.clone());
+    (action, responses)
+}
+
+// responds to the oldest call that is still waiting, and returns its input
+fn respond<E>(responses: &Responses<E>, result: Result<i32, E>) -> i32 {
+    let (n, tx) = responses.lock().unwrap().remove(0);
+    _ = tx.send(result);
+    TestExecutor::run_until_stalled();
+    n
+}
+
+fn states<E: Send + Sync + 'static>(
+    action: &ArcMultiAction<i32, Result<i32, E>>,
+) -> Vec<SubmissionState> {
+    action
+        .submissions()
+        .get_untracked()
+        .iter()
+        .map(|sub| sub.state().get_untracked())
+        .collect()
+}
+
+#[test]
+fn submissions_are_queued_offline_and_replayed_in_order() {
+    _ = Executor::init_test_executor();
+    let owner = Owner::new();
+    owner.set();
+
+    let store = MemoryStore::new();
+    let (action, responses) = action_with(&store);
+    action.set_online(false);
+    action.dispatch(1);
+    action.dispatch(2);
+    TestExecutor::run_until_stalled();
+    assert!(responses.lock().unwrap().is_empty());
+    assert_eq!(store.load(), vec![1, 2]);
+    assert_eq!(states(&action), [SubmissionState::Queued; 2]);
+
+    action.set_online(true);
+    TestExecutor::run_until_stalled();
+    assert_eq!(responses.lock().unwrap().len(), 1);
+    assert_eq!(
+        states(&action),
+        [SubmissionState::InFlight, SubmissionState::Queued]
+    );
+
+    assert_eq!(respond(&responses, Ok(1)), 1);
+    assert_eq!(store.load(), vec![2]);
+    assert_eq!(respond(&responses, Ok(2)), 2);
+    assert!(store.load().is_empty());
+    assert_eq!(states(&action), [SubmissionState::Resolved; 2]);
+    assert_eq!(action.version().get_untracked(), 2);
+}
+
+#[test]
+fn failed_submission_holds_up_the_queue_until_replayed() {
+    _ = Executor::init_test_executor();
+    let owner = Owner::new();
+    owner.set();
+
+    let store = MemoryStore::new();
+    let (action, responses) = action_with(&store);
+    action.dispatch(1);
+    action.dispatch(2);
+    TestExecutor::run_until_stalled();
+
+    assert_eq!(respond(&responses, Err(())), 1);
+    assert!(responses.lock().unwrap().is_empty());
+    assert_eq!(
+        states(&action),
+        [SubmissionState::Failed, SubmissionState::Queued]
+    );
+    let failed = action.submissions().get_untracked().remove(0);
+    assert!(!failed.pending().get_untracked());
+    assert_eq!(failed.input().get_untracked(), Some(1));
+    assert_eq!(store.load(), vec![1, 2]);
+
+    action.replay();
+    TestExecutor::run_until_stalled();
+    assert_eq!(failed.state().get_untracked(), SubmissionState::InFlight);
+    assert_eq!(respond(&responses, Ok(1)), 1);
+    assert_eq!(respond(&responses, Ok(2)), 2);
+    assert_eq!(failed.value().get_untracked(), Some(Ok(1)));
+    assert_eq!(states(&action), [SubmissionState::Resolved; 2]);
+    assert!(store.load().is_empty());
+}
+
+#[test]
+fn stored_submissions_are_restored_and_canceled_ones_are_skipped() {
+    _ = Executor::init_test_executor();
+    let owner = Owner::new();
+    owner.set();
+
+    let store = MemoryStore::new();
+    store.save(&[1, 2, 3]);
+
+    let responses = Responses::default();
+    let action = ArcMultiAction::new({
+        let responses = Arc::clone(&responses);
+        move |n: &i32| {
+            let (tx, rx) = oneshot::channel();
+            responses.lock().unwrap().push((*n, tx));
+            async move { rx.await.unwrap_or(Err(())) }
+        }
+    });
+    action.set_online(false);
+    let action = action.with_offline_queue(store.clone());
+    assert_eq!(states(&action), [SubmissionState::Queued; 3]);
+
+    action.submissions().get_untracked()[1].cancel();
+    action.set_online(true);
+    TestExecutor::run_until_stalled();
+    assert_eq!(respond(&responses, Ok(1)), 1);
+    assert_eq!(respond(&responses, Ok(3)), 3);
+    assert!(responses.lock().unwrap().is_empty());
+    assert!(store.load().is_empty());
+    assert_eq!(states(&action), [SubmissionState::Resolved; 3]);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SaveError {
+    Network,
+    Invalid,
+}
+
+#[test]
+fn permanent_failures_are_resolved_without_holding_up_the_queue() {
+    _ = Executor::init_test_executor();
+    let owner = Owner::new();
+    owner.set();
+
+    let store = MemoryStore::new();
+    let responses = Responses::<SaveError>::default();
+    let action = ArcMultiAction::new({
+        let responses = Arc::clone(&responses);
+        move |n: &i32| {
+            let (tx, rx) = oneshot::channel();
+            responses.lock().unwrap().push((*n, tx));
+            async move { rx.await.unwrap_or(Err(SaveError::Network)) }
+        }
+    })
+    .with_offline_queue_retrying_if(store.clone(), |err| {
+        *err == SaveError::Network
+    });
+    action.dispatch(1);
+    action.dispatch(2);
+    action.dispatch(3);
+    TestExecutor::run_until_stalled();
+
+    // an invalid submission would only fail again, so the queue moves on
+    assert_eq!(respond(&responses, Err(SaveError::Invalid)), 1);
+    assert_eq!(store.load(), vec![2, 3]);
+    assert_eq!(
+        states(&action),
+        [
+            SubmissionState::Resolved,
+            SubmissionState::InFlight,
+            SubmissionState::Queued
+        ]
+    );
+    let invalid = action.submissions().get_untracked().remove(0);
+    assert_eq!(
+        invalid.value().get_untracked(),
+        Some(Err(SaveError::Invalid))
+    );
+
+    // a network error is retried later
+    assert_eq!(respond(&responses, Err(SaveError::Network)), 2);
+    assert!(responses.lock().unwrap().is_empty());
+    assert_eq!(store.load(), vec![2, 3]);
+    assert_eq!(
+        states(&action),
+        [
+            SubmissionState::Resolved,
+            SubmissionState::Failed,
+            SubmissionState::Queued
+        ]
+    );
+}
@@ -0,0 +1,191 @@
+use any_spawner::{Executor, TestExecutor};
+use futures::channel::oneshot;
+use reactive_graph::{
+    actions::{ArcAction, ArcMultiAction},
+    owner::Owner,
+    signal::ArcRwSignal,
+    traits::{GetUntracked, Update},
+};
+use std::sync::{Arc, Mutex};
+
+type Responses = Arc<Mutex<Vec<oneshot::Sender<Result<(), ()>>>>>;
+
+// an action function whose futures resolve when a response is sent
+fn respond_later(
+    responses: &Responses,
+) -> impl Fn(&i32) -> oneshot::Receiver<Result<(), ()>> + Send + Sync + 'static
+{
+    let responses = Arc::clone(responses);
+    move |_| {
+        let (tx, rx) = oneshot::channel();
+        responses.lock().unwrap().push(tx);
+        rx
+    }
+}
+
+#[test]
+fn optimistic_update_is_kept_on_success_and_rolled_back_on_error() {
+    _ = Executor::init_test_executor();
+    let owner = Owner::new();
+    owner.set();
+
+    let count = ArcRwSignal::new(0);
+    let responses = Responses::default();
+    let respond = respond_later(&responses);
+    let action = ArcAction::new(move |n: &i32| {
+        let rx = respond(n);
+        async move { rx.await.unwrap() }
+    })
+    .with_optimistic_update({
+        let count = count.clone();
+        move |n, update| {
+            let n = *n;
+            update.update(&count, |count| *count += n, move |count| *count -= n)
+        }
+    });
+
+    action.dispatch(1);
+    assert_eq!(count.get_untracked(), 1);
+    responses.lock().unwrap().remove(0).send(Ok(())).unwrap();
+    TestExecutor::run_until_stalled();
+    assert_eq!(count.get_untracked(), 1);
+
+    action.dispatch(10);
+    assert_eq!(count.get_untracked(), 11);
+    responses.lock().unwrap().remove(0).send(Err(())).unwrap();
+    TestExecutor::run_until_stalled();
+    assert_eq!(count.get_untracked(), 1);
+}
+
+#[test]
+fn optimistic_update_is_rolled_back_on_abort() {
+    _ = Executor::init_test_executor();
+    let owner = Owner::new();
+    owner.set();
+
+    let label = ArcRwSignal::new("saved");
+    let action =
+        ArcAction::new(|_: &()| std::future::pending::<Result<(), ()>>())
+            .with_optimistic_update({
+                let label = label.clone();
+                move |_, update| update.set(&label, "saving")
+            });
+
+    let handle = action.dispatch(());
+    TestExecutor::run_until_stalled();
+    assert_eq!(label.get_untracked(), "saving");
+
+    handle.abort();
+    TestExecutor::run_until_stalled();
+    assert_eq!(label.get_untracked(), "saved");
+}
+
+#[test]
+fn multi_action_rolls_back_each_failed_submission() {
+    _ = Executor::init_test_executor();
+    let owner = Owner::new();
+    owner.set();
+
+    let todos = ArcRwSignal::new(Vec::<i32>::new());
+    let responses = Responses::default();
+    let respond = respond_later(&responses);
+    let action = ArcMultiAction::new(move |n: &i32| {
+        let rx = respond(n);
+        async move { rx.await.unwrap() }
+    })
+    .with_optimistic_update({
+        let todos = todos.clone();
+        move |n, update| {
+            let n = *n;
+            update.update(
+                &todos,
+                |todos| todos.push(n),
+                move |todos| todos.retain(|todo| *todo != n),
+            )
+        }
+    });
+
+    action.dispatch(1);
+    action.dispatch(2);
+    assert_eq!(todos.get_untracked(), vec![1, 2]);
+
+    // the second submission fails, which removes only its own item
+    let mut responses = std::mem::take(&mut *responses.lock().unwrap());
+    responses.remove(1).send(Err(())).unwrap();
+    TestExecutor::run_until_stalled();
+    assert_eq!(todos.get_untracked(), vec![1]);
+
+    responses.remove(0).send(Ok(())).unwrap();
+    TestExecutor::run_until_stalled();
+    assert_eq!(todos.get_untracked(), vec![1]);
+}
+
+#[test]
+fn rolling_back_keeps_later_optimistic_changes_and_writes() {
+    _ = Executor::init_test_executor();
+    let owner = Owner::new();
+    owner.set();
+
+    let count = ArcRwSignal::new(0);
+    let responses = Responses::default();
+    let respond = respond_later(&responses);
+    let action = ArcAction::new(move |n: &i32| {
+        let rx = respond(n);
+        async move { rx.await.unwrap() }
+    })
+    .with_optimistic_update({
+        let count = count.clone();
+        move |n, update| {
+            let n = *n;
+            update.update(&count, |count| *count += n, move |count| *count -= n)
+        }
+    });
+
+    action.dispatch(1);
+    action.dispatch(10);
+    count.update(|count| *count += 100);
+    assert_eq!(count.get_untracked(), 111);
+
+    // the first dispatch fails while the second is still pending
+    let mut responses = std::mem::take(&mut *responses.lock().unwrap());
+    responses.remove(0).send(Err(())).unwrap();
+    TestExecutor::run_until_stalled();
+    assert_eq!(count.get_untracked(), 110);
+
+    responses.remove(0).send(Ok(())).unwrap();
+    TestExecutor::run_until_stalled();
+    assert_eq!(count.get_untracked(), 110);
+}
+
+#[test]
+fn rolling_back_a_set_leaves_a_later_value_alone() {
+    _ = Executor::init_test_executor();
+    let owner = Owner::new();
+    owner.set();
+
+    let label = ArcRwSignal::new(0);
+    let responses = Responses::default();
+    let respond = respond_later(&responses);
+    let action = ArcAction::new(move |n: &i32| {
+        let rx = respond(n);
+        async move { rx.await.unwrap() }
+    })
+    .with_optimistic_update({
+        let label = label.clone();
+        move |n, update| update.set(&label, *n)
+    });
+
+    action.dispatch(1);
+    action.dispatch(2);
+    assert_eq!(label.get_untracked(), 2);
+
+    // the first dispatch fails, but its value has already been replaced by the second one
+    let mut responses = std::mem::take(&mut *responses.lock().unwrap());
+    responses.remove(0).send(Err(())).unwrap();
+    TestExecutor::run_until_stalled();
+    assert_eq!(label.get_untracked(), 2);
+
+    responses.remove(0).send(Ok(())).unwrap();
+    TestExecutor::run_until_stalled();
+    assert_eq!(label.get_untracked(), 2);
+}
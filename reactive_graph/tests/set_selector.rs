@@ -0,0 +1,88 @@
+use reactive_graph::{
+    computed::{ArcMemo, SetSelector},
+    owner::Owner,
+    prelude::*,
+};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+// one memo per row, each counting how many times it has run
+fn rows(
+    selection: &SetSelector<usize>,
+    count: usize,
+) -> (Vec<ArcMemo<bool>>, Vec<Arc<AtomicUsize>>) {
+    (0..count)
+        .map(|row| {
+            let runs = Arc::new(AtomicUsize::new(0));
+            let memo = ArcMemo::new({
+                let selection = selection.clone();
+                let runs = Arc::clone(&runs);
+                move |_| {
+                    runs.fetch_add(1, Ordering::Relaxed);
+                    selection.selected(&row)
+                }
+            });
+            (memo, runs)
+        })
+        .unzip()
+}
+
+fn read_all(rows: &[ArcMemo<bool>]) -> Vec<bool> {
+    rows.iter().map(|row| row.get()).collect()
+}
+
+fn run_counts(runs: &[Arc<AtomicUsize>]) -> Vec<usize> {
+    runs.iter()
+        .map(|runs| runs.load(Ordering::Relaxed))
+        .collect()
+}
+
+#[test]
+fn toggling_a_key_only_notifies_that_row() {
+    let owner = Owner::new();
+    owner.set();
+
+    let selection = SetSelector::new();
+    let (rows, runs) = rows(&selection, 4);
+    assert_eq!(read_all(&rows), [false; 4]);
+    assert_eq!(run_counts(&runs), [1; 4]);
+
+    selection.toggle(2);
+    assert_eq!(read_all(&rows), [false, false, true, false]);
+    assert_eq!(run_counts(&runs), [1, 1, 2, 1]);
+
+    selection.toggle(2);
+    selection.select(0);
+    // selecting a key that is already selected does not notify it again
+    selection.select(0);
+    assert_eq!(read_all(&rows), [true, false, false, false]);
+    assert_eq!(run_counts(&runs), [2, 1, 3, 1]);
+}
+
+#[test]
+fn select_all_and_clear_notify_each_affected_row_once() {
+    let owner = Owner::new();
+    owner.set();
+
+    let selection = SetSelector::from_iter([1]);
+    let (rows, runs) = rows(&selection, 4);
+    let len = ArcMemo::new({
+        let selection = selection.clone();
+        move |_| selection.len()
+    });
+    assert_eq!(read_all(&rows), [false, true, false, false]);
+    assert_eq!(len.get(), 1);
+
+    selection.select_all(0..3);
+    assert_eq!(read_all(&rows), [true, true, true, false]);
+    assert_eq!(run_counts(&runs), [2, 1, 2, 1]);
+    assert_eq!(len.get(), 3);
+
+    selection.clear();
+    assert_eq!(read_all(&rows), [false; 4]);
+    assert_eq!(run_counts(&runs), [3, 2, 3, 1]);
+    assert!(selection.is_empty());
+    assert_eq!(len.get(), 0);
+}
@@ -0,0 +1,179 @@
+use futures::StreamExt;
+use indexmap::IndexMap;
+use reactive_graph::{
+    computed::Memo,
+    owner::Owner,
+    prelude::*,
+    signal::{
+        ArcSignalMap, ArcSignalVec, MapDiff, SignalMap, SignalVec, VecDiff,
+    },
+};
+use std::panic::AssertUnwindSafe;
+
+#[test]
+fn vec_diffs_reproduce_the_list() {
+    let owner = Owner::new();
+    owner.set();
+
+    let list = SignalVec::new(vec![1, 2, 3]);
+    let mut diffs = list.diffs();
+
+    list.push(4);
+    list.insert(0, 0);
+    list.remove(2);
+    list.move_item(3, 1);
+    list.set_at(0, 10);
+    list.update_at(1, |n| *n *= 2);
+    assert_eq!(list.pop(), Some(3));
+    assert_eq!(list.get(), vec![10, 8, 1]);
+
+    let mut copy = Vec::new();
+    let mut count = 0;
+    while let Some(diff) = diffs.try_next() {
+        diff.apply(&mut copy);
+        count += 1;
+    }
+    assert_eq!(copy, list.get());
+    // the initial snapshot, plus one diff per change
+    assert_eq!(count, 8);
+}
+
+#[test]
+fn vec_is_tracked_like_a_signal() {
+    let owner = Owner::new();
+    owner.set();
+
+    let list = ArcSignalVec::new(vec![1, 2]);
+    let sum = Memo::new({
+        let list = list.clone();
+        move |_| list.read().iter().sum::<i32>()
+    });
+    assert_eq!(sum.get(), 3);
+
+    list.push(3);
+    assert_eq!(sum.get(), 6);
+    list.clear();
+    assert_eq!(sum.get(), 0);
+}
+
+#[test]
+fn vec_diffs_start_from_current_values() {
+    let owner = Owner::new();
+    owner.set();
+
+    let list = ArcSignalVec::new(vec![1]);
+    list.push(2);
+    let mut diffs = list.diffs();
+    assert_eq!(
+        diffs.try_next(),
+        Some(VecDiff::Replace { values: vec![1, 2] })
+    );
+    assert_eq!(diffs.try_next(), None);
+
+    list.replace(vec![3]);
+    assert_eq!(diffs.try_next(), Some(VecDiff::Replace { values: vec![3] }));
+}
+
+#[test]
+fn vec_is_usable_after_an_out_of_bounds_change() {
+    let owner = Owner::new();
+    owner.set();
+
+    let list = ArcSignalVec::new(vec![1, 2]);
+    let mut diffs = list.diffs();
+    _ = diffs.try_next();
+
+    for change in [
+        Box::new(|list: &ArcSignalVec<i32>| list.insert(3, 0))
+            as Box<dyn Fn(&ArcSignalVec<i32>)>,
+        Box::new(|list| list.move_item(0, 2)),
+        Box::new(|list| _ = list.remove(2)),
+        Box::new(|list| list.update_at(2, |n| *n += 1)),
+    ] {
+        let result =
+            std::panic::catch_unwind(AssertUnwindSafe(|| change(&list)));
+        assert!(result.is_err());
+    }
+
+    // failed changes are not recorded, and leave the list unchanged and unlocked
+    assert_eq!(diffs.try_next(), None);
+    list.push(3);
+    assert_eq!(list.get(), vec![1, 2, 3]);
+    assert_eq!(diffs.try_next(), Some(VecDiff::Push { value: 3 }));
+}
+
+#[tokio::test]
+async fn diff_stream_ends_when_collection_is_dropped() {
+    let list = ArcSignalVec::new(vec!["a"]);
+    let diffs = list.diffs();
+    list.push("b");
+    drop(list);
+
+    let all = diffs.collect::<Vec<_>>().await;
+    assert_eq!(
+        all,
+        vec![
+            VecDiff::Replace { values: vec!["a"] },
+            VecDiff::Push { value: "b" }
+        ]
+    );
+}
+
+#[test]
+fn map_diffs_reproduce_the_map() {
+    let owner = Owner::new();
+    owner.set();
+
+    let map = SignalMap::new();
+    map.insert("a", 1);
+    let mut diffs = map.diffs();
+
+    map.insert("b", 2);
+    map.insert("c", 3);
+    map.insert("a", 10);
+    assert!(map.update(&"b", |n| *n += 1));
+    assert!(!map.update(&"z", |n| *n += 1));
+    assert_eq!(map.remove(&"c"), Some(3));
+    assert_eq!(map.remove(&"c"), None);
+
+    let mut copy = IndexMap::new();
+    let mut all = Vec::new();
+    while let Some(diff) = diffs.try_next() {
+        all.push(diff.clone());
+        diff.apply(&mut copy);
+    }
+    assert_eq!(copy, *map.read());
+    assert_eq!(
+        all,
+        vec![
+            MapDiff::Replace {
+                entries: vec![("a", 1)]
+            },
+            MapDiff::Insert { key: "b", value: 2 },
+            MapDiff::Insert { key: "c", value: 3 },
+            MapDiff::Update {
+                key: "a",
+                value: 10
+            },
+            MapDiff::Update { key: "b", value: 3 },
+            MapDiff::Remove { key: "c" },
+        ]
+    );
+}
+
+#[test]
+fn map_is_tracked_like_a_signal() {
+    let owner = Owner::new();
+    owner.set();
+
+    let map = ArcSignalMap::<&str, i32>::new();
+    let len = Memo::new({
+        let map = map.clone();
+        move |_| map.read().len()
+    });
+    assert_eq!(len.get(), 0);
+    map.insert("a", 1);
+    assert_eq!(len.get(), 1);
+    map.clear();
+    assert_eq!(len.get(), 0);
+}
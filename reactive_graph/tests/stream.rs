@@ -0,0 +1,106 @@
+use any_spawner::{Executor, TestExecutor};
+use futures::{channel::mpsc, FutureExt, StreamExt};
+use reactive_graph::{
+    owner::Owner,
+    signal::{ArcReadSignal, ArcRwSignal, ReadSignal},
+    traits::{FromStream, GetUntracked, Set, ToStream},
+};
+
+#[test]
+fn from_stream_with_initial_stops_when_owner_is_cleaned_up() {
+    _ = Executor::init_test_executor();
+    let owner = Owner::new();
+    let (tx, rx) = mpsc::unbounded();
+    let value = owner.with(|| ArcReadSignal::from_stream_with_initial(rx, 0));
+    assert_eq!(value.get_untracked(), 0);
+
+    tx.unbounded_send(1).unwrap();
+    TestExecutor::run_until_stalled();
+    assert_eq!(value.get_untracked(), 1);
+
+    owner.cleanup();
+    tx.unbounded_send(2).unwrap();
+    TestExecutor::run_until_stalled();
+    assert_eq!(value.get_untracked(), 1);
+    assert_eq!(TestExecutor::pending_tasks(), 0);
+}
+
+#[test]
+fn from_stream_polls_one_value_at_a_time() {
+    _ = Executor::init_test_executor();
+    let owner = Owner::new();
+    owner.set();
+    let (tx, rx) = mpsc::unbounded();
+    let value = ReadSignal::<Option<i32>>::from_stream(rx);
+    assert_eq!(value.get_untracked(), None);
+
+    tx.unbounded_send(1).unwrap();
+    tx.unbounded_send(2).unwrap();
+    TestExecutor::run_until_stalled();
+    assert_eq!(value.get_untracked(), Some(2));
+
+    drop(tx);
+    TestExecutor::run_until_stalled();
+    assert_eq!(TestExecutor::pending_tasks(), 0);
+}
+
+#[test]
+fn from_stream_stops_when_owner_is_cleaned_up() {
+    _ = Executor::init_test_executor();
+    let owner = Owner::new();
+    let (tx, rx) = mpsc::unbounded();
+    let value = owner.with(|| ReadSignal::<Option<i32>>::from_stream(rx));
+
+    tx.unbounded_send(1).unwrap();
+    TestExecutor::run_until_stalled();
+    assert_eq!(value.get_untracked(), Some(1));
+
+    owner.cleanup();
+    tx.unbounded_send(2).unwrap();
+    TestExecutor::run_until_stalled();
+    assert_eq!(TestExecutor::pending_tasks(), 0);
+}
+
+#[test]
+fn to_stream_yields_every_value_and_ends_on_cleanup() {
+    _ = Executor::init_test_executor();
+    let owner = Owner::new();
+    let count = ArcRwSignal::new(0);
+    let mut stream = owner.with(|| count.to_stream());
+
+    TestExecutor::run_until_stalled();
+    for value in 1..=3 {
+        count.set(value);
+        TestExecutor::run_until_stalled();
+    }
+    for value in 0..=3 {
+        assert_eq!(stream.next().now_or_never(), Some(Some(value)));
+    }
+    assert_eq!(stream.next().now_or_never(), None);
+
+    owner.cleanup();
+    assert_eq!(stream.next().now_or_never(), Some(None));
+}
+
+#[test]
+fn to_stream_latest_skips_to_latest_value_and_ends_on_cleanup() {
+    _ = Executor::init_test_executor();
+    let owner = Owner::new();
+    let count = ArcRwSignal::new(0);
+    let mut stream = owner.with(|| count.to_stream_latest());
+
+    TestExecutor::run_until_stalled();
+    assert_eq!(stream.next().now_or_never(), Some(Some(0)));
+    assert_eq!(stream.next().now_or_never(), None);
+
+    // the consumer is not ready while these values are set
+    for value in 1..=3 {
+        count.set(value);
+        TestExecutor::run_until_stalled();
+    }
+    assert_eq!(stream.next().now_or_never(), Some(Some(3)));
+    assert_eq!(stream.next().now_or_never(), None);
+
+    owner.cleanup();
+    assert_eq!(stream.next().now_or_never(), Some(None));
+}
@@ -0,0 +1,88 @@
+use any_spawner::{Executor, TestExecutor};
+use reactive_graph::{
+    owner::Owner,
+    prelude::*,
+    signal::{signal, ArcRwSignal},
+    wrappers::read::Signal,
+};
+use std::time::Duration;
+
+fn ms(ms: u64) -> Duration {
+    Duration::from_millis(ms)
+}
+
+#[test]
+fn debounced_waits_for_quiet_period() {
+    _ = Executor::init_test_executor();
+    let owner = Owner::new();
+    owner.set();
+
+    let (a, set_a) = signal(0);
+    let debounced = Signal::<i32>::from(a).debounced(ms(100));
+    assert_eq!(debounced.get(), 0);
+
+    for n in 1..=5 {
+        set_a.set(n);
+        TestExecutor::advance(ms(50));
+        assert_eq!(debounced.get(), 0);
+    }
+
+    TestExecutor::advance(ms(50));
+    assert_eq!(debounced.get(), 5);
+    assert_eq!(TestExecutor::pending_tasks(), 0);
+}
+
+#[test]
+fn throttled_updates_at_most_once_per_period() {
+    _ = Executor::init_test_executor();
+    let owner = Owner::new();
+    owner.set();
+
+    let a = ArcRwSignal::new(0);
+    let throttled = Signal::<i32>::from(a.clone()).throttled(ms(100));
+
+    // leading edge
+    a.set(1);
+    assert_eq!(throttled.get(), 1);
+
+    a.set(2);
+    a.set(3);
+    TestExecutor::advance(ms(99));
+    assert_eq!(throttled.get(), 1);
+
+    // trailing edge applies the most recent value, and starts a new period
+    TestExecutor::advance(ms(1));
+    assert_eq!(throttled.get(), 3);
+    a.set(4);
+    assert_eq!(throttled.get(), 3);
+    TestExecutor::advance(ms(100));
+    assert_eq!(throttled.get(), 4);
+
+    // once a period passes without changes, the next change applies immediately
+    TestExecutor::advance(ms(100));
+    a.set(5);
+    assert_eq!(throttled.get(), 5);
+}
+
+#[test]
+fn timed_signals_stop_when_owner_is_cleaned_up() {
+    _ = Executor::init_test_executor();
+    let owner = Owner::new();
+    owner.set();
+
+    let a = ArcRwSignal::new(0);
+    let child = owner.child();
+    let (debounced, throttled) = child.with(|| {
+        let source = Signal::<i32>::from(a.clone());
+        (source.debounced(ms(100)), source.throttled(ms(100)))
+    });
+    a.set(1);
+    a.set(2);
+    assert!(TestExecutor::pending_tasks() > 0);
+
+    child.cleanup();
+    TestExecutor::run_until_stalled();
+    assert_eq!(TestExecutor::pending_tasks(), 0);
+    assert!(debounced.try_get().is_none());
+    assert!(throttled.try_get().is_none());
+}
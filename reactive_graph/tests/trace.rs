@@ -0,0 +1,78 @@
+#![cfg(feature = "tracing")]
+
+use any_spawner::{Executor, TestExecutor};
+use reactive_graph::{
+    computed::ArcMemo,
+    effect::{Effect, RenderEffect},
+    owner::Owner,
+    prelude::*,
+    signal::ArcRwSignal,
+    trace::ChromeTraceCollector,
+};
+
+#[test]
+fn memo_recomputes_are_recorded_as_duration_events() {
+    let owner = Owner::new();
+    owner.set();
+
+    let collector = ChromeTraceCollector::new();
+    let count = ArcRwSignal::new(1);
+    let double = ArcMemo::new({
+        let count = count.clone();
+        move |_| count.get() * 2
+    });
+    #[cfg(feature = "inspector")]
+    reactive_graph::inspector::label(&double, "double");
+
+    tracing::subscriber::with_default(collector.clone(), || {
+        assert_eq!(double.get(), 2);
+        count.set(2);
+        assert_eq!(double.get(), 4);
+    });
+
+    let json = collector.to_json();
+    assert!(json.starts_with(r#"{"traceEvents":["#));
+    assert_eq!(json.matches(r#"{"name":"memo","#).count(), 4);
+    assert!(json.contains(r#""owner":"#));
+    #[cfg(feature = "inspector")]
+    assert!(json.contains(r#""label":"double""#));
+
+    collector.clear();
+    assert_eq!(collector.to_json(), r#"{"traceEvents":[]}"#);
+}
+
+#[test]
+fn effect_runs_are_recorded_where_they_were_defined() {
+    _ = Executor::init_test_executor();
+    let owner = Owner::new();
+    owner.set();
+
+    let collector = ChromeTraceCollector::new();
+    tracing::subscriber::with_default(collector.clone(), || {
+        let _render_effect = RenderEffect::new_isomorphic(|_| ());
+        Effect::new_isomorphic(|_| ());
+        TestExecutor::run_until_stalled();
+    });
+
+    let json = collector.to_json();
+    assert_eq!(json.matches(r#"{"name":"render_effect","#).count(), 2);
+    assert_eq!(json.matches(r#"{"name":"effect","#).count(), 2);
+    #[cfg(debug_assertions)]
+    assert_eq!(
+        json.matches(r#""defined_at":"reactive_graph/tests/trace.rs:"#)
+            .count(),
+        2
+    );
+}
+
+#[test]
+fn events_are_recorded_with_their_fields() {
+    let collector = ChromeTraceCollector::new();
+    tracing::subscriber::with_default(collector.clone(), || {
+        tracing::info!(quote = "\"quoted\"", "hello");
+    });
+    let json = collector.to_json();
+    assert!(json.contains(r#""ph":"i""#));
+    assert!(json.contains(r#""message":"hello""#));
+    assert!(json.contains(r#""quote":"\"quoted\"""#));
+}
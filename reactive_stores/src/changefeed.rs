@@ -0,0 +1,950 @@
+use crate::{
+    map::KEY_SET,
+    path::{StorePath, StorePathSegment},
+    ArcStore, KeyMap, PatchOperation, Store, StoreFieldTrigger,
+};
+use futures::Stream;
+use or_poisoned::OrPoisoned;
+use reactive_graph::{
+    graph::{AnySource, AnySubscriber, ReactiveNode, Source, Subscriber},
+    owner::Storage,
+};
+use rustc_hash::FxHashMap;
+use serde::{ser, Serialize, Serializer};
+use serde_json::{value::Serializer as ValueSerializer, Value};
+use std::{
+    fmt,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, RwLock, Weak,
+    },
+    task::{Context, Poll, Waker},
+};
+
+/// A change to one field of a store, as yielded by a [`Changefeed`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Change {
+    /// The path to the field that changed, from the root of the store.
+    pub path: Vec<ChangeSegment>,
+    /// The new value of the field, or `None` if it has been removed (for example, if it was an
+    /// item past the end of a `Vec` that has been truncated).
+    pub value: Option<Value>,
+}
+
+/// One segment of the path of a [`Change`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChangeSegment {
+    /// A named struct field.
+    Field(&'static str),
+    /// An item in a `Vec`, or a field of a tuple or tuple struct.
+    Index(usize),
+    /// An item in a keyed field.
+    Key {
+        /// The key of the item, formatted with `Debug`.
+        key: String,
+        /// The current index of the item.
+        index: usize,
+    },
+}
+
+impl fmt::Display for ChangeSegment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChangeSegment::Field(name) => f.write_str(name),
+            ChangeSegment::Index(index) => write!(f, "[{index}]"),
+            ChangeSegment::Key { key, .. } => write!(f, "[{key}]"),
+        }
+    }
+}
+
+impl Change {
+    /// Returns the path of the field as a [JSON Pointer](https://datatracker.ietf.org/doc/html/rfc6901)
+    /// into the serialized form of the store's value.
+    pub fn pointer(&self) -> String {
+        let mut pointer = String::new();
+        for segment in &self.path {
+            pointer.push('/');
+            match segment {
+                ChangeSegment::Field(name) => pointer
+                    .push_str(&name.replace('~', "~0").replace('/', "~1")),
+                ChangeSegment::Index(index)
+                | ChangeSegment::Key { index, .. } => {
+                    pointer.push_str(&index.to_string())
+                }
+            }
+        }
+        pointer
+    }
+
+    /// Converts the change into a JSON Patch operation, which replaces or removes the value at
+    /// its [`pointer`](Change::pointer).
+    pub fn to_patch_operation(&self) -> PatchOperation {
+        match &self.value {
+            Some(value) => PatchOperation::Replace {
+                path: self.pointer(),
+                value: value.clone(),
+            },
+            None => PatchOperation::Remove {
+                path: self.pointer(),
+            },
+        }
+    }
+}
+
+/// Records which fields of a store have been notified, for each of its changefeeds.
+#[derive(Default)]
+pub(crate) struct ChangeLog {
+    // set once the first changefeed is created, so that stores without one don't watch fields
+    active: AtomicBool,
+    feeds: Mutex<Vec<Weak<FeedInner>>>,
+    watchers: Mutex<FxHashMap<StorePath, Arc<FieldWatcher>>>,
+}
+
+impl fmt::Debug for ChangeLog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChangeLog")
+            .field("active", &self.active)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ChangeLog {
+    /// Starts recording changes to the field at `path`, if any changefeed has been created.
+    pub(crate) fn watch(
+        self: &Arc<Self>,
+        path: &StorePath,
+        trigger: &StoreFieldTrigger,
+    ) {
+        if !self.active.load(Ordering::Relaxed) {
+            return;
+        }
+        let mut watchers = self.watchers.lock().or_poisoned();
+        if watchers.contains_key(path) {
+            return;
+        }
+        let watcher = Arc::new_cyclic(|this| FieldWatcher {
+            this: Weak::clone(this),
+            path: path.clone(),
+            log: Arc::downgrade(self),
+        });
+        // every direct change to a field notifies its own `this` trigger
+        trigger.this.add_subscriber(watcher.to_any_subscriber());
+        watchers.insert(path.clone(), watcher);
+    }
+
+    fn record(&self, path: &StorePath) {
+        let mut feeds = self.feeds.lock().or_poisoned();
+        feeds.retain(|feed| match feed.upgrade() {
+            Some(feed) => {
+                feed.push(path);
+                true
+            }
+            None => false,
+        });
+    }
+}
+
+/// Subscribes to the trigger of one field, and records that field whenever it is notified.
+struct FieldWatcher {
+    this: Weak<FieldWatcher>,
+    path: StorePath,
+    log: Weak<ChangeLog>,
+}
+
+impl FieldWatcher {
+    fn to_any_subscriber(&self) -> AnySubscriber {
+        AnySubscriber(
+            self.this.as_ptr() as usize,
+            Weak::clone(&self.this) as Weak<dyn Subscriber + Send + Sync>,
+        )
+    }
+}
+
+impl ReactiveNode for FieldWatcher {
+    fn mark_dirty(&self) {
+        if let Some(log) = self.log.upgrade() {
+            log.record(&self.path);
+        }
+    }
+
+    fn mark_check(&self) {}
+
+    fn mark_subscribers_check(&self) {}
+
+    fn update_if_necessary(&self) -> bool {
+        false
+    }
+}
+
+impl Subscriber for FieldWatcher {
+    fn add_source(&self, _source: AnySource) {}
+
+    fn clear_sources(&self, _subscriber: &AnySubscriber) {}
+}
+
+#[derive(Default)]
+struct FeedInner {
+    pending: Mutex<Vec<StorePath>>,
+    waker: Mutex<Option<Waker>>,
+}
+
+impl FeedInner {
+    fn push(&self, path: &StorePath) {
+        let mut pending = self.pending.lock().or_poisoned();
+        if !pending.contains(path) {
+            pending.push(path.clone());
+        }
+        drop(pending);
+        if let Some(waker) = self.waker.lock().or_poisoned().take() {
+            waker.wake();
+        }
+    }
+}
+
+/// A [`Stream`] of batches of changes to the fields of a store.
+///
+/// Created with [`ArcStore::changefeed`] or [`Store::changefeed`]. Each batch contains the
+/// fields that have been notified since the previous batch was yielded, with their paths resolved
+/// to field names and keys, and their current values serialized as JSON. If both a field and one
+/// of its ancestors changed, only the ancestor is included.
+///
+/// Changes are recorded from when the changefeed is created. The stream ends once the store has
+/// been dropped.
+pub struct Changefeed<T> {
+    inner: Arc<FeedInner>,
+    value: Weak<RwLock<T>>,
+    keys: KeyMap,
+}
+
+impl<T> fmt::Debug for Changefeed<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Changefeed").finish_non_exhaustive()
+    }
+}
+
+impl<T> Changefeed<T>
+where
+    T: Serialize,
+{
+    /// Returns the changes that have been recorded since the last batch, without waiting for
+    /// any, or `None` if there are none.
+    pub fn try_next_batch(&self) -> Option<Vec<Change>> {
+        let paths =
+            std::mem::take(&mut *self.inner.pending.lock().or_poisoned());
+        if paths.is_empty() {
+            return None;
+        }
+        // a change to the keys of a map or set is a change to the map or set itself
+        let paths = paths.into_iter().fold(Vec::new(), |mut paths, path| {
+            let path = path
+                .into_iter()
+                .take_while(|segment| *segment != KEY_SET)
+                .collect::<StorePath>();
+            if !paths.contains(&path) {
+                paths.push(path);
+            }
+            paths
+        });
+        let value = self.value.upgrade()?;
+        let value = value.read().or_poisoned();
+        let changes = paths
+            .iter()
+            // a change to an ancestor includes this one
+            .filter(|path| !paths.iter().any(|other| is_ancestor(other, path)))
+            .filter_map(|path| resolve(&*value, path, &self.keys))
+            .collect::<Vec<_>>();
+        (!changes.is_empty()).then_some(changes)
+    }
+}
+
+impl<T> Stream for Changefeed<T>
+where
+    T: Serialize,
+{
+    type Item = Vec<Change>;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        if let Some(changes) = self.try_next_batch() {
+            return Poll::Ready(Some(changes));
+        }
+        if self.value.strong_count() == 0 {
+            return Poll::Ready(None);
+        }
+        *self.inner.waker.lock().or_poisoned() = Some(cx.waker().clone());
+        // a change may have been recorded before the waker was stored
+        match self.try_next_batch() {
+            Some(changes) => Poll::Ready(Some(changes)),
+            None => Poll::Pending,
+        }
+    }
+}
+
+impl<T> ArcStore<T>
+where
+    T: Serialize + 'static,
+{
+    /// Creates a [`Changefeed`] that yields batches of the fields of this store that change,
+    /// with their new values.
+    ///
+    /// This is useful for persisting a store or syncing it with a server, without comparing the
+    /// whole value to find what changed. Only changes that notify subscribers are recorded, so
+    /// untracked writes are not included.
+    ///
+    /// Field names come from the `Serialize` implementation of the value, so they follow any
+    /// `#[serde(rename)]` attributes. The fields of structs are matched up with store fields by
+    /// their position, so fields should not be skipped when serializing. A change inside an enum
+    /// or a map is reported as a change to the whole enum or map.
+    pub fn changefeed(&self) -> Changefeed<T> {
+        let feed = Arc::new(FeedInner::default());
+        self.changes.active.store(true, Ordering::Relaxed);
+        self.changes
+            .feeds
+            .lock()
+            .or_poisoned()
+            .push(Arc::downgrade(&feed));
+        // watch every field that has been accessed so far; later fields are watched as soon
+        // as their triggers are created
+        let triggers = self
+            .signals
+            .read()
+            .or_poisoned()
+            .0
+            .iter()
+            .map(|(path, trigger)| (path.clone(), trigger.clone()))
+            .collect::<Vec<_>>();
+        for (path, trigger) in triggers {
+            self.changes.watch(&path, &trigger);
+        }
+        Changefeed {
+            inner: feed,
+            value: Arc::downgrade(&self.value),
+            keys: self.keys.clone(),
+        }
+    }
+}
+
+impl<T, S> Store<T, S>
+where
+    T: Serialize + 'static,
+    S: Storage<ArcStore<T>>,
+{
+    /// Creates a [`Changefeed`] that yields batches of the fields of this store that change,
+    /// with their new values.
+    ///
+    /// See [`ArcStore::changefeed`] for details.
+    pub fn changefeed(&self) -> Changefeed<T> {
+        self.inner
+            .try_get_value()
+            .map(|store| store.changefeed())
+            .unwrap_or_else(|| Changefeed {
+                inner: Default::default(),
+                value: Weak::new(),
+                keys: Default::default(),
+            })
+    }
+}
+
+fn is_ancestor(ancestor: &StorePath, path: &StorePath) -> bool {
+    ancestor.len() < path.len()
+        && ancestor.into_iter().zip(path).all(|(a, b)| a == b)
+}
+
+/// One segment of a path, with the index of the item in the value it refers to.
+struct Step {
+    index: usize,
+    key: Option<String>,
+}
+
+impl Step {
+    fn segment(&self) -> ChangeSegment {
+        match &self.key {
+            Some(key) => ChangeSegment::Key {
+                key: key.clone(),
+                index: self.index,
+            },
+            None => ChangeSegment::Index(self.index),
+        }
+    }
+}
+
+/// Resolves the keyed segments of a path to indices, and finds the names and value of the field
+/// it refers to.
+fn resolve<T: Serialize>(
+    value: &T,
+    path: &StorePath,
+    keys: &KeyMap,
+) -> Option<Change> {
+    let mut steps = Vec::with_capacity(path.len());
+    let mut parent = StorePath::with_capacity(path.len());
+    for segment in path {
+        let StorePathSegment(segment) = *segment;
+        if keys.contains_key(&parent) {
+            // the item may have been removed since the change was recorded, in which case
+            // the keyed field itself has changed too
+            let (key, index) =
+                keys.describe_key(&parent, StorePathSegment(segment))?;
+            steps.push(Step {
+                index,
+                key: Some(key),
+            });
+        } else {
+            steps.push(Step {
+                index: segment,
+                key: None,
+            });
+        }
+        parent.push(segment);
+    }
+
+    let mut names = Vec::with_capacity(steps.len());
+    let value = navigate(value, &steps, &mut names).ok()?;
+    Some(Change { path: names, value })
+}
+
+fn navigate<T: Serialize + ?Sized>(
+    value: &T,
+    steps: &[Step],
+    names: &mut Vec<ChangeSegment>,
+) -> Result<Option<Value>, serde_json::Error> {
+    if steps.is_empty() {
+        serde_json::to_value(value).map(Some)
+    } else {
+        value.serialize(Navigator { steps, names })
+    }
+}
+
+/// A [`Serializer`] that only serializes the value at the end of a path, recording the names of
+/// the fields it passes through along the way.
+///
+/// `steps` is never empty.
+struct Navigator<'a> {
+    steps: &'a [Step],
+    names: &'a mut Vec<ChangeSegment>,
+}
+
+fn no_such_path() -> serde_json::Error {
+    ser::Error::custom("the path does not match the shape of the value")
+}
+
+macro_rules! missing {
+    ($($method:ident($($ty:ty),*)),* $(,)?) => {
+        $(fn $method(self, $(_: $ty),*) -> Result<Self::Ok, Self::Error> {
+            // a value with no fields has nothing at this path
+            Err(no_such_path())
+        })*
+    };
+}
+
+macro_rules! whole {
+    ($($method:ident($($arg:ident: $ty:ty),*)),* $(,)?) => {
+        $(fn $method(self, $($arg: $ty),*) -> Result<Self::Ok, Self::Error> {
+            // this value can't be navigated into, so it changes as a whole
+            ValueSerializer.$method($($arg),*).map(Some)
+        })*
+    };
+}
+
+impl<'a> Serializer for Navigator<'a> {
+    type Ok = Option<Value>;
+    type Error = serde_json::Error;
+    type SerializeSeq = NavigateSeq<'a>;
+    type SerializeTuple = NavigateSeq<'a>;
+    type SerializeTupleStruct = NavigateSeq<'a>;
+    type SerializeTupleVariant =
+        Whole<<ValueSerializer as Serializer>::SerializeTupleVariant>;
+    type SerializeMap = Whole<<ValueSerializer as Serializer>::SerializeMap>;
+    type SerializeStruct = NavigateStruct<'a>;
+    type SerializeStructVariant =
+        Whole<<ValueSerializer as Serializer>::SerializeStructVariant>;
+
+    missing! {
+        serialize_bool(bool),
+        serialize_i8(i8),
+        serialize_i16(i16),
+        serialize_i32(i32),
+        serialize_i64(i64),
+        serialize_i128(i128),
+        serialize_u8(u8),
+        serialize_u16(u16),
+        serialize_u32(u32),
+        serialize_u64(u64),
+        serialize_u128(u128),
+        serialize_f32(f32),
+        serialize_f64(f64),
+        serialize_char(char),
+        serialize_str(&str),
+        serialize_bytes(&[u8]),
+        serialize_unit(),
+        serialize_unit_struct(&'static str),
+    }
+
+    whole! {
+        serialize_none(),
+        serialize_unit_variant(name: &'static str, variant_index: u32, variant: &'static str),
+    }
+
+    fn serialize_some<U: Serialize + ?Sized>(
+        self,
+        value: &U,
+    ) -> Result<Self::Ok, Self::Error> {
+        // the inner value of an `Option` is its only field
+        navigate(value, &self.steps[1..], self.names)
+    }
+
+    fn serialize_newtype_struct<U: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &U,
+    ) -> Result<Self::Ok, Self::Error> {
+        navigate(value, &self.steps[1..], self.names)
+    }
+
+    fn serialize_newtype_variant<U: Serialize + ?Sized>(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        value: &U,
+    ) -> Result<Self::Ok, Self::Error> {
+        ValueSerializer
+            .serialize_newtype_variant(name, variant_index, variant, value)
+            .map(Some)
+    }
+
+    fn serialize_seq(
+        self,
+        _len: Option<usize>,
+    ) -> Result<Self::SerializeSeq, Self::Error> {
+        Ok(NavigateSeq::new(self))
+    }
+
+    fn serialize_tuple(
+        self,
+        _len: usize,
+    ) -> Result<Self::SerializeTuple, Self::Error> {
+        Ok(NavigateSeq::new(self))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        Ok(NavigateSeq::new(self))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        ValueSerializer
+            .serialize_tuple_variant(name, variant_index, variant, len)
+            .map(Whole)
+    }
+
+    fn serialize_map(
+        self,
+        len: Option<usize>,
+    ) -> Result<Self::SerializeMap, Self::Error> {
+        ValueSerializer.serialize_map(len).map(Whole)
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        Ok(NavigateStruct {
+            inner: NavigateSeq::new(self),
+        })
+    }
+
+    fn serialize_struct_variant(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        ValueSerializer
+            .serialize_struct_variant(name, variant_index, variant, len)
+            .map(Whole)
+    }
+}
+
+/// Navigates into the item of a sequence or tuple at the index of the next step.
+struct NavigateSeq<'a> {
+    steps: &'a [Step],
+    names: &'a mut Vec<ChangeSegment>,
+    index: usize,
+    found: Option<Option<Value>>,
+}
+
+impl<'a> NavigateSeq<'a> {
+    fn new(navigator: Navigator<'a>) -> Self {
+        Self {
+            steps: navigator.steps,
+            names: navigator.names,
+            index: 0,
+            found: None,
+        }
+    }
+
+    fn item<U: Serialize + ?Sized>(
+        &mut self,
+        name: impl FnOnce(&Step) -> ChangeSegment,
+        value: Option<&U>,
+    ) -> Result<(), serde_json::Error> {
+        if self.index == self.steps[0].index && self.found.is_none() {
+            self.names.push(name(&self.steps[0]));
+            if let Some(value) = value {
+                self.found =
+                    Some(navigate(value, &self.steps[1..], self.names)?);
+            }
+        }
+        self.index += 1;
+        Ok(())
+    }
+
+    fn end(self) -> Result<Option<Value>, serde_json::Error> {
+        if self.found.is_none() {
+            // there is no item at this index any more
+            self.names.push(self.steps[0].segment());
+        }
+        Ok(self.found.flatten())
+    }
+}
+
+impl ser::SerializeSeq for NavigateSeq<'_> {
+    type Ok = Option<Value>;
+    type Error = serde_json::Error;
+
+    fn serialize_element<U: Serialize + ?Sized>(
+        &mut self,
+        value: &U,
+    ) -> Result<(), Self::Error> {
+        self.item(Step::segment, Some(value))
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        NavigateSeq::end(self)
+    }
+}
+
+impl ser::SerializeTuple for NavigateSeq<'_> {
+    type Ok = Option<Value>;
+    type Error = serde_json::Error;
+
+    fn serialize_element<U: Serialize + ?Sized>(
+        &mut self,
+        value: &U,
+    ) -> Result<(), Self::Error> {
+        self.item(Step::segment, Some(value))
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        NavigateSeq::end(self)
+    }
+}
+
+impl ser::SerializeTupleStruct for NavigateSeq<'_> {
+    type Ok = Option<Value>;
+    type Error = serde_json::Error;
+
+    fn serialize_field<U: Serialize + ?Sized>(
+        &mut self,
+        value: &U,
+    ) -> Result<(), Self::Error> {
+        self.item(Step::segment, Some(value))
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        NavigateSeq::end(self)
+    }
+}
+
+/// Navigates into the field of a struct at the index of the next step.
+struct NavigateStruct<'a> {
+    inner: NavigateSeq<'a>,
+}
+
+impl ser::SerializeStruct for NavigateStruct<'_> {
+    type Ok = Option<Value>;
+    type Error = serde_json::Error;
+
+    fn serialize_field<U: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &U,
+    ) -> Result<(), Self::Error> {
+        self.inner.item(|_| ChangeSegment::Field(key), Some(value))
+    }
+
+    fn skip_field(&mut self, key: &'static str) -> Result<(), Self::Error> {
+        // skipped fields still have a position in the store
+        self.inner.item::<()>(|_| ChangeSegment::Field(key), None)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        if self.inner.index <= self.inner.steps[0].index {
+            return Err(no_such_path());
+        }
+        // a field that was skipped has been removed from the serialized value
+        Ok(self.inner.found.flatten())
+    }
+}
+
+/// Serializes a value that can't be navigated into as a whole.
+struct Whole<S>(S);
+
+impl<S> ser::SerializeTupleVariant for Whole<S>
+where
+    S: ser::SerializeTupleVariant<Ok = Value, Error = serde_json::Error>,
+{
+    type Ok = Option<Value>;
+    type Error = serde_json::Error;
+
+    fn serialize_field<U: Serialize + ?Sized>(
+        &mut self,
+        value: &U,
+    ) -> Result<(), Self::Error> {
+        self.0.serialize_field(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.0.end().map(Some)
+    }
+}
+
+impl<S> ser::SerializeMap for Whole<S>
+where
+    S: ser::SerializeMap<Ok = Value, Error = serde_json::Error>,
+{
+    type Ok = Option<Value>;
+    type Error = serde_json::Error;
+
+    fn serialize_key<U: Serialize + ?Sized>(
+        &mut self,
+        key: &U,
+    ) -> Result<(), Self::Error> {
+        self.0.serialize_key(key)
+    }
+
+    fn serialize_value<U: Serialize + ?Sized>(
+        &mut self,
+        value: &U,
+    ) -> Result<(), Self::Error> {
+        self.0.serialize_value(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.0.end().map(Some)
+    }
+}
+
+impl<S> ser::SerializeStructVariant for Whole<S>
+where
+    S: ser::SerializeStructVariant<Ok = Value, Error = serde_json::Error>,
+{
+    type Ok = Option<Value>;
+    type Error = serde_json::Error;
+
+    fn serialize_field<U: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &U,
+    ) -> Result<(), Self::Error> {
+        self.0.serialize_field(key, value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.0.end().map(Some)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Change, ChangeSegment};
+    use crate::{
+        self as reactive_stores, AtKeyed, Store, StoreFieldIterator,
+        StoreFieldMap, StoreFieldSet,
+    };
+    use futures::StreamExt;
+    use reactive_graph::traits::{
+        Dispose, GetUntracked, Set, Update, UpdateUntracked, Write,
+    };
+    use serde::Serialize;
+    use serde_json::json;
+    use std::collections::{BTreeMap, BTreeSet};
+
+    #[derive(Debug, Store, Serialize)]
+    struct Todos {
+        #[serde(rename = "userName")]
+        user: String,
+        #[store(key: usize = |todo| todo.id)]
+        todos: Vec<Todo>,
+        tags: Vec<String>,
+    }
+
+    #[derive(Debug, Store, Serialize)]
+    struct Todo {
+        id: usize,
+        label: String,
+        completed: bool,
+    }
+
+    fn data() -> Todos {
+        Todos {
+            user: "Bob".to_string(),
+            todos: vec![
+                Todo {
+                    id: 10,
+                    label: "Create reactive store".to_string(),
+                    completed: true,
+                },
+                Todo {
+                    id: 11,
+                    label: "???".to_string(),
+                    completed: false,
+                },
+            ],
+            tags: vec!["a".to_string(), "b".to_string()],
+        }
+    }
+
+    #[test]
+    fn changes_are_batched_with_names_and_values() {
+        let store = Store::new(data());
+        let feed = store.changefeed();
+        assert_eq!(feed.try_next_batch(), None);
+
+        store.user().set("Alice".to_string());
+        store.tags().at_unkeyed(1).set("c".to_string());
+        store.user().set("Carol".to_string());
+        assert_eq!(
+            feed.try_next_batch().unwrap(),
+            [
+                Change {
+                    path: vec![ChangeSegment::Field("userName")],
+                    value: Some(json!("Carol")),
+                },
+                // writing to an unkeyed item notifies the whole list
+                Change {
+                    path: vec![ChangeSegment::Field("tags")],
+                    value: Some(json!(["a", "c"])),
+                },
+            ]
+        );
+        assert_eq!(feed.try_next_batch(), None);
+
+        // untracked writes don't notify, so they aren't recorded
+        store.user().update_untracked(|user| user.push('!'));
+        assert_eq!(feed.try_next_batch(), None);
+    }
+
+    #[test]
+    fn keyed_items_are_resolved_to_their_keys() {
+        let store = Store::new(data());
+        let feed = store.changefeed();
+
+        let todo = AtKeyed::new(store.todos(), 11);
+        assert_eq!(todo.label().get_untracked(), "???");
+        todo.label().set("Profit".to_string());
+        let batch = feed.try_next_batch().unwrap();
+        assert_eq!(
+            batch[0].path,
+            [
+                ChangeSegment::Field("todos"),
+                ChangeSegment::Key {
+                    key: "11".to_string(),
+                    index: 1
+                },
+                ChangeSegment::Field("label"),
+            ]
+        );
+        assert_eq!(batch[0].pointer(), "/todos/1/label");
+        assert_eq!(batch[0].value, Some(json!("Profit")));
+    }
+
+    #[test]
+    fn changes_to_ancestors_include_their_children() {
+        let store = Store::new(data());
+        let feed = store.changefeed();
+
+        let todo = AtKeyed::new(store.todos(), 10);
+        assert!(todo.completed().get_untracked());
+        todo.completed().set(false);
+        store.todos().write().truncate(1);
+        let batch = feed.try_next_batch().unwrap();
+        assert_eq!(batch.len(), 1);
+        assert_eq!(batch[0].path, [ChangeSegment::Field("todos")]);
+        assert_eq!(
+            batch[0].value,
+            Some(json!([{
+                "id": 10,
+                "label": "Create reactive store",
+                "completed": false
+            }]))
+        );
+    }
+
+    #[tokio::test]
+    async fn changefeed_is_a_stream() {
+        let store = Store::new(data());
+        let mut feed = store.changefeed();
+
+        store.user().update(|user| user.push('!'));
+        let batch = feed.next().await.unwrap();
+        assert_eq!(
+            batch
+                .iter()
+                .map(|change| change.to_patch_operation())
+                .collect::<Vec<_>>(),
+            [crate::PatchOperation::Replace {
+                path: "/userName".to_string(),
+                value: json!("Bob!"),
+            }]
+        );
+
+        store.dispose();
+        assert_eq!(feed.next().await, None);
+    }
+
+    #[test]
+    fn changes_to_maps_and_sets_are_reported_as_the_whole_collection() {
+        #[derive(Debug, Store, Serialize)]
+        struct Directory {
+            users: BTreeMap<u32, String>,
+            admins: BTreeSet<u32>,
+        }
+
+        let store = Store::new(Directory {
+            users: BTreeMap::from([(1, "Alice".to_string())]),
+            admins: BTreeSet::new(),
+        });
+        let feed = store.changefeed();
+
+        store.users().at_key(1).set("Bob".to_string());
+        store.admins().insert(1);
+        assert_eq!(
+            feed.try_next_batch().unwrap(),
+            [
+                Change {
+                    path: vec![ChangeSegment::Field("users")],
+                    value: Some(json!({ "1": "Bob" })),
+                },
+                Change {
+                    path: vec![ChangeSegment::Field("admins")],
+                    value: Some(json!([1])),
+                },
+            ]
+        );
+    }
+}
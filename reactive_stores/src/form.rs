@@ -0,0 +1,943 @@
+use crate::{path::StorePath, ArcStore, StoreField};
+use or_poisoned::OrPoisoned;
+use reactive_graph::{
+    signal::{ArcReadSignal, ArcRwSignal},
+    traits::{
+        DefinedAt, Get, GetUntracked, IsDisposed, Notify, ReadUntracked, Set,
+        Track, UntrackableGuard, With, WithUntracked, Write,
+    },
+};
+use rustc_hash::FxHashMap;
+use std::{
+    fmt::{self, Debug, Display},
+    future::Future,
+    ops::{Deref, DerefMut},
+    panic::Location,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, RwLock,
+    },
+};
+
+type ValidatorFuture = Pin<Box<dyn Future<Output = Result<(), String>> + Send>>;
+type SyncValidator = Arc<dyn Fn() -> Result<(), String> + Send + Sync>;
+type AsyncValidator = Arc<dyn Fn() -> ValidatorFuture + Send + Sync>;
+
+/// Form state for a store: which of its fields have been changed or visited, and whether their
+/// current values are valid.
+///
+/// Fields are added to the form with [`StoreForm::field`], which returns a [`FormField`] that can
+/// be read and written like the store field itself (including with `bind:` in a view), but that
+/// also tracks the state of the field, and runs its validators whenever it is written.
+///
+/// The state of each field is identified by its [`StorePath`], so calling
+/// [`field`](StoreForm::field) for the same field again returns a handle to the same state.
+pub struct StoreForm<T> {
+    store: ArcStore<T>,
+    inner: Arc<FormInner>,
+}
+
+struct FormInner {
+    fields: RwLock<FxHashMap<StorePath, Arc<FieldState>>>,
+    // notified whenever a field is added, so that form-wide state includes it
+    fields_changed: ArcRwSignal<()>,
+    submitting: ArcRwSignal<bool>,
+    submit_error: ArcRwSignal<Option<String>>,
+}
+
+impl FormInner {
+    fn fields(&self) -> Vec<Arc<FieldState>> {
+        self.fields.read().or_poisoned().values().cloned().collect()
+    }
+}
+
+impl<T> Clone for StoreForm<T> {
+    fn clone(&self) -> Self {
+        Self {
+            store: self.store.clone(),
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+impl<T> Debug for StoreForm<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StoreForm")
+            .field("fields", &self.inner.fields().len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> StoreForm<T>
+where
+    T: 'static,
+{
+    /// Creates a form for a new store holding the given value.
+    pub fn new(value: T) -> Self {
+        Self::from_store(ArcStore::new(value))
+    }
+
+    /// Creates a form for an existing store.
+    pub fn from_store(store: ArcStore<T>) -> Self {
+        Self {
+            store,
+            inner: Arc::new(FormInner {
+                fields: Default::default(),
+                fields_changed: ArcRwSignal::new(()),
+                submitting: ArcRwSignal::new(false),
+                submit_error: ArcRwSignal::new(None),
+            }),
+        }
+    }
+
+    /// The store that holds the value of the form.
+    pub fn store(&self) -> ArcStore<T> {
+        self.store.clone()
+    }
+
+    /// Adds a field of the store to the form, or returns the existing state for it.
+    #[track_caller]
+    pub fn field<F>(&self, field: F) -> FormField<F>
+    where
+        F: StoreField,
+    {
+        let path = field.path().into_iter().collect::<StorePath>();
+        let mut fields = self.inner.fields.write().or_poisoned();
+        let state = match fields.get(&path) {
+            Some(state) => Arc::clone(state),
+            None => {
+                let state = Arc::new(FieldState::new(path.clone()));
+                fields.insert(path, Arc::clone(&state));
+                drop(fields);
+                self.inner.fields_changed.set(());
+                state
+            }
+        };
+        FormField {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: Location::caller(),
+            field,
+            state,
+        }
+    }
+
+    fn fields(&self) -> Vec<Arc<FieldState>> {
+        self.inner.fields()
+    }
+
+    fn tracked_fields(&self) -> Vec<Arc<FieldState>> {
+        self.inner.fields_changed.track();
+        self.fields()
+    }
+
+    /// Reactively checks whether any field has been changed through the form.
+    pub fn is_dirty(&self) -> bool {
+        self.tracked_fields().iter().any(|field| field.dirty.get())
+    }
+
+    /// Reactively checks whether every field is currently valid, and none is still being
+    /// validated.
+    ///
+    /// Fields that have not been validated yet count as valid.
+    pub fn is_valid(&self) -> bool {
+        self.tracked_fields().iter().all(|field| {
+            field.error.with(Option::is_none) && !field.validating.get()
+        })
+    }
+
+    /// Reactively returns the current error for each field that has one, by path.
+    pub fn errors(&self) -> Vec<(StorePath, String)> {
+        self.tracked_fields()
+            .iter()
+            .filter_map(|field| {
+                field.error.get().map(|error| (field.path.clone(), error))
+            })
+            .collect()
+    }
+
+    /// Reactively checks whether the form is being submitted.
+    pub fn is_submitting(&self) -> bool {
+        self.inner.submitting.get()
+    }
+
+    /// Sets whether the form is being submitted.
+    ///
+    /// This is set by [`submit`](StoreForm::submit), and can also be set when the form is
+    /// submitted some other way, such as with a server action.
+    pub fn set_submitting(&self, submitting: bool) {
+        self.inner.submitting.set(submitting);
+    }
+
+    /// The error returned by the last submission of the form, if any.
+    pub fn submit_error(&self) -> ArcReadSignal<Option<String>> {
+        self.inner.submit_error.read_only()
+    }
+
+    /// Sets the error for the form as a whole, as opposed to any one field.
+    pub fn set_submit_error(&self, error: Option<String>) {
+        self.inner.submit_error.set(error);
+    }
+
+    /// Marks every field as touched, and runs their synchronous validators, returning `true`
+    /// if every validator of every field has passed for its current value.
+    ///
+    /// The result of the asynchronous validators is kept if they have already finished for the
+    /// current value of a field; otherwise they are started in the background, and the field
+    /// counts as invalid until they finish. Use [`validate_async`](StoreForm::validate_async) to
+    /// wait for them instead.
+    pub fn validate(&self) -> bool {
+        let mut valid = true;
+        for field in self.fields() {
+            field.touched.set(true);
+            valid &= field.check();
+        }
+        valid
+    }
+
+    /// Marks every field as touched, and runs all of their validators, returning `true` if none
+    /// of them failed.
+    pub async fn validate_async(&self) -> bool {
+        let mut valid = true;
+        for field in self.fields() {
+            field.touched.set(true);
+            valid &= field.validate_all().await;
+        }
+        valid
+    }
+
+    /// Validates every field, and if they are all valid, calls `on_submit` with the store.
+    ///
+    /// The form is marked as submitting until the future returned by `on_submit` resolves. If it
+    /// resolves to an error, that becomes the [`submit_error`](StoreForm::submit_error) of the
+    /// form. Returns `true` if the form was submitted successfully.
+    pub async fn submit<Fut, E>(
+        &self,
+        on_submit: impl FnOnce(ArcStore<T>) -> Fut,
+    ) -> bool
+    where
+        Fut: Future<Output = Result<(), E>>,
+        E: Display,
+    {
+        if !self.validate_async().await {
+            return false;
+        }
+
+        self.set_submitting(true);
+        self.set_submit_error(None);
+        let result = on_submit(self.store()).await;
+        self.set_submitting(false);
+
+        match result {
+            Ok(()) => true,
+            Err(error) => {
+                self.set_submit_error(Some(error.to_string()));
+                false
+            }
+        }
+    }
+
+    /// Sets the form to a new value, and clears the state of every field and the submit error.
+    pub fn reset(&self, value: T) {
+        self.store.set(value);
+        for field in self.fields() {
+            field.reset();
+        }
+        self.set_submit_error(None);
+    }
+}
+
+/// The state of one field of a [`StoreForm`].
+struct FieldState {
+    path: StorePath,
+    dirty: ArcRwSignal<bool>,
+    touched: ArcRwSignal<bool>,
+    error: ArcRwSignal<Option<String>>,
+    validating: ArcRwSignal<bool>,
+    validators: RwLock<Vec<SyncValidator>>,
+    async_validators: RwLock<Vec<AsyncValidator>>,
+    // incremented each time the field is validated, so that the result of an async validator
+    // is ignored if the field has changed again since it started
+    generation: AtomicUsize,
+    // whether the async validators have finished for the current generation
+    async_done: AtomicBool,
+}
+
+impl FieldState {
+    fn new(path: StorePath) -> Self {
+        Self {
+            path,
+            dirty: ArcRwSignal::new(false),
+            touched: ArcRwSignal::new(false),
+            error: ArcRwSignal::new(None),
+            validating: ArcRwSignal::new(false),
+            validators: Default::default(),
+            async_validators: Default::default(),
+            generation: AtomicUsize::new(0),
+            async_done: AtomicBool::new(false),
+        }
+    }
+
+    fn validate_sync(&self) -> Result<(), String> {
+        let validators = self.validators.read().or_poisoned().clone();
+        validators.iter().try_for_each(|validator| validator())
+    }
+
+    /// Runs the synchronous validators, and starts the asynchronous ones if those pass.
+    fn validate(self: &Arc<Self>) -> bool {
+        let generation = self.generation.fetch_add(1, Ordering::Relaxed) + 1;
+        self.async_done.store(false, Ordering::Relaxed);
+        if let Err(error) = self.validate_sync() {
+            self.error.set(Some(error));
+            self.validating.set(false);
+            return false;
+        }
+
+        let validators = self.async_validators.read().or_poisoned().clone();
+        self.error.set(None);
+        if validators.is_empty() {
+            self.validating.set(false);
+            self.async_done.store(true, Ordering::Relaxed);
+        } else {
+            self.validating.set(true);
+            let futures =
+                validators.iter().map(|validator| validator()).collect();
+            let this = Arc::clone(self);
+            reactive_graph::spawn(async move {
+                let result = run_async_validators(futures).await;
+                if this.generation.load(Ordering::Relaxed) == generation {
+                    this.async_done.store(true, Ordering::Relaxed);
+                    this.error.set(result.err());
+                    this.validating.set(false);
+                }
+            });
+        }
+        true
+    }
+
+    /// Runs the synchronous validators, and starts the asynchronous ones unless they are
+    /// already running or have finished for the current value.
+    ///
+    /// Returns `true` only if every validator has passed for the current value.
+    fn check(self: &Arc<Self>) -> bool {
+        let validating = self.validating.get_untracked();
+        if self.validate_sync().is_ok()
+            && (validating || self.async_done.load(Ordering::Relaxed))
+        {
+            // keep the result of the async validators rather than clearing it
+            return !validating && self.error.with_untracked(Option::is_none);
+        }
+        self.validate() && self.async_validators.read().or_poisoned().is_empty()
+    }
+
+    /// Runs all the validators, and waits for the asynchronous ones to finish.
+    async fn validate_all(self: &Arc<Self>) -> bool {
+        let generation = self.generation.fetch_add(1, Ordering::Relaxed) + 1;
+        self.async_done.store(false, Ordering::Relaxed);
+        let result = match self.validate_sync() {
+            Err(error) => Err(error),
+            Ok(()) => {
+                let validators =
+                    self.async_validators.read().or_poisoned().clone();
+                self.validating.set(!validators.is_empty());
+                run_async_validators(
+                    validators.iter().map(|validator| validator()).collect(),
+                )
+                .await
+            }
+        };
+
+        if self.generation.load(Ordering::Relaxed) == generation {
+            self.async_done.store(true, Ordering::Relaxed);
+            let valid = result.is_ok();
+            self.error.set(result.err());
+            self.validating.set(false);
+            valid
+        } else {
+            // the field changed while it was being validated, so this result is out of date
+            false
+        }
+    }
+
+    /// Called after the value of the field has been changed through the form.
+    fn changed(self: &Arc<Self>) {
+        self.dirty.set(true);
+        self.validate();
+    }
+
+    fn reset(&self) {
+        self.generation.fetch_add(1, Ordering::Relaxed);
+        self.async_done.store(false, Ordering::Relaxed);
+        self.dirty.set(false);
+        self.touched.set(false);
+        self.error.set(None);
+        self.validating.set(false);
+    }
+}
+
+async fn run_async_validators(
+    futures: Vec<ValidatorFuture>,
+) -> Result<(), String> {
+    for future in futures {
+        future.await?;
+    }
+    Ok(())
+}
+
+/// A field of a [`StoreForm`].
+///
+/// This can be read and written in the same way as the store field it wraps. Writing to it marks
+/// the field as dirty and runs its validators.
+pub struct FormField<F> {
+    #[cfg(any(debug_assertions, leptos_debuginfo))]
+    defined_at: &'static Location<'static>,
+    field: F,
+    state: Arc<FieldState>,
+}
+
+impl<F> Clone for FormField<F>
+where
+    F: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: self.defined_at,
+            field: self.field.clone(),
+            state: Arc::clone(&self.state),
+        }
+    }
+}
+
+impl<F> Debug for FormField<F>
+where
+    F: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FormField")
+            .field("field", &self.field)
+            .field("path", &self.state.path)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<F> FormField<F>
+where
+    F: StoreField,
+{
+    /// Adds a validator, which checks the value of the field whenever it changes.
+    ///
+    /// Validators run in the order they were added, and the first error is shown.
+    pub fn validate(
+        self,
+        validator: impl Fn(&F::Value) -> Result<(), String> + Send + Sync + 'static,
+    ) -> Self
+    where
+        F: Clone + Send + Sync + 'static,
+    {
+        let field = self.field.clone();
+        self.state
+            .validators
+            .write()
+            .or_poisoned()
+            .push(Arc::new(move || match field.reader() {
+                Some(value) => validator(&value),
+                None => Ok(()),
+            }));
+        self
+    }
+
+    /// Adds an asynchronous validator, which checks the value of the field whenever it changes,
+    /// if all the synchronous validators pass.
+    ///
+    /// While it is running, the field is [validating](FormField::is_validating). If the field
+    /// changes again before it finishes, its result is ignored.
+    pub fn validate_async<Fut>(
+        self,
+        validator: impl Fn(F::Value) -> Fut + Send + Sync + 'static,
+    ) -> Self
+    where
+        F: Clone + Send + Sync + 'static,
+        F::Value: Clone,
+        Fut: Future<Output = Result<(), String>> + Send + 'static,
+    {
+        let field = self.field.clone();
+        let validator = Arc::new(validator);
+        self.state
+            .async_validators
+            .write()
+            .or_poisoned()
+            .push(Arc::new(move || match field.reader() {
+                Some(value) => Box::pin(validator(value.clone())),
+                None => Box::pin(async { Ok(()) }),
+            }));
+        self
+    }
+
+    /// The store field this wraps.
+    pub fn inner(&self) -> &F {
+        &self.field
+    }
+
+    /// The path of the field within the store.
+    pub fn path(&self) -> &StorePath {
+        &self.state.path
+    }
+
+    /// Reactively checks whether the field has been changed through the form.
+    pub fn is_dirty(&self) -> bool {
+        self.state.dirty.get()
+    }
+
+    /// Reactively checks whether the field has been [touched](FormField::touch).
+    pub fn is_touched(&self) -> bool {
+        self.state.touched.get()
+    }
+
+    /// Reactively checks whether an asynchronous validator is running for this field.
+    pub fn is_validating(&self) -> bool {
+        self.state.validating.get()
+    }
+
+    /// The current validation error for this field, if any.
+    pub fn error(&self) -> ArcReadSignal<Option<String>> {
+        self.state.error.read_only()
+    }
+
+    /// Sets the error for this field, for example from an error returned by the server.
+    ///
+    /// This is replaced the next time the field is validated.
+    pub fn set_error(&self, error: Option<String>) {
+        self.state.error.set(error);
+    }
+
+    /// Marks the field as touched, and validates it.
+    ///
+    /// This is usually called when an input loses focus, so that errors are only shown for
+    /// fields the user has visited.
+    pub fn touch(&self) {
+        if !self.state.touched.get_untracked() {
+            self.state.touched.set(true);
+            self.state.validate();
+        }
+    }
+}
+
+impl<F> DefinedAt for FormField<F> {
+    fn defined_at(&self) -> Option<&'static Location<'static>> {
+        #[cfg(any(debug_assertions, leptos_debuginfo))]
+        {
+            Some(self.defined_at)
+        }
+        #[cfg(not(any(debug_assertions, leptos_debuginfo)))]
+        {
+            None
+        }
+    }
+}
+
+impl<F> IsDisposed for FormField<F>
+where
+    F: IsDisposed,
+{
+    fn is_disposed(&self) -> bool {
+        self.field.is_disposed()
+    }
+}
+
+impl<F> Notify for FormField<F>
+where
+    F: Notify,
+{
+    fn notify(&self) {
+        self.field.notify();
+    }
+}
+
+impl<F> Track for FormField<F>
+where
+    F: Track,
+{
+    fn track(&self) {
+        self.field.track();
+    }
+}
+
+impl<F> ReadUntracked for FormField<F>
+where
+    F: StoreField,
+{
+    type Value = F::Reader;
+
+    fn try_read_untracked(&self) -> Option<Self::Value> {
+        self.field.reader()
+    }
+}
+
+impl<F> Write for FormField<F>
+where
+    F: StoreField + Notify,
+    F::Value: 'static,
+{
+    type Value = F::Value;
+
+    fn try_write(&self) -> Option<impl UntrackableGuard<Target = Self::Value>> {
+        let guard = self.field.writer()?;
+        Some(FormFieldWriteGuard {
+            state: Arc::clone(&self.state),
+            guard: Some(guard),
+            untracked: false,
+        })
+    }
+
+    fn try_write_untracked(
+        &self,
+    ) -> Option<impl DerefMut<Target = Self::Value>> {
+        let mut guard = self.field.writer()?;
+        guard.untrack();
+        Some(FormFieldWriteGuard {
+            state: Arc::clone(&self.state),
+            guard: Some(guard),
+            untracked: true,
+        })
+    }
+}
+
+/// Gives write access to a [`FormField`], and validates it when dropped.
+struct FormFieldWriteGuard<Guard> {
+    state: Arc<FieldState>,
+    guard: Option<Guard>,
+    untracked: bool,
+}
+
+impl<Guard> Deref for FormFieldWriteGuard<Guard>
+where
+    Guard: Deref,
+{
+    type Target = Guard::Target;
+
+    fn deref(&self) -> &Self::Target {
+        self.guard
+            .as_ref()
+            .expect("should be Some(_) until dropped")
+            .deref()
+    }
+}
+
+impl<Guard> DerefMut for FormFieldWriteGuard<Guard>
+where
+    Guard: DerefMut,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.guard
+            .as_mut()
+            .expect("should be Some(_) until dropped")
+            .deref_mut()
+    }
+}
+
+impl<Guard> UntrackableGuard for FormFieldWriteGuard<Guard>
+where
+    Guard: UntrackableGuard,
+{
+    fn untrack(&mut self) {
+        self.untracked = true;
+        if let Some(inner) = self.guard.as_mut() {
+            inner.untrack();
+        }
+    }
+}
+
+impl<Guard> Drop for FormFieldWriteGuard<Guard> {
+    fn drop(&mut self) {
+        // the validators read the new value, so the write lock needs to be released first
+        drop(self.guard.take());
+
+        if !self.untracked {
+            self.state.changed();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{self as reactive_stores, tests::tick, Store, StoreForm};
+    use reactive_graph::{
+        effect::Effect,
+        traits::{GetUntracked, Set, Update},
+    };
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    #[derive(Debug, Store, Default, Clone)]
+    struct Signup {
+        name: String,
+        email: String,
+    }
+
+    fn not_empty(value: &str) -> Result<(), String> {
+        if value.is_empty() {
+            Err("required".to_string())
+        } else {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn writing_a_field_marks_it_dirty_and_validates_it() {
+        _ = any_spawner::Executor::init_tokio();
+
+        let form = StoreForm::new(Signup::default());
+        let name = form
+            .field(form.store().name())
+            .validate(|value| not_empty(value));
+        let email = form.field(form.store().email());
+
+        assert!(!name.is_dirty());
+        assert_eq!(name.error().get_untracked(), None);
+
+        name.set(String::new());
+        assert!(name.is_dirty());
+        assert!(!email.is_dirty());
+        assert_eq!(name.error().get_untracked(), Some("required".to_string()));
+        assert!(!form.is_valid());
+
+        name.update(|name| name.push_str("Alice"));
+        assert_eq!(form.store().name().get_untracked(), "Alice");
+        assert_eq!(name.get_untracked(), "Alice");
+        assert_eq!(name.error().get_untracked(), None);
+        assert!(form.is_valid());
+
+        // writing to the store directly does not go through the form
+        form.store().email().set("alice@example.com".to_string());
+        assert!(!email.is_dirty());
+    }
+
+    #[tokio::test]
+    async fn fields_share_state_by_path() {
+        _ = any_spawner::Executor::init_tokio();
+
+        let form = StoreForm::new(Signup::default());
+        let name = form
+            .field(form.store().name())
+            .validate(|value| not_empty(value));
+        let same_name = form.field(form.store().name());
+
+        assert_eq!(name.path(), same_name.path());
+        same_name.set(String::new());
+        assert!(name.is_dirty());
+        assert_eq!(
+            form.errors(),
+            vec![(name.path().clone(), "required".to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn validate_touches_every_field() {
+        _ = any_spawner::Executor::init_tokio();
+
+        let form = StoreForm::new(Signup::default());
+        let name = form
+            .field(form.store().name())
+            .validate(|value| not_empty(value));
+        let email = form
+            .field(form.store().email())
+            .validate(|value| not_empty(value));
+
+        assert!(!name.is_touched());
+        assert!(!form.validate());
+        assert!(name.is_touched());
+        assert!(email.is_touched());
+        assert_eq!(form.errors().len(), 2);
+
+        form.reset(Signup {
+            name: "Alice".to_string(),
+            email: "alice@example.com".to_string(),
+        });
+        assert!(!name.is_touched());
+        assert!(form.errors().is_empty());
+        assert!(form.validate());
+    }
+
+    #[tokio::test]
+    async fn async_validators_run_after_sync_validators() {
+        _ = any_spawner::Executor::init_tokio();
+
+        let runs = Arc::new(AtomicUsize::new(0));
+        let form = StoreForm::new(Signup::default());
+        let name = form
+            .field(form.store().name())
+            .validate(|value| not_empty(value))
+            .validate_async({
+                let runs = Arc::clone(&runs);
+                move |name| {
+                    runs.fetch_add(1, Ordering::Relaxed);
+                    async move {
+                        tick().await;
+                        if name == "taken" {
+                            Err("already taken".to_string())
+                        } else {
+                            Ok(())
+                        }
+                    }
+                }
+            });
+
+        // the async validator does not run if a sync validator fails
+        name.set(String::new());
+        tick().await;
+        assert_eq!(runs.load(Ordering::Relaxed), 0);
+        assert_eq!(name.error().get_untracked(), Some("required".to_string()));
+
+        name.set("taken".to_string());
+        assert!(name.is_validating());
+        assert_eq!(name.error().get_untracked(), None);
+        assert!(!form.is_valid());
+        tick().await;
+        tick().await;
+        assert!(!name.is_validating());
+        assert_eq!(
+            name.error().get_untracked(),
+            Some("already taken".to_string())
+        );
+
+        // only the result for the latest value is kept
+        name.set("taken".to_string());
+        name.set("free".to_string());
+        tick().await;
+        tick().await;
+        assert_eq!(name.error().get_untracked(), None);
+        assert_eq!(runs.load(Ordering::Relaxed), 3);
+
+        name.set("taken".to_string());
+        assert!(!form.validate_async().await);
+        assert_eq!(
+            name.error().get_untracked(),
+            Some("already taken".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn validate_keeps_the_result_of_async_validators() {
+        _ = any_spawner::Executor::init_tokio();
+
+        let runs = Arc::new(AtomicUsize::new(0));
+        let form = StoreForm::new(Signup::default());
+        let name = form.field(form.store().name()).validate_async({
+            let runs = Arc::clone(&runs);
+            move |name| {
+                runs.fetch_add(1, Ordering::Relaxed);
+                async move {
+                    tick().await;
+                    if name == "taken" {
+                        Err("already taken".to_string())
+                    } else {
+                        Ok(())
+                    }
+                }
+            }
+        });
+
+        // the async validator has not run for the current value yet
+        assert!(!form.validate());
+        assert!(name.is_validating());
+        assert!(!form.validate());
+        tick().await;
+        tick().await;
+        assert!(form.validate());
+        assert_eq!(runs.load(Ordering::Relaxed), 1);
+
+        name.set("taken".to_string());
+        assert!(!form.validate());
+        tick().await;
+        tick().await;
+        assert_eq!(
+            name.error().get_untracked(),
+            Some("already taken".to_string())
+        );
+
+        // a failed async validator still fails, without running again
+        assert!(!form.validate());
+        assert_eq!(
+            name.error().get_untracked(),
+            Some("already taken".to_string())
+        );
+        assert_eq!(runs.load(Ordering::Relaxed), 2);
+    }
+
+    #[tokio::test]
+    async fn submit_only_runs_when_valid() {
+        _ = any_spawner::Executor::init_tokio();
+
+        let form = StoreForm::new(Signup::default());
+        let name = form
+            .field(form.store().name())
+            .validate(|value| not_empty(value));
+
+        let submitted = form
+            .submit(|_| async { unreachable!() as Result<(), String> })
+            .await;
+        assert!(!submitted);
+
+        name.set("Alice".to_string());
+        let submitted = form
+            .submit(|store| async move {
+                if store.name().get_untracked() == "Alice" {
+                    Err("server error")
+                } else {
+                    Ok(())
+                }
+            })
+            .await;
+        assert!(!submitted);
+        assert!(!form.is_submitting());
+        assert_eq!(
+            form.submit_error().get_untracked(),
+            Some("server error".to_string())
+        );
+
+        name.set("Bob".to_string());
+        let submitted = form.submit(|_| async { Ok::<_, String>(()) }).await;
+        assert!(submitted);
+        assert_eq!(form.submit_error().get_untracked(), None);
+    }
+
+    #[tokio::test]
+    async fn errors_are_reactive() {
+        _ = any_spawner::Executor::init_tokio();
+
+        let form = StoreForm::new(Signup::default());
+        let name = form
+            .field(form.store().name())
+            .validate(|value| not_empty(value));
+        let runs = Arc::new(AtomicUsize::new(0));
+
+        Effect::new_sync({
+            let form = form.clone();
+            let runs = Arc::clone(&runs);
+            move |_| {
+                form.is_valid();
+                runs.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+        tick().await;
+        assert_eq!(runs.load(Ordering::Relaxed), 1);
+
+        name.set(String::new());
+        tick().await;
+        assert_eq!(runs.load(Ordering::Relaxed), 2);
+
+        // adding a field notifies anything that depends on the whole form
+        _ = form.field(form.store().email());
+        tick().await;
+        assert_eq!(runs.load(Ordering::Relaxed), 3);
+
+        name.touch();
+        name.set("Alice".to_string());
+        tick().await;
+        assert!(form.is_valid());
+        assert!(runs.load(Ordering::Relaxed) > 3);
+    }
+}
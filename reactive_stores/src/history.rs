@@ -0,0 +1,411 @@
+use crate::{
+    ApplyJsonPatch, JsonPatch, JsonPatchError, PatchField, StoreField,
+};
+use or_poisoned::OrPoisoned;
+use reactive_graph::{
+    effect::Effect,
+    owner::SyncStorage,
+    signal::ArcTrigger,
+    traits::{Dispose, Notify, Track},
+};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use std::{
+    collections::VecDeque,
+    fmt::{self, Debug},
+    sync::{Arc, Mutex, Weak},
+};
+
+/// Undo and redo for a store or field.
+///
+/// Rather than keeping a copy of the value for each change, the history keeps a pair of
+/// [`JsonPatch`]es for each one: one that undoes the change, and one that redoes it. Undoing or
+/// redoing applies the patch with [`ApplyJsonPatch::apply_json_patch`], so only the fields that
+/// actually change are notified.
+///
+/// Changes are recorded automatically by an effect, so all the changes made before the next
+/// “tick” become a single step in the history. Use [`record`](History::record) to end a step
+/// immediately.
+pub struct History<F> {
+    field: F,
+    inner: Arc<HistoryInner>,
+}
+
+struct HistoryInner {
+    state: Mutex<HistoryState>,
+    // notified whenever the undo or redo stacks change
+    changed: ArcTrigger,
+    effect: Mutex<Option<Effect<SyncStorage>>>,
+}
+
+struct HistoryState {
+    // the serialized value at the end of the last recorded step
+    current: Value,
+    undo: VecDeque<HistoryEntry>,
+    redo: Vec<HistoryEntry>,
+    limit: Option<usize>,
+}
+
+struct HistoryEntry {
+    undo: JsonPatch,
+    redo: JsonPatch,
+}
+
+impl Drop for HistoryInner {
+    fn drop(&mut self) {
+        if let Some(effect) = self.effect.lock().or_poisoned().take() {
+            effect.dispose();
+        }
+    }
+}
+
+impl<F> Clone for History<F>
+where
+    F: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            field: self.field.clone(),
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+impl<F> Debug for History<F>
+where
+    F: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.inner.state.lock().or_poisoned();
+        f.debug_struct("History")
+            .field("field", &self.field)
+            .field("undo", &state.undo.len())
+            .field("redo", &state.redo.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<F> History<F>
+where
+    F: StoreField + Track + Clone + Send + Sync + 'static,
+    F::Value: PatchField + Serialize + DeserializeOwned,
+{
+    /// Starts recording the history of a store or field, from its current value.
+    pub fn new(field: F) -> Result<Self, JsonPatchError> {
+        let current = Self::serialize(&field)?;
+        let inner = Arc::new(HistoryInner {
+            state: Mutex::new(HistoryState {
+                current,
+                undo: VecDeque::new(),
+                redo: Vec::new(),
+                limit: None,
+            }),
+            changed: ArcTrigger::new(),
+            effect: Mutex::new(None),
+        });
+
+        let effect = Effect::new_sync({
+            let field = field.clone();
+            let inner = Arc::downgrade(&inner);
+            move |_| {
+                field.track();
+                if let Some(inner) = Weak::upgrade(&inner) {
+                    // there is nowhere to report a failure from here; it will be returned by the
+                    // next call to `record`, `undo`, or `redo` instead
+                    _ = Self::record_inner(&field, &inner);
+                }
+            }
+        });
+        *inner.effect.lock().or_poisoned() = Some(effect);
+
+        Ok(Self { field, inner })
+    }
+
+    /// Sets the maximum number of steps that can be undone. Older steps are forgotten.
+    pub fn with_limit(self, limit: usize) -> Self {
+        {
+            let mut state = self.inner.state.lock().or_poisoned();
+            state.limit = Some(limit);
+            state.truncate();
+        }
+        self.inner.changed.notify();
+        self
+    }
+
+    fn serialize(field: &F) -> Result<Value, JsonPatchError> {
+        match field.reader() {
+            Some(value) => Ok(serde_json::to_value(&*value)?),
+            None => Ok(Value::Null),
+        }
+    }
+
+    fn record_inner(
+        field: &F,
+        inner: &HistoryInner,
+    ) -> Result<bool, JsonPatchError> {
+        let new = Self::serialize(field)?;
+        let mut state = inner.state.lock().or_poisoned();
+        let redo = JsonPatch::diff_json(&state.current, &new);
+        if redo.is_empty() {
+            return Ok(false);
+        }
+        let undo = JsonPatch::diff_json(&new, &state.current);
+        state.current = new;
+        state.undo.push_back(HistoryEntry { undo, redo });
+        state.redo.clear();
+        state.truncate();
+        drop(state);
+        inner.changed.notify();
+        Ok(true)
+    }
+
+    /// Records any changes since the last step as a new step, returning `true` if there were
+    /// any.
+    ///
+    /// Recording a new step clears the steps that could be redone.
+    pub fn record(&self) -> Result<bool, JsonPatchError> {
+        Self::record_inner(&self.field, &self.inner)
+    }
+
+    /// Undoes the last step, returning `false` if there was nothing to undo.
+    ///
+    /// Any changes that have not been recorded yet are recorded first, and then undone.
+    pub fn undo(&self) -> Result<bool, JsonPatchError> {
+        self.record()?;
+        self.apply(
+            |state| state.undo.pop_back(),
+            |entry| &entry.undo,
+            |state, entry| state.redo.push(entry),
+            |state, entry| state.undo.push_back(entry),
+        )
+    }
+
+    /// Redoes the last step that was undone, returning `false` if there was nothing to redo.
+    ///
+    /// If there are changes that have not been recorded yet, they are recorded as a new step,
+    /// and there is nothing left to redo.
+    pub fn redo(&self) -> Result<bool, JsonPatchError> {
+        self.record()?;
+        self.apply(
+            |state| state.redo.pop(),
+            |entry| &entry.redo,
+            |state, entry| state.undo.push_back(entry),
+            |state, entry| state.redo.push(entry),
+        )
+    }
+
+    fn apply(
+        &self,
+        take: impl FnOnce(&mut HistoryState) -> Option<HistoryEntry>,
+        patch: impl Fn(&HistoryEntry) -> &JsonPatch,
+        done: impl FnOnce(&mut HistoryState, HistoryEntry),
+        failed: impl FnOnce(&mut HistoryState, HistoryEntry),
+    ) -> Result<bool, JsonPatchError> {
+        let Some(entry) = take(&mut self.inner.state.lock().or_poisoned())
+        else {
+            return Ok(false);
+        };
+
+        // the lock is not held while patching, so that anything notified can read the history
+        let result = self.field.apply_json_patch(patch(&entry));
+
+        let mut state = self.inner.state.lock().or_poisoned();
+        let result =
+            result.and_then(|_| patch(&entry).apply(&mut state.current));
+        match result {
+            Ok(()) => {
+                done(&mut state, entry);
+                state.truncate();
+            }
+            Err(_) => failed(&mut state, entry),
+        }
+        drop(state);
+        self.inner.changed.notify();
+        result.map(|_| true)
+    }
+}
+
+impl<F> History<F> {
+    /// Reactively checks whether there is a step that can be undone.
+    ///
+    /// Changes that have not been recorded yet are not included.
+    pub fn can_undo(&self) -> bool {
+        self.inner.changed.track();
+        !self.inner.state.lock().or_poisoned().undo.is_empty()
+    }
+
+    /// Reactively checks whether there is a step that can be redone.
+    pub fn can_redo(&self) -> bool {
+        self.inner.changed.track();
+        !self.inner.state.lock().or_poisoned().redo.is_empty()
+    }
+
+    /// Forgets all the steps that can be undone or redone.
+    pub fn clear(&self) {
+        {
+            let mut state = self.inner.state.lock().or_poisoned();
+            state.undo.clear();
+            state.redo.clear();
+        }
+        self.inner.changed.notify();
+    }
+
+    /// The store or field this is the history of.
+    pub fn field(&self) -> &F {
+        &self.field
+    }
+}
+
+impl HistoryState {
+    fn truncate(&mut self) {
+        if let Some(limit) = self.limit {
+            while self.undo.len() > limit {
+                self.undo.pop_front();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::History;
+    use crate::{self as reactive_stores, tests::tick, Patch, Store};
+    use reactive_graph::{
+        effect::Effect,
+        traits::{GetUntracked, Read, Set, Update},
+    };
+    use serde::{Deserialize, Serialize};
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    #[derive(
+        Debug, Store, Patch, Clone, Default, PartialEq, Serialize, Deserialize,
+    )]
+    struct Document {
+        title: String,
+        paragraphs: Vec<String>,
+    }
+
+    #[tokio::test]
+    async fn changes_can_be_undone_and_redone() {
+        _ = any_spawner::Executor::init_tokio();
+
+        let store = Store::new(Document::default());
+        let history = History::new(store).unwrap();
+        assert!(!history.can_undo());
+
+        store.title().set("Draft".to_string());
+        assert!(history.record().unwrap());
+        store
+            .paragraphs()
+            .update(|paragraphs| paragraphs.push("Hello".to_string()));
+        store.title().set("Greeting".to_string());
+        assert!(history.record().unwrap());
+        assert!(!history.record().unwrap());
+        assert!(history.can_undo());
+
+        assert!(history.undo().unwrap());
+        assert_eq!(
+            store.get_untracked(),
+            Document {
+                title: "Draft".to_string(),
+                paragraphs: vec![],
+            }
+        );
+        assert!(history.can_redo());
+
+        assert!(history.undo().unwrap());
+        assert_eq!(store.get_untracked(), Document::default());
+        assert!(!history.undo().unwrap());
+
+        assert!(history.redo().unwrap());
+        assert!(history.redo().unwrap());
+        assert!(!history.redo().unwrap());
+        assert_eq!(
+            store.get_untracked(),
+            Document {
+                title: "Greeting".to_string(),
+                paragraphs: vec!["Hello".to_string()],
+            }
+        );
+
+        // a new change clears the steps that could be redone
+        history.undo().unwrap();
+        store.title().set("Other".to_string());
+        assert!(history.record().unwrap());
+        assert!(!history.can_redo());
+    }
+
+    #[tokio::test]
+    async fn changes_are_recorded_once_per_tick() {
+        _ = any_spawner::Executor::init_tokio();
+
+        let store = Store::new(Document::default());
+        let history = History::new(store).unwrap();
+        tick().await;
+
+        store.title().set("A".to_string());
+        store.title().set("AB".to_string());
+        tick().await;
+        store.title().set("ABC".to_string());
+        tick().await;
+
+        history.undo().unwrap();
+        assert_eq!(store.title().get_untracked(), "AB");
+        // undoing does not record a new step
+        tick().await;
+        assert!(history.can_redo());
+        history.undo().unwrap();
+        assert_eq!(store.title().get_untracked(), "");
+        assert!(!history.can_undo());
+    }
+
+    #[tokio::test]
+    async fn undo_notifies_only_changed_fields() {
+        _ = any_spawner::Executor::init_tokio();
+
+        let store = Store::new(Document::default());
+        let history = History::new(store.paragraphs()).unwrap();
+
+        let title_runs = Arc::new(AtomicUsize::new(0));
+        Effect::new_sync({
+            let title_runs = Arc::clone(&title_runs);
+            move |_| {
+                _ = store.title().read();
+                title_runs.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+        tick().await;
+
+        store
+            .paragraphs()
+            .update(|paragraphs| paragraphs.push("Hello".to_string()));
+        history.undo().unwrap();
+        tick().await;
+        assert!(store.paragraphs().get_untracked().is_empty());
+        assert_eq!(title_runs.load(Ordering::Relaxed), 1);
+    }
+
+    #[tokio::test]
+    async fn history_can_be_limited() {
+        _ = any_spawner::Executor::init_tokio();
+
+        let store = Store::new(Document::default());
+        let history = History::new(store).unwrap().with_limit(2);
+
+        for title in ["A", "B", "C"] {
+            store.title().set(title.to_string());
+            history.record().unwrap();
+        }
+
+        history.undo().unwrap();
+        history.undo().unwrap();
+        assert!(!history.undo().unwrap());
+        assert_eq!(store.title().get_untracked(), "A");
+
+        history.clear();
+        assert!(!history.can_redo());
+    }
+}
@@ -0,0 +1,564 @@
+use crate::{Patch, PatchField, StoreField};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+use std::{error::Error, fmt, mem};
+
+/// A [JSON Patch](https://datatracker.ietf.org/doc/html/rfc6902): a list of operations that
+/// describe how to change one JSON document into another.
+///
+/// A patch between two values can be created with [`JsonPatch::diff`], and applied to a store or
+/// field with [`ApplyJsonPatch::apply_json_patch`]. It serializes to and deserializes from the
+/// JSON representation defined by RFC 6902.
+///
+/// Paths are [JSON Pointers](https://datatracker.ietf.org/doc/html/rfc6901) into the serialized
+/// form of the value, so they use the names of struct fields and the indices of items in a `Vec`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct JsonPatch(pub Vec<PatchOperation>);
+
+/// A single operation in a [`JsonPatch`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum PatchOperation {
+    /// Adds a value to an object, inserts it into an array, or replaces the whole document.
+    Add {
+        /// The location to add the value at.
+        path: String,
+        /// The value to add.
+        value: Value,
+    },
+    /// Removes the value at the given location.
+    Remove {
+        /// The location to remove.
+        path: String,
+    },
+    /// Replaces the value at the given location, which must exist.
+    Replace {
+        /// The location to replace.
+        path: String,
+        /// The new value.
+        value: Value,
+    },
+    /// Removes the value at `from` and adds it at `path`.
+    Move {
+        /// The location to move the value from.
+        from: String,
+        /// The location to move the value to.
+        path: String,
+    },
+    /// Adds a copy of the value at `from` at `path`.
+    Copy {
+        /// The location to copy the value from.
+        from: String,
+        /// The location to add the copy at.
+        path: String,
+    },
+    /// Checks that the value at the given location is equal to `value`.
+    Test {
+        /// The location to check.
+        path: String,
+        /// The expected value.
+        value: Value,
+    },
+}
+
+/// An error that occurs while creating or applying a [`JsonPatch`].
+#[derive(Debug)]
+pub enum JsonPatchError {
+    /// A path is not a valid JSON Pointer.
+    InvalidPointer(String),
+    /// A path does not point to a value that the operation can be applied to.
+    PathNotFound(String),
+    /// A `test` operation found a value that is not equal to the expected value.
+    TestFailed(String),
+    /// The value could not be converted to or from JSON.
+    Serde(serde_json::Error),
+}
+
+impl fmt::Display for JsonPatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JsonPatchError::InvalidPointer(path) => {
+                write!(f, "{path:?} is not a valid JSON Pointer")
+            }
+            JsonPatchError::PathNotFound(path) => {
+                write!(f, "no value can be patched at {path:?}")
+            }
+            JsonPatchError::TestFailed(path) => {
+                write!(f, "the value at {path:?} did not match the test")
+            }
+            JsonPatchError::Serde(e) => e.fmt(f),
+        }
+    }
+}
+
+impl Error for JsonPatchError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            JsonPatchError::Serde(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for JsonPatchError {
+    fn from(value: serde_json::Error) -> Self {
+        JsonPatchError::Serde(value)
+    }
+}
+
+impl JsonPatch {
+    /// Creates a patch that changes `old` into `new`, by comparing their serialized forms.
+    ///
+    /// Objects are compared key by key, and arrays item by item, so the patch only replaces the
+    /// values that differ.
+    pub fn diff<T>(old: &T, new: &T) -> Result<Self, JsonPatchError>
+    where
+        T: Serialize,
+    {
+        let old = serde_json::to_value(old)?;
+        let new = serde_json::to_value(new)?;
+        Ok(Self::diff_json(&old, &new))
+    }
+
+    /// Creates a patch that changes one JSON value into another.
+    pub(crate) fn diff_json(old: &Value, new: &Value) -> Self {
+        let mut ops = Vec::new();
+        diff_values(&mut String::new(), old, new, &mut ops);
+        JsonPatch(ops)
+    }
+
+    /// Returns `true` if the patch has no operations.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Applies the patch to a JSON value.
+    ///
+    /// The operations are applied in order. If any of them fails, `value` is left unchanged.
+    pub fn apply(&self, value: &mut Value) -> Result<(), JsonPatchError> {
+        let mut patched = value.clone();
+        for op in &self.0 {
+            op.apply(&mut patched)?;
+        }
+        *value = patched;
+        Ok(())
+    }
+}
+
+impl PatchOperation {
+    fn apply(&self, doc: &mut Value) -> Result<(), JsonPatchError> {
+        match self {
+            PatchOperation::Add { path, value } => {
+                add(doc, path, value.clone())
+            }
+            PatchOperation::Remove { path } => remove(doc, path).map(drop),
+            PatchOperation::Replace { path, value } => {
+                *pointer_mut(doc, path)? = value.clone();
+                Ok(())
+            }
+            PatchOperation::Move { from, path } => {
+                // a value cannot be moved into one of its own children
+                if path.starts_with(from.as_str())
+                    && path[from.len()..].starts_with('/')
+                {
+                    return Err(JsonPatchError::PathNotFound(path.clone()));
+                }
+                let value = remove(doc, from)?;
+                add(doc, path, value)
+            }
+            PatchOperation::Copy { from, path } => {
+                let value = pointer_mut(doc, from)?.clone();
+                add(doc, path, value)
+            }
+            PatchOperation::Test { path, value } => {
+                if *pointer_mut(doc, path)? == *value {
+                    Ok(())
+                } else {
+                    Err(JsonPatchError::TestFailed(path.clone()))
+                }
+            }
+        }
+    }
+}
+
+fn diff_values(
+    path: &mut String,
+    old: &Value,
+    new: &Value,
+    ops: &mut Vec<PatchOperation>,
+) {
+    let len = path.len();
+    match (old, new) {
+        (Value::Object(old), Value::Object(new)) => {
+            for (key, old) in old {
+                push_token(path, key);
+                match new.get(key) {
+                    Some(new) => diff_values(path, old, new, ops),
+                    None => {
+                        ops.push(PatchOperation::Remove { path: path.clone() })
+                    }
+                }
+                path.truncate(len);
+            }
+            for (key, new) in new {
+                if !old.contains_key(key) {
+                    push_token(path, key);
+                    ops.push(PatchOperation::Add {
+                        path: path.clone(),
+                        value: new.clone(),
+                    });
+                    path.truncate(len);
+                }
+            }
+        }
+        (Value::Array(old), Value::Array(new)) => {
+            for (idx, (old, new)) in old.iter().zip(new).enumerate() {
+                push_token(path, &idx.to_string());
+                diff_values(path, old, new, ops);
+                path.truncate(len);
+            }
+            // remove from the end, so that the indices of earlier items don't shift
+            for idx in (new.len()..old.len()).rev() {
+                push_token(path, &idx.to_string());
+                ops.push(PatchOperation::Remove { path: path.clone() });
+                path.truncate(len);
+            }
+            for (idx, new) in new.iter().enumerate().skip(old.len()) {
+                push_token(path, &idx.to_string());
+                ops.push(PatchOperation::Add {
+                    path: path.clone(),
+                    value: new.clone(),
+                });
+                path.truncate(len);
+            }
+        }
+        (old, new) if old != new => ops.push(PatchOperation::Replace {
+            path: path.clone(),
+            value: new.clone(),
+        }),
+        _ => {}
+    }
+}
+
+fn push_token(path: &mut String, token: &str) {
+    path.push('/');
+    path.push_str(&token.replace('~', "~0").replace('/', "~1"));
+}
+
+/// Splits a JSON Pointer into its unescaped reference tokens.
+fn tokens(path: &str) -> Result<Vec<String>, JsonPatchError> {
+    if path.is_empty() {
+        return Ok(Vec::new());
+    }
+    let Some(rest) = path.strip_prefix('/') else {
+        return Err(JsonPatchError::InvalidPointer(path.to_string()));
+    };
+    Ok(rest
+        .split('/')
+        .map(|token| token.replace("~1", "/").replace("~0", "~"))
+        .collect())
+}
+
+fn array_index(
+    token: &str,
+    len: usize,
+    path: &str,
+) -> Result<usize, JsonPatchError> {
+    // leading zeros are not allowed, other than the index 0 itself
+    if token.is_empty()
+        || (token.len() > 1 && token.starts_with('0'))
+        || !token.bytes().all(|b| b.is_ascii_digit())
+    {
+        return Err(JsonPatchError::InvalidPointer(path.to_string()));
+    }
+    match token.parse::<usize>() {
+        Ok(idx) if idx < len => Ok(idx),
+        _ => Err(JsonPatchError::PathNotFound(path.to_string())),
+    }
+}
+
+fn pointer_mut<'a>(
+    doc: &'a mut Value,
+    path: &str,
+) -> Result<&'a mut Value, JsonPatchError> {
+    let mut curr = doc;
+    for token in tokens(path)? {
+        curr = match curr {
+            Value::Object(map) => map.get_mut(&token),
+            Value::Array(items) => {
+                let idx = array_index(&token, items.len(), path)?;
+                items.get_mut(idx)
+            }
+            _ => None,
+        }
+        .ok_or_else(|| JsonPatchError::PathNotFound(path.to_string()))?;
+    }
+    Ok(curr)
+}
+
+/// Returns the parent of the value at `path`, and the last token of the path, or `None` for the
+/// root.
+fn parent_mut<'a>(
+    doc: &'a mut Value,
+    path: &str,
+) -> Result<Option<(&'a mut Value, String)>, JsonPatchError> {
+    let Some(last) = tokens(path)?.pop() else {
+        return Ok(None);
+    };
+    // escaped tokens never contain `/`, so the parent ends at the last one
+    let parent_path = &path[..path.rfind('/').unwrap_or_default()];
+    Ok(Some((pointer_mut(doc, parent_path)?, last)))
+}
+
+fn add(
+    doc: &mut Value,
+    path: &str,
+    value: Value,
+) -> Result<(), JsonPatchError> {
+    match parent_mut(doc, path)? {
+        None => *doc = value,
+        Some((Value::Object(map), key)) => {
+            map.insert(key, value);
+        }
+        Some((Value::Array(items), token)) => {
+            let idx = if token == "-" {
+                items.len()
+            } else {
+                // an item can be added at the end of the array, as well as before any item
+                array_index(&token, items.len() + 1, path)?
+            };
+            items.insert(idx, value);
+        }
+        Some(_) => return Err(JsonPatchError::PathNotFound(path.to_string())),
+    }
+    Ok(())
+}
+
+fn remove(doc: &mut Value, path: &str) -> Result<Value, JsonPatchError> {
+    match parent_mut(doc, path)? {
+        None => Ok(mem::replace(doc, Value::Null)),
+        Some((Value::Object(map), key)) => map
+            .remove(&key)
+            .ok_or_else(|| JsonPatchError::PathNotFound(path.to_string())),
+        Some((Value::Array(items), token)) => {
+            let idx = array_index(&token, items.len(), path)?;
+            Ok(items.remove(idx))
+        }
+        Some(_) => Err(JsonPatchError::PathNotFound(path.to_string())),
+    }
+}
+
+/// Allows applying a [`JsonPatch`] to a store or field.
+pub trait ApplyJsonPatch {
+    /// Applies a JSON Patch to the value of the store or field, only notifying the fields that
+    /// have changed.
+    ///
+    /// The patch is applied to the serialized form of the current value, and the result is
+    /// deserialized and applied with [`Patch::patch`](crate::Patch::patch). If any operation
+    /// fails, or the result cannot be deserialized, the value is left unchanged.
+    fn apply_json_patch(&self, patch: &JsonPatch)
+        -> Result<(), JsonPatchError>;
+}
+
+impl<T> ApplyJsonPatch for T
+where
+    T: StoreField,
+    T::Value: PatchField + Serialize + DeserializeOwned,
+{
+    fn apply_json_patch(
+        &self,
+        patch: &JsonPatch,
+    ) -> Result<(), JsonPatchError> {
+        let Some(value) =
+            self.reader().map(|value| serde_json::to_value(&*value))
+        else {
+            return Ok(());
+        };
+        let mut value = value?;
+        patch.apply(&mut value)?;
+        let new = serde_json::from_value::<T::Value>(value)?;
+        self.patch(new);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ApplyJsonPatch, JsonPatch, JsonPatchError, PatchOperation};
+    use crate::{self as reactive_stores, Patch, Store};
+    use reactive_graph::{
+        effect::Effect,
+        traits::{Read, ReadUntracked},
+    };
+    use serde::{Deserialize, Serialize};
+    use serde_json::json;
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    pub async fn tick() {
+        tokio::time::sleep(std::time::Duration::from_micros(1)).await;
+    }
+
+    #[derive(Debug, Clone, PartialEq, Store, Patch, Serialize, Deserialize)]
+    struct Todos {
+        user: String,
+        todos: Vec<Todo>,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Store, Patch, Serialize, Deserialize)]
+    struct Todo {
+        label: String,
+        completed: bool,
+    }
+
+    fn data() -> Todos {
+        Todos {
+            user: "Bob".to_string(),
+            todos: vec![
+                Todo {
+                    label: "Create reactive store".to_string(),
+                    completed: true,
+                },
+                Todo {
+                    label: "???".to_string(),
+                    completed: false,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn diff_only_includes_changed_values() {
+        let old = data();
+        let mut new = data();
+        new.todos[1].completed = true;
+        new.todos.pop();
+        new.todos.push(Todo {
+            label: "Profit".to_string(),
+            completed: false,
+        });
+        new.todos.push(Todo {
+            label: "Retire".to_string(),
+            completed: false,
+        });
+
+        let patch = JsonPatch::diff(&old, &new).unwrap();
+        assert_eq!(
+            serde_json::to_value(&patch).unwrap(),
+            json!([
+                { "op": "replace", "path": "/todos/1/label", "value": "Profit" },
+                { "op": "add", "path": "/todos/2", "value": { "label": "Retire", "completed": false } }
+            ])
+        );
+        assert!(JsonPatch::diff(&old, &old).unwrap().is_empty());
+
+        let mut value = serde_json::to_value(&old).unwrap();
+        patch.apply(&mut value).unwrap();
+        assert_eq!(serde_json::from_value::<Todos>(value).unwrap(), new);
+    }
+
+    #[test]
+    fn apply_supports_all_operations() {
+        let mut value = json!({ "a/b": [1, 2], "c": { "d": "e" } });
+        let patch: JsonPatch = serde_json::from_value(json!([
+            { "op": "test", "path": "/a~1b/0", "value": 1 },
+            { "op": "add", "path": "/a~1b/-", "value": 3 },
+            { "op": "remove", "path": "/a~1b/0" },
+            { "op": "copy", "from": "/c/d", "path": "/f" },
+            { "op": "move", "from": "/c", "path": "/g" },
+            { "op": "replace", "path": "/f", "value": null }
+        ]))
+        .unwrap();
+        patch.apply(&mut value).unwrap();
+        assert_eq!(
+            value,
+            json!({ "a/b": [2, 3], "f": null, "g": { "d": "e" } })
+        );
+    }
+
+    #[test]
+    fn failed_patch_leaves_value_unchanged() {
+        let mut value = json!({ "a": 1 });
+        let patch = JsonPatch(vec![
+            PatchOperation::Replace {
+                path: "/a".to_string(),
+                value: json!(2),
+            },
+            PatchOperation::Test {
+                path: "/a".to_string(),
+                value: json!(3),
+            },
+        ]);
+        assert!(matches!(
+            patch.apply(&mut value),
+            Err(JsonPatchError::TestFailed(_))
+        ));
+        assert_eq!(value, json!({ "a": 1 }));
+
+        let patch = JsonPatch(vec![PatchOperation::Remove {
+            path: "/b".to_string(),
+        }]);
+        assert!(matches!(
+            patch.apply(&mut value),
+            Err(JsonPatchError::PathNotFound(_))
+        ));
+        let patch = JsonPatch(vec![PatchOperation::Remove {
+            path: "a".to_string(),
+        }]);
+        assert!(matches!(
+            patch.apply(&mut value),
+            Err(JsonPatchError::InvalidPointer(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn applying_patch_only_notifies_changed_fields() {
+        _ = any_spawner::Executor::init_tokio();
+
+        let user_count = Arc::new(AtomicUsize::new(0));
+        let todos_count = Arc::new(AtomicUsize::new(0));
+        let store = Store::new(data());
+
+        Effect::new_sync({
+            let user_count = Arc::clone(&user_count);
+            move |_| {
+                _ = store.user().read();
+                user_count.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+        Effect::new_sync({
+            let todos_count = Arc::clone(&todos_count);
+            move |_| {
+                _ = store.todos().read();
+                todos_count.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+        tick().await;
+
+        let patch: JsonPatch = serde_json::from_value(json!([
+            { "op": "replace", "path": "/user", "value": "Alice" }
+        ]))
+        .unwrap();
+        store.apply_json_patch(&patch).unwrap();
+        tick().await;
+        assert_eq!(store.user().read_untracked().as_str(), "Alice");
+        assert_eq!(user_count.load(Ordering::Relaxed), 2);
+        assert_eq!(todos_count.load(Ordering::Relaxed), 1);
+
+        // a patch that fails to deserialize leaves the store unchanged
+        let patch: JsonPatch = serde_json::from_value(json!([
+            { "op": "replace", "path": "/user", "value": 42 }
+        ]))
+        .unwrap();
+        assert!(matches!(
+            store.apply_json_patch(&patch),
+            Err(JsonPatchError::Serde(_))
+        ));
+        tick().await;
+        assert_eq!(store.user().read_untracked().as_str(), "Alice");
+        assert_eq!(user_count.load(Ordering::Relaxed), 2);
+    }
+}
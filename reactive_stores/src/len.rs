@@ -1,6 +1,6 @@
 use std::{
     borrow::Cow,
-    collections::{LinkedList, VecDeque},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet, LinkedList, VecDeque},
 };
 
 /// A trait for getting the length of a collection.
@@ -62,6 +62,14 @@ delegate_impl_len!(<T,> [T]);
 delegate_impl_len!(<T,> Vec<T>);
 delegate_impl_len!(str);
 delegate_impl_len!(String);
+delegate_impl_len!(<K, V, S,> HashMap<K, V, S>);
+delegate_impl_len!(<K, V,> BTreeMap<K, V>);
+delegate_impl_len!(<T, S,> HashSet<T, S>);
+delegate_impl_len!(<T,> BTreeSet<T>);
+#[cfg(feature = "indexmap")]
+delegate_impl_len!(<K, V, S,> indexmap::IndexMap<K, V, S>);
+#[cfg(feature = "indexmap")]
+delegate_impl_len!(<T, S,> indexmap::IndexSet<T, S>);
 
 impl Len for Cow<'_, str> {
     #[inline(always)]
@@ -22,6 +22,17 @@
 //! [`.patch()`](Patch::patch) method, which allows you to provide an entirely new value, but only
 //! notify fields that have changed.
 //!
+//! With the `json-patch` feature, a `JsonPatch` ([RFC 6902](https://datatracker.ietf.org/doc/html/rfc6902))
+//! can be computed between two values, or applied to a store or field with `apply_json_patch()`,
+//! which notifies only the fields that have changed in the same way.
+//!
+//! A [`Snapshot`] of a store or field can be taken with [`StoreSnapshot::snapshot`] and restored
+//! later, again notifying only the fields that differ. With the `json-patch` feature, a `History`
+//! records each change as a pair of patches, for undo and redo.
+//!
+//! With the `changefeed` feature, `changefeed()` returns a stream of batches of the fields of a
+//! store that have changed, with their new values, for persisting or syncing a store.
+//!
 //! Updating a field will notify its parents and children, but not its siblings.
 //!
 //! Stores can therefore
@@ -155,6 +166,68 @@
 //! assert_eq!(store.vec_field().at_unkeyed(1).get(), 2);
 //! assert_eq!(store.vec_field().at_unkeyed(2).get(), 3);
 //! ```
+//! #### Maps and sets
+//! Fields that hold a [`HashMap`](std::collections::HashMap) or [`BTreeMap`](std::collections::BTreeMap)
+//! (or an `IndexMap`, with the `indexmap` feature) have a reactive field for each key, through
+//! [StoreFieldMap]. Inserting or removing a key only notifies that key and anything that iterates
+//! over the keys, and updating the value at one key does not notify any other. Sets can check
+//! reactively whether they contain an item with [StoreFieldSet]. For example:
+//! ```rust
+//! # use reactive_stores::Store;
+//! use reactive_stores::{StoreFieldMap, StoreFieldSet};
+//! use reactive_graph::traits::Get;
+//! use std::collections::{HashMap, HashSet};
+//!
+//! #[derive(Store)]
+//! struct Directory {
+//!     users: HashMap<u32, String>,
+//!     admins: HashSet<u32>,
+//! }
+//!
+//! let store = Store::new(Directory {
+//!     users: HashMap::from([(1, "Alice".to_string())]),
+//!     admins: HashSet::new(),
+//! });
+//!
+//! store.users().insert(2, "Bob".to_string());
+//! assert_eq!(store.users().at_key(2).get(), "Bob");
+//! // iter_keyed() only tracks the set of keys, so it can be used for keyed iteration in a view
+//! assert_eq!(store.users().iter_keyed().count(), 2);
+//!
+//! store.admins().insert(1);
+//! assert!(store.admins().contains(&1));
+//! ```
+//! #### Forms
+//! A [StoreForm] wraps a store, and tracks whether each of its fields has been changed or
+//! visited, along with its validation errors. Fields added to the form with
+//! [`field`](StoreForm::field) can still be read, written, and bound to inputs, and run their
+//! validators whenever they change. For example:
+//! ```rust
+//! # use reactive_stores::Store;
+//! use reactive_stores::StoreForm;
+//! use reactive_graph::traits::{Get, Set};
+//!
+//! #[derive(Store)]
+//! struct Signup {
+//!     email: String,
+//! }
+//!
+//! let form = StoreForm::new(Signup { email: String::new() });
+//! let email = form.field(form.store().email()).validate(|email| {
+//!     if email.contains('@') {
+//!         Ok(())
+//!     } else {
+//!         Err("Please enter a valid email address.".to_string())
+//!     }
+//! });
+//!
+//! email.set("alice".to_string());
+//! assert!(email.is_dirty());
+//! assert!(email.error().get().is_some());
+//!
+//! email.set("alice@example.com".to_string());
+//! assert!(form.is_valid());
+//! ```
 //! #### Enum
 //! Enumerated types behave a bit differently as the [`Store`](macro@Store) macro builds underlying traits instead of alternate
 //! enumerated structures.  Each element in an `Enum` generates methods to access it in the store: a
@@ -262,26 +335,44 @@ use std::{
 };
 
 mod arc_field;
+#[cfg(feature = "changefeed")]
+mod changefeed;
 mod deref;
 mod field;
+mod form;
+#[cfg(feature = "json-patch")]
+mod history;
 mod iter;
+#[cfg(feature = "json-patch")]
+mod json_patch;
 mod keyed;
 mod len;
+mod map;
 mod option;
 mod patch;
 mod path;
+mod snapshot;
 mod store_field;
 mod subfield;
 
 pub use arc_field::ArcField;
+#[cfg(feature = "changefeed")]
+pub use changefeed::*;
 pub use deref::*;
 pub use field::Field;
+pub use form::*;
+#[cfg(feature = "json-patch")]
+pub use history::*;
 pub use iter::*;
+#[cfg(feature = "json-patch")]
+pub use json_patch::*;
 pub use keyed::*;
 pub use len::Len;
+pub use map::*;
 pub use option::*;
 pub use patch::*;
 pub use path::{StorePath, StorePathSegment};
+pub use snapshot::*;
 pub use store_field::StoreField;
 pub use subfield::Subfield;
 
@@ -421,10 +512,35 @@ type HashMap<K, V> = send_wrapper::SendWrapper<
     std::rc::Rc<std::cell::RefCell<std::collections::HashMap<K, V>>>,
 >;
 
+/// The [`FieldKeys`] for some key type, with the key type erased.
+trait AnyFieldKeys: Send + Sync {
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+
+    /// Returns the key for the given segment, formatted with `Debug`, and its current index.
+    #[cfg_attr(not(feature = "changefeed"), allow(dead_code))]
+    fn describe(&self, segment: StorePathSegment) -> Option<(String, usize)>;
+}
+
+impl<K> AnyFieldKeys for FieldKeys<K>
+where
+    K: Debug + Send + Sync + 'static,
+{
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn describe(&self, segment: StorePathSegment) -> Option<(String, usize)> {
+        self.keys
+            .iter()
+            .find(|(_, (key_segment, _))| *key_segment == segment)
+            .map(|(key, (_, idx))| (format!("{key:?}"), *idx))
+    }
+}
+
 /// A map of the keys for a keyed subfield.
 #[derive(Clone)]
 pub struct KeyMap(
-    HashMap<StorePath, Box<dyn Any + Send + Sync>>,
+    HashMap<StorePath, Box<dyn AnyFieldKeys>>,
     HashMap<(StorePath, usize), StorePathSegment>,
 );
 
@@ -453,14 +569,13 @@ impl KeyMap {
         let initial_keys = initialize();
 
         #[cfg(not(target_arch = "wasm32"))]
-        let mut entry = self
-            .0
-            .entry(path.clone())
-            .or_insert_with(|| Box::new(FieldKeys::new(initial_keys)));
+        let mut entry = self.0.entry(path.clone()).or_insert_with(|| {
+            Box::new(FieldKeys::new(initial_keys)) as Box<dyn AnyFieldKeys>
+        });
 
         #[cfg(target_arch = "wasm32")]
         let entry = if !self.0.borrow().contains_key(&path) {
-            Some(Box::new(FieldKeys::new(initial_keys)))
+            Some(Box::new(FieldKeys::new(initial_keys)) as Box<dyn AnyFieldKeys>)
         } else {
             None
         };
@@ -469,7 +584,7 @@ impl KeyMap {
         #[cfg(target_arch = "wasm32")]
         let entry = map.entry(path.clone()).or_insert_with(|| entry.unwrap());
 
-        let entry = entry.downcast_mut::<FieldKeys<K>>()?;
+        let entry = entry.as_any_mut().downcast_mut::<FieldKeys<K>>()?;
         let (result, new_keys) = fun(entry);
         if !new_keys.is_empty() {
             for (idx, segment) in new_keys {
@@ -495,6 +610,25 @@ impl KeyMap {
         }
     }
 
+    /// Returns the key for the given segment of the keyed field at `path`, formatted with
+    /// `Debug`, and its current index.
+    #[cfg(feature = "changefeed")]
+    fn describe_key(
+        &self,
+        path: &StorePath,
+        segment: StorePathSegment,
+    ) -> Option<(String, usize)> {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            self.0.get(path)?.describe(segment)
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        {
+            self.0.borrow().get(path)?.describe(segment)
+        }
+    }
+
     fn get_key_for_index(
         &self,
         key: &(StorePath, usize),
@@ -523,6 +657,8 @@ pub struct ArcStore<T> {
     pub(crate) value: Arc<RwLock<T>>,
     signals: Arc<RwLock<TriggerMap>>,
     keys: KeyMap,
+    #[cfg(feature = "changefeed")]
+    changes: Arc<changefeed::ChangeLog>,
 }
 
 impl<T> ArcStore<T> {
@@ -534,6 +670,8 @@ impl<T> ArcStore<T> {
             value: Arc::new(RwLock::new(value)),
             signals: Default::default(),
             keys: Default::default(),
+            #[cfg(feature = "changefeed")]
+            changes: Default::default(),
         }
     }
 }
@@ -563,6 +701,8 @@ impl<T> Clone for ArcStore<T> {
             value: Arc::clone(&self.value),
             signals: Arc::clone(&self.signals),
             keys: self.keys.clone(),
+            #[cfg(feature = "changefeed")]
+            changes: Arc::clone(&self.changes),
         }
     }
 }
@@ -0,0 +1,926 @@
+use crate::{
+    path::{StorePath, StorePathSegment},
+    store_field::StoreField,
+    KeyMap, StoreFieldTrigger,
+};
+use reactive_graph::{
+    signal::{
+        guards::{MappedMutArc, WriteGuard},
+        ArcTrigger,
+    },
+    traits::{
+        DefinedAt, IsDisposed, Notify, ReadUntracked, Track, UntrackableGuard,
+        Write,
+    },
+};
+use rustc_hash::FxHasher;
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque},
+    hash::{BuildHasher, Hash, Hasher},
+    iter,
+    marker::PhantomData,
+    ops::DerefMut,
+    panic::Location,
+};
+
+/// The path segment, under a map or set field, of the trigger that is notified when its keys
+/// change.
+pub(crate) const KEY_SET: StorePathSegment = StorePathSegment(usize::MAX);
+
+/// Returns the path segment for a key in a map or set field.
+///
+/// Keys are identified by their hash, so that the field for a key always has the same path, no
+/// matter which keys are added or removed around it. (Two keys with the same hash share their
+/// triggers, which means they may notify one another, but never that a change is missed.)
+pub(crate) fn key_segment<K>(key: &K) -> StorePathSegment
+where
+    K: Hash + ?Sized,
+{
+    let mut hasher = FxHasher::default();
+    key.hash(&mut hasher);
+    // clearing the highest bit means that a key can never share a path with the key set
+    StorePathSegment(hasher.finish() as usize & (usize::MAX >> 1))
+}
+
+/// A map that can be held in a store field, with reactive access to the value at each key.
+pub trait StoreMap {
+    /// The type of the keys of the map.
+    type Key;
+    /// The type of the values of the map.
+    type Value;
+
+    /// Returns a reference to the value at the given key.
+    fn get(&self, key: &Self::Key) -> Option<&Self::Value>;
+
+    /// Returns a mutable reference to the value at the given key.
+    fn get_mut(&mut self, key: &Self::Key) -> Option<&mut Self::Value>;
+
+    /// Inserts a value at the given key, returning the previous value.
+    fn insert(
+        &mut self,
+        key: Self::Key,
+        value: Self::Value,
+    ) -> Option<Self::Value>;
+
+    /// Removes the value at the given key, and returns it.
+    fn remove(&mut self, key: &Self::Key) -> Option<Self::Value>;
+
+    /// Returns an iterator over the keys of the map, in its iteration order.
+    fn map_keys(&self) -> impl Iterator<Item = &Self::Key>;
+}
+
+/// A set that can be held in a store field, with reactive access to whether it contains each
+/// item.
+pub trait StoreSet {
+    /// The type of the items in the set.
+    type Item;
+
+    /// Returns `true` if the set contains the item.
+    fn contains(&self, item: &Self::Item) -> bool;
+
+    /// Adds an item to the set, returning `false` if it was already present.
+    fn insert(&mut self, item: Self::Item) -> bool;
+
+    /// Removes an item from the set, returning `true` if it was present.
+    fn remove(&mut self, item: &Self::Item) -> bool;
+}
+
+macro_rules! delegate_impl_map {
+    (<$($generics:ident),*> $ty:ty, $remove:ident where $($bounds:tt)*) => {
+        impl<$($generics),*> StoreMap for $ty
+        where
+            $($bounds)*
+        {
+            type Key = K;
+            type Value = V;
+
+            #[inline(always)]
+            fn get(&self, key: &K) -> Option<&V> {
+                <$ty>::get(self, key)
+            }
+
+            #[inline(always)]
+            fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+                <$ty>::get_mut(self, key)
+            }
+
+            #[inline(always)]
+            fn insert(&mut self, key: K, value: V) -> Option<V> {
+                <$ty>::insert(self, key, value)
+            }
+
+            #[inline(always)]
+            fn remove(&mut self, key: &K) -> Option<V> {
+                <$ty>::$remove(self, key)
+            }
+
+            #[inline(always)]
+            fn map_keys(&self) -> impl Iterator<Item = &K> {
+                <$ty>::keys(self)
+            }
+        }
+    };
+}
+
+macro_rules! delegate_impl_set {
+    (<$($generics:ident),*> $ty:ty, $remove:ident where $($bounds:tt)*) => {
+        impl<$($generics),*> StoreSet for $ty
+        where
+            $($bounds)*
+        {
+            type Item = T;
+
+            #[inline(always)]
+            fn contains(&self, item: &T) -> bool {
+                <$ty>::contains(self, item)
+            }
+
+            #[inline(always)]
+            fn insert(&mut self, item: T) -> bool {
+                <$ty>::insert(self, item)
+            }
+
+            #[inline(always)]
+            fn remove(&mut self, item: &T) -> bool {
+                <$ty>::$remove(self, item)
+            }
+        }
+    };
+}
+
+delegate_impl_map!(<K, V, S> HashMap<K, V, S>, remove where K: Hash + Eq, S: BuildHasher);
+delegate_impl_map!(<K, V> BTreeMap<K, V>, remove where K: Ord);
+// removing with `shift_remove` keeps the order of the other entries, which matters when they are
+// rendered in order
+#[cfg(feature = "indexmap")]
+delegate_impl_map!(<K, V, S> indexmap::IndexMap<K, V, S>, shift_remove where K: Hash + Eq, S: BuildHasher);
+
+delegate_impl_set!(<T, S> HashSet<T, S>, remove where T: Hash + Eq, S: BuildHasher);
+delegate_impl_set!(<T> BTreeSet<T>, remove where T: Ord);
+#[cfg(feature = "indexmap")]
+delegate_impl_set!(<T, S> indexmap::IndexSet<T, S>, shift_remove where T: Hash + Eq, S: BuildHasher);
+
+/// Tracks the trigger for the field at the given path, and any change that is made directly to
+/// one of its ancestors.
+fn track_path(field: &impl StoreField, mut path: StorePath) {
+    let trigger = field.get_trigger(path.clone());
+    trigger.this.track();
+    trigger.children.track();
+
+    while !path.is_empty() {
+        path.pop();
+        let inner = field.get_trigger(path.clone());
+        inner.this.track();
+    }
+}
+
+/// Notifies the entry with the given key in a map or set field, and its set of keys if they
+/// have changed.
+fn notify_entry(
+    field: &impl StoreField,
+    key: StorePathSegment,
+    keys_changed: bool,
+) {
+    let mut path = field.path().into_iter().collect::<StorePath>();
+    path.push(key);
+    field.triggers_for_path(path.clone()).notify();
+    if keys_changed {
+        path.replace_last(KEY_SET);
+        field.triggers_for_path(path).notify();
+    }
+}
+
+/// Provides access to the value at some key in a map.
+#[derive(Debug)]
+pub struct AtMapKey<Inner, Prev, K> {
+    #[cfg(any(debug_assertions, leptos_debuginfo))]
+    defined_at: &'static Location<'static>,
+    inner: Inner,
+    key: K,
+    ty: PhantomData<Prev>,
+}
+
+impl<Inner, Prev, K> Clone for AtMapKey<Inner, Prev, K>
+where
+    Inner: Clone,
+    K: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: self.defined_at,
+            inner: self.inner.clone(),
+            key: self.key.clone(),
+            ty: self.ty,
+        }
+    }
+}
+
+impl<Inner, Prev, K> Copy for AtMapKey<Inner, Prev, K>
+where
+    Inner: Copy,
+    K: Copy,
+{
+}
+
+impl<Inner, Prev, K> AtMapKey<Inner, Prev, K> {
+    /// Provides access to the value in the inner map at this key.
+    #[track_caller]
+    pub fn new(inner: Inner, key: K) -> Self {
+        Self {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: Location::caller(),
+            inner,
+            key,
+            ty: PhantomData,
+        }
+    }
+
+    /// The key this field accesses.
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+}
+
+impl<Inner, Prev, K> StoreField for AtMapKey<Inner, Prev, K>
+where
+    Inner: StoreField<Value = Prev>,
+    Prev: StoreMap<Key = K> + 'static,
+    K: Hash + Clone + 'static,
+{
+    type Value = Prev::Value;
+    type Reader = MappedMutArc<Inner::Reader, Prev::Value>;
+    type Writer =
+        WriteGuard<Vec<ArcTrigger>, MappedMutArc<Inner::Writer, Prev::Value>>;
+
+    fn path(&self) -> impl IntoIterator<Item = StorePathSegment> {
+        self.inner
+            .path()
+            .into_iter()
+            .chain(iter::once(key_segment(&self.key)))
+    }
+
+    fn path_unkeyed(&self) -> impl IntoIterator<Item = StorePathSegment> {
+        self.inner
+            .path_unkeyed()
+            .into_iter()
+            .chain(iter::once(key_segment(&self.key)))
+    }
+
+    fn get_trigger(&self, path: StorePath) -> StoreFieldTrigger {
+        self.inner.get_trigger(path)
+    }
+
+    fn get_trigger_unkeyed(&self, path: StorePath) -> StoreFieldTrigger {
+        self.inner.get_trigger_unkeyed(path)
+    }
+
+    fn reader(&self) -> Option<Self::Reader> {
+        let inner = self.inner.reader()?;
+        // there is no field if the key is not in the map
+        StoreMap::get(&*inner, &self.key)?;
+
+        let key = self.key.clone();
+        let key_mut = self.key.clone();
+        Some(MappedMutArc::new(
+            inner,
+            move |map| map.get(&key).expect("key was checked while locked"),
+            move |map| {
+                map.get_mut(&key_mut).expect("key was checked while locked")
+            },
+        ))
+    }
+
+    fn writer(&self) -> Option<Self::Writer> {
+        let mut inner = self.inner.writer()?;
+        // only this entry, and not its siblings, should be notified
+        inner.untrack();
+        StoreMap::get(&*inner, &self.key)?;
+
+        let triggers = self.triggers_for_current_path();
+        let key = self.key.clone();
+        let key_mut = self.key.clone();
+        Some(WriteGuard::new(
+            triggers,
+            MappedMutArc::new(
+                inner,
+                move |map| map.get(&key).expect("key was checked while locked"),
+                move |map| {
+                    map.get_mut(&key_mut).expect("key was checked while locked")
+                },
+            ),
+        ))
+    }
+
+    #[inline(always)]
+    fn keys(&self) -> Option<KeyMap> {
+        self.inner.keys()
+    }
+
+    fn track_field(&self) {
+        track_path(self, self.path().into_iter().collect());
+    }
+}
+
+impl<Inner, Prev, K> DefinedAt for AtMapKey<Inner, Prev, K> {
+    fn defined_at(&self) -> Option<&'static Location<'static>> {
+        #[cfg(any(debug_assertions, leptos_debuginfo))]
+        {
+            Some(self.defined_at)
+        }
+        #[cfg(not(any(debug_assertions, leptos_debuginfo)))]
+        {
+            None
+        }
+    }
+}
+
+impl<Inner, Prev, K> IsDisposed for AtMapKey<Inner, Prev, K>
+where
+    Inner: IsDisposed,
+{
+    fn is_disposed(&self) -> bool {
+        self.inner.is_disposed()
+    }
+}
+
+impl<Inner, Prev, K> Notify for AtMapKey<Inner, Prev, K>
+where
+    Inner: StoreField<Value = Prev>,
+    Prev: StoreMap<Key = K> + 'static,
+    K: Hash + Clone + 'static,
+{
+    fn notify(&self) {
+        let trigger = self.get_trigger(self.path().into_iter().collect());
+        trigger.this.notify();
+        trigger.children.notify();
+    }
+}
+
+impl<Inner, Prev, K> Track for AtMapKey<Inner, Prev, K>
+where
+    Inner: StoreField<Value = Prev>,
+    Prev: StoreMap<Key = K> + 'static,
+    K: Hash + Clone + 'static,
+{
+    fn track(&self) {
+        self.track_field();
+    }
+}
+
+impl<Inner, Prev, K> ReadUntracked for AtMapKey<Inner, Prev, K>
+where
+    Inner: StoreField<Value = Prev>,
+    Prev: StoreMap<Key = K> + 'static,
+    K: Hash + Clone + 'static,
+{
+    type Value = <Self as StoreField>::Reader;
+
+    fn try_read_untracked(&self) -> Option<Self::Value> {
+        self.reader()
+    }
+}
+
+impl<Inner, Prev, K> Write for AtMapKey<Inner, Prev, K>
+where
+    Inner: StoreField<Value = Prev>,
+    Prev: StoreMap<Key = K> + 'static,
+    Prev::Value: 'static,
+    K: Hash + Clone + 'static,
+{
+    type Value = Prev::Value;
+
+    fn try_write(&self) -> Option<impl UntrackableGuard<Target = Self::Value>> {
+        self.writer()
+    }
+
+    fn try_write_untracked(
+        &self,
+    ) -> Option<impl DerefMut<Target = Self::Value>> {
+        self.writer().map(|mut writer| {
+            writer.untrack();
+            writer
+        })
+    }
+}
+
+/// Provides keyed reactive access to the entries of a map.
+///
+/// Each key has its own reactive field, and the set of keys is tracked separately from the
+/// values, so inserting or removing a key only notifies that key and anything that iterates
+/// over the map, and updating the value at one key does not notify any other.
+///
+/// Keys are identified by their [`Hash`], so the key type must implement it, even for a
+/// [`BTreeMap`].
+pub trait StoreFieldMap<Prev>
+where
+    Self: StoreField<Value = Prev>,
+    Prev: StoreMap,
+{
+    /// Reactive access to the value at some key.
+    ///
+    /// The field has no value while the key is not in the map, so reading it returns `None`.
+    fn at_key(self, key: Prev::Key) -> AtMapKey<Self, Prev, Prev::Key>;
+
+    /// Inserts a value at some key, returning the previous value.
+    ///
+    /// This notifies the field for that key, and the set of keys if the key was not already
+    /// present.
+    fn insert(&self, key: Prev::Key, value: Prev::Value)
+        -> Option<Prev::Value>;
+
+    /// Removes the value at some key, and returns it.
+    ///
+    /// This notifies the field for that key, and the set of keys, if the key was present.
+    fn remove(&self, key: &Prev::Key) -> Option<Prev::Value>;
+
+    /// Reactively checks whether the map contains some key.
+    ///
+    /// This only tracks the set of keys, not the values in the map.
+    fn contains_key(&self, key: &Prev::Key) -> bool;
+
+    /// An iterator over the entries in the map, in its iteration order, as reactive fields.
+    ///
+    /// This only tracks the set of keys, not the values in the map. The key of each field is
+    /// available from [`AtMapKey::key`], which makes this suitable for keyed iteration in a view.
+    fn iter_keyed(self) -> StoreFieldMapIter<Self, Prev, Prev::Key>;
+}
+
+impl<Inner, Prev> StoreFieldMap<Prev> for Inner
+where
+    Inner: StoreField<Value = Prev> + Clone,
+    Prev: StoreMap + 'static,
+    Prev::Key: Hash + Clone + 'static,
+{
+    #[track_caller]
+    fn at_key(self, key: Prev::Key) -> AtMapKey<Inner, Prev, Prev::Key> {
+        AtMapKey::new(self, key)
+    }
+
+    fn insert(
+        &self,
+        key: Prev::Key,
+        value: Prev::Value,
+    ) -> Option<Prev::Value> {
+        let segment = key_segment(&key);
+        let mut writer = self.writer()?;
+        writer.untrack();
+        let prev = StoreMap::insert(&mut *writer, key, value);
+        drop(writer);
+
+        notify_entry(self, segment, prev.is_none());
+        prev
+    }
+
+    fn remove(&self, key: &Prev::Key) -> Option<Prev::Value> {
+        let mut writer = self.writer()?;
+        writer.untrack();
+        let prev = StoreMap::remove(&mut *writer, key);
+        drop(writer);
+
+        if prev.is_some() {
+            notify_entry(self, key_segment(key), true);
+        }
+        prev
+    }
+
+    fn contains_key(&self, key: &Prev::Key) -> bool {
+        let mut path = self.path().into_iter().collect::<StorePath>();
+        path.push(KEY_SET);
+        track_path(self, path);
+
+        self.reader()
+            .map(|map| StoreMap::get(&*map, key).is_some())
+            .unwrap_or(false)
+    }
+
+    #[track_caller]
+    fn iter_keyed(self) -> StoreFieldMapIter<Inner, Prev, Prev::Key> {
+        // reactively track changes to the set of keys
+        let mut path = self.path().into_iter().collect::<StorePath>();
+        path.push(KEY_SET);
+        track_path(&self, path);
+
+        let keys = self
+            .reader()
+            .map(|map| map.map_keys().cloned().collect::<VecDeque<_>>())
+            .unwrap_or_default();
+
+        StoreFieldMapIter {
+            inner: self,
+            keys,
+            ty: PhantomData,
+        }
+    }
+}
+
+/// An iterator over the entries in a map, as reactive fields.
+pub struct StoreFieldMapIter<Inner, Prev, K> {
+    inner: Inner,
+    keys: VecDeque<K>,
+    ty: PhantomData<Prev>,
+}
+
+impl<Inner, Prev, K> Iterator for StoreFieldMapIter<Inner, Prev, K>
+where
+    Inner: Clone,
+{
+    type Item = AtMapKey<Inner, Prev, K>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.keys
+            .pop_front()
+            .map(|key| AtMapKey::new(self.inner.clone(), key))
+    }
+}
+
+impl<Inner, Prev, K> DoubleEndedIterator for StoreFieldMapIter<Inner, Prev, K>
+where
+    Inner: Clone,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.keys
+            .pop_back()
+            .map(|key| AtMapKey::new(self.inner.clone(), key))
+    }
+}
+
+/// Provides reactive access to the items of a set.
+///
+/// Each item is tracked separately, so inserting or removing an item only notifies code that has
+/// checked for that item, along with anything that reads the whole set.
+///
+/// Items are identified by their [`Hash`], so the item type must implement it, even for a
+/// [`BTreeSet`].
+pub trait StoreFieldSet<Prev>
+where
+    Self: StoreField<Value = Prev>,
+    Prev: StoreSet,
+{
+    /// Reactively checks whether the set contains an item.
+    ///
+    /// This only tracks that item, so it will not re-run when other items are inserted or
+    /// removed.
+    fn contains(&self, item: &Prev::Item) -> bool;
+
+    /// Adds an item to the set, returning `false` if it was already present.
+    ///
+    /// If it was not, this notifies that item and the set of items.
+    fn insert(&self, item: Prev::Item) -> bool;
+
+    /// Removes an item from the set, returning `true` if it was present.
+    ///
+    /// If it was, this notifies that item and the set of items.
+    fn remove(&self, item: &Prev::Item) -> bool;
+}
+
+impl<Inner, Prev> StoreFieldSet<Prev> for Inner
+where
+    Inner: StoreField<Value = Prev>,
+    Prev: StoreSet + 'static,
+    Prev::Item: Hash,
+{
+    fn contains(&self, item: &Prev::Item) -> bool {
+        let mut path = self.path().into_iter().collect::<StorePath>();
+        path.push(key_segment(item));
+        track_path(self, path);
+
+        self.reader()
+            .map(|set| StoreSet::contains(&*set, item))
+            .unwrap_or(false)
+    }
+
+    fn insert(&self, item: Prev::Item) -> bool {
+        let segment = key_segment(&item);
+        let Some(mut writer) = self.writer() else {
+            return false;
+        };
+        writer.untrack();
+        let inserted = StoreSet::insert(&mut *writer, item);
+        drop(writer);
+
+        if inserted {
+            notify_entry(self, segment, true);
+        }
+        inserted
+    }
+
+    fn remove(&self, item: &Prev::Item) -> bool {
+        let Some(mut writer) = self.writer() else {
+            return false;
+        };
+        writer.untrack();
+        let removed = StoreSet::remove(&mut *writer, item);
+        drop(writer);
+
+        if removed {
+            notify_entry(self, key_segment(item), true);
+        }
+        removed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        self as reactive_stores, tests::tick, Patch, Store, StoreFieldMap,
+        StoreFieldSet,
+    };
+    use reactive_graph::{
+        effect::Effect,
+        traits::{Get, GetUntracked, Read, Set, Update, Write},
+    };
+    use std::{
+        collections::{BTreeMap, HashMap, HashSet},
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+    };
+
+    #[derive(Debug, Clone, PartialEq, Store, Patch)]
+    struct Directory {
+        users: HashMap<u32, User>,
+        teams: BTreeMap<String, Vec<u32>>,
+        admins: HashSet<u32>,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Store, Patch)]
+    struct User {
+        name: String,
+        active: bool,
+    }
+
+    fn user(name: &str) -> User {
+        User {
+            name: name.to_string(),
+            active: true,
+        }
+    }
+
+    fn data() -> Directory {
+        Directory {
+            users: HashMap::from([(1, user("Alice")), (2, user("Bob"))]),
+            teams: BTreeMap::from([
+                ("a".to_string(), vec![1]),
+                ("b".to_string(), vec![2]),
+            ]),
+            admins: HashSet::from([1]),
+        }
+    }
+
+    fn counter() -> (Arc<AtomicUsize>, impl Fn() -> usize) {
+        let count = Arc::new(AtomicUsize::new(0));
+        let read = {
+            let count = Arc::clone(&count);
+            move || count.load(Ordering::Relaxed)
+        };
+        (count, read)
+    }
+
+    #[tokio::test]
+    async fn updating_a_key_notifies_only_that_key() {
+        _ = any_spawner::Executor::init_tokio();
+
+        let store = Store::new(data());
+        let (alice, alice_runs) = counter();
+        let (bob, bob_runs) = counter();
+        let (keys, keys_runs) = counter();
+
+        Effect::new_sync(move || {
+            _ = store.users().at_key(1).name().get();
+            alice.fetch_add(1, Ordering::Relaxed);
+        });
+        Effect::new_sync(move || {
+            _ = store.users().at_key(2).read();
+            bob.fetch_add(1, Ordering::Relaxed);
+        });
+        Effect::new_sync(move || {
+            _ = store.users().iter_keyed().count();
+            keys.fetch_add(1, Ordering::Relaxed);
+        });
+        tick().await;
+        assert_eq!((alice_runs(), bob_runs(), keys_runs()), (1, 1, 1));
+
+        store.users().at_key(1).name().set("Carol".to_string());
+        tick().await;
+        assert_eq!((alice_runs(), bob_runs(), keys_runs()), (2, 1, 1));
+
+        store.users().at_key(2).update(|user| user.active = false);
+        tick().await;
+        assert_eq!((alice_runs(), bob_runs(), keys_runs()), (2, 2, 1));
+        assert_eq!(
+            store.users().get_untracked()[&1],
+            User {
+                name: "Carol".to_string(),
+                active: true
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn insert_and_remove_notify_the_key_and_the_key_set() {
+        _ = any_spawner::Executor::init_tokio();
+
+        let store = Store::new(data());
+        let (alice, alice_runs) = counter();
+        let (dave, dave_runs) = counter();
+        let (keys, keys_runs) = counter();
+
+        Effect::new_sync(move || {
+            _ = store.users().at_key(1).try_read().is_some();
+            alice.fetch_add(1, Ordering::Relaxed);
+        });
+        Effect::new_sync(move || {
+            _ = store.users().at_key(4).try_read().is_some();
+            dave.fetch_add(1, Ordering::Relaxed);
+        });
+        Effect::new_sync(move || {
+            _ = store.users().contains_key(&4);
+            keys.fetch_add(1, Ordering::Relaxed);
+        });
+        tick().await;
+        assert_eq!((alice_runs(), dave_runs(), keys_runs()), (1, 1, 1));
+
+        assert_eq!(store.users().insert(4, user("Dave")), None);
+        tick().await;
+        assert_eq!((alice_runs(), dave_runs(), keys_runs()), (1, 2, 2));
+        assert!(store.users().contains_key(&4));
+        assert_eq!(store.users().at_key(4).name().get_untracked(), "Dave");
+
+        // replacing the value at an existing key doesn't change the key set
+        assert!(store.users().insert(4, user("Dan")).is_some());
+        tick().await;
+        assert_eq!((alice_runs(), dave_runs(), keys_runs()), (1, 3, 2));
+
+        assert!(store.users().remove(&4).is_some());
+        assert!(store.users().remove(&4).is_none());
+        tick().await;
+        assert_eq!((alice_runs(), dave_runs(), keys_runs()), (1, 4, 3));
+        assert!(store.users().at_key(4).try_read().is_none());
+
+        // replacing the whole map notifies every key
+        store.users().set(HashMap::new());
+        tick().await;
+        assert_eq!((alice_runs(), dave_runs(), keys_runs()), (2, 5, 4));
+    }
+
+    #[tokio::test]
+    async fn iterating_a_map_yields_keyed_fields() {
+        _ = any_spawner::Executor::init_tokio();
+
+        let store = Store::new(data());
+        let names = store
+            .teams()
+            .iter_keyed()
+            .map(|team| (team.key().clone(), team.get_untracked()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            [("a".to_string(), vec![1]), ("b".to_string(), vec![2])]
+        );
+
+        store.teams().at_key("b".to_string()).write().push(3);
+        assert_eq!(store.teams().read()["b"], [2, 3]);
+    }
+
+    #[tokio::test]
+    async fn set_items_are_tracked_separately() {
+        _ = any_spawner::Executor::init_tokio();
+
+        let store = Store::new(data());
+        let (one, one_runs) = counter();
+        let (two, two_runs) = counter();
+        let (all, all_runs) = counter();
+
+        Effect::new_sync(move || {
+            _ = store.admins().contains(&1);
+            one.fetch_add(1, Ordering::Relaxed);
+        });
+        Effect::new_sync(move || {
+            _ = store.admins().contains(&2);
+            two.fetch_add(1, Ordering::Relaxed);
+        });
+        Effect::new_sync(move || {
+            _ = store.admins().read().len();
+            all.fetch_add(1, Ordering::Relaxed);
+        });
+        tick().await;
+        assert_eq!((one_runs(), two_runs(), all_runs()), (1, 1, 1));
+
+        assert!(store.admins().insert(2));
+        assert!(!store.admins().insert(2));
+        tick().await;
+        assert_eq!((one_runs(), two_runs(), all_runs()), (1, 2, 2));
+        assert!(store.admins().contains(&2));
+
+        assert!(store.admins().remove(&1));
+        tick().await;
+        assert_eq!((one_runs(), two_runs(), all_runs()), (2, 2, 3));
+        assert!(!store.admins().contains(&1));
+    }
+
+    #[tokio::test]
+    async fn patching_maps_notifies_changed_keys() {
+        _ = any_spawner::Executor::init_tokio();
+
+        let store = Store::new(data());
+        let (alice, alice_runs) = counter();
+        let (bob, bob_runs) = counter();
+        let (keys, keys_runs) = counter();
+        let (admin, admin_runs) = counter();
+
+        Effect::new_sync(move || {
+            _ = store.users().at_key(1).name().read();
+            alice.fetch_add(1, Ordering::Relaxed);
+        });
+        Effect::new_sync(move || {
+            _ = store.users().at_key(2).name().read();
+            bob.fetch_add(1, Ordering::Relaxed);
+        });
+        Effect::new_sync(move || {
+            _ = store.users().iter_keyed().count();
+            keys.fetch_add(1, Ordering::Relaxed);
+        });
+        Effect::new_sync(move || {
+            _ = store.admins().contains(&1);
+            admin.fetch_add(1, Ordering::Relaxed);
+        });
+        tick().await;
+
+        let mut new = data();
+        new.users.get_mut(&2).unwrap().name = "Robert".to_string();
+        new.admins.insert(3);
+        store.patch(new.clone());
+        tick().await;
+        assert_eq!(
+            (alice_runs(), bob_runs(), keys_runs(), admin_runs()),
+            (1, 2, 1, 1)
+        );
+
+        new.users.insert(3, user("Carol"));
+        new.admins.remove(&1);
+        store.patch(new.clone());
+        tick().await;
+        assert_eq!(
+            (alice_runs(), bob_runs(), keys_runs(), admin_runs()),
+            (1, 2, 2, 2)
+        );
+        assert_eq!(*store.read(), new);
+    }
+
+    #[cfg(feature = "indexmap")]
+    #[tokio::test]
+    async fn patching_an_index_map_keeps_the_new_order() {
+        use indexmap::IndexMap;
+
+        _ = any_spawner::Executor::init_tokio();
+
+        #[derive(Debug, Store, Patch)]
+        struct Ordered {
+            entries: IndexMap<u32, String>,
+        }
+
+        let store = Store::new(Ordered {
+            entries: IndexMap::from([
+                (1, "a".to_string()),
+                (2, "b".to_string()),
+                (3, "c".to_string()),
+            ]),
+        });
+        let (keys, keys_runs) = counter();
+        let (one, one_runs) = counter();
+
+        Effect::new_sync(move || {
+            _ = store.entries().iter_keyed().count();
+            keys.fetch_add(1, Ordering::Relaxed);
+        });
+        Effect::new_sync(move || {
+            _ = store.entries().at_key(1).read();
+            one.fetch_add(1, Ordering::Relaxed);
+        });
+        tick().await;
+
+        store.patch(Ordered {
+            entries: IndexMap::from([
+                (3, "c".to_string()),
+                (1, "a".to_string()),
+                (4, "d".to_string()),
+            ]),
+        });
+        tick().await;
+        assert_eq!((keys_runs(), one_runs()), (2, 1));
+        assert_eq!(
+            store
+                .entries()
+                .iter_keyed()
+                .map(|entry| *entry.key())
+                .collect::<Vec<_>>(),
+            [3, 1, 4]
+        );
+
+        // removing a key keeps the order of the others
+        store.entries().remove(&3);
+        assert_eq!(store.entries().read().keys().collect::<Vec<_>>(), [&1, &4]);
+    }
+}
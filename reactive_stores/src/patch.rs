@@ -1,8 +1,14 @@
-use crate::{path::StorePath, StoreField};
+use crate::{
+    map::{key_segment, KEY_SET},
+    path::StorePath,
+    StoreField,
+};
 use itertools::{EitherOrBoth, Itertools};
 use reactive_graph::traits::{Notify, UntrackableGuard};
 use std::{
     borrow::Cow,
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    hash::{BuildHasher, Hash},
     net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6},
     num::{
         NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8,
@@ -195,6 +201,175 @@ where
     }
 }
 
+macro_rules! patch_map {
+    (<$($generics:ident),*> $ty:ty where $($bounds:tt)*) => {
+        impl<$($generics),*> PatchField for $ty
+        where
+            $($bounds)*
+        {
+            fn patch_field(
+                &mut self,
+                mut new: Self,
+                path: &StorePath,
+                notify: &mut dyn FnMut(&StorePath),
+            ) {
+                let mut keys_changed = false;
+                let mut new_path = path.to_owned();
+                new_path.push(KEY_SET);
+
+                self.retain(|key, old| {
+                    new_path.replace_last(key_segment(key));
+                    match new.remove(key) {
+                        Some(new) => {
+                            old.patch_field(new, &new_path, notify);
+                            true
+                        }
+                        None => {
+                            notify(&new_path);
+                            keys_changed = true;
+                            false
+                        }
+                    }
+                });
+                for (key, value) in new {
+                    new_path.replace_last(key_segment(&key));
+                    notify(&new_path);
+                    self.insert(key, value);
+                    keys_changed = true;
+                }
+
+                if keys_changed {
+                    new_path.replace_last(KEY_SET);
+                    notify(&new_path);
+                }
+            }
+        }
+    };
+}
+
+patch_map!(<K, V, S> HashMap<K, V, S> where K: Hash + Eq, V: PatchField, S: BuildHasher);
+patch_map!(<K, V> BTreeMap<K, V> where K: Hash + Ord, V: PatchField);
+
+#[cfg(feature = "indexmap")]
+impl<K, V, S> PatchField for indexmap::IndexMap<K, V, S>
+where
+    K: Hash + Eq,
+    V: PatchField,
+    S: BuildHasher,
+{
+    fn patch_field(
+        &mut self,
+        new: Self,
+        path: &StorePath,
+        notify: &mut dyn FnMut(&StorePath),
+    ) {
+        // the order of the entries is part of the key set, because they are iterated in order
+        let mut keys_changed = false;
+        let mut new_path = path.to_owned();
+        new_path.push(KEY_SET);
+
+        let new_len = new.len();
+        for (idx, (key, value)) in new.into_iter().enumerate() {
+            new_path.replace_last(key_segment(&key));
+            match self.get_index_of(&key) {
+                Some(old_idx) => {
+                    if old_idx != idx {
+                        self.move_index(old_idx, idx);
+                        keys_changed = true;
+                    }
+                    self[idx].patch_field(value, &new_path, notify);
+                }
+                None => {
+                    self.shift_insert(idx, key, value);
+                    notify(&new_path);
+                    keys_changed = true;
+                }
+            }
+        }
+        // any entries after the new ones are no longer in the map
+        while self.len() > new_len {
+            if let Some((key, _)) = self.pop() {
+                new_path.replace_last(key_segment(&key));
+                notify(&new_path);
+                keys_changed = true;
+            }
+        }
+
+        if keys_changed {
+            new_path.replace_last(KEY_SET);
+            notify(&new_path);
+        }
+    }
+}
+
+macro_rules! patch_set {
+    (<$($generics:ident),*> $ty:ty where $($bounds:tt)*) => {
+        impl<$($generics),*> PatchField for $ty
+        where
+            $($bounds)*
+        {
+            fn patch_field(
+                &mut self,
+                new: Self,
+                path: &StorePath,
+                notify: &mut dyn FnMut(&StorePath),
+            ) {
+                let mut keys_changed = false;
+                let mut new_path = path.to_owned();
+                new_path.push(KEY_SET);
+
+                let removed = self.iter().filter(|item| !new.contains(*item));
+                let added = new.iter().filter(|item| !self.contains(*item));
+                for item in removed.chain(added) {
+                    new_path.replace_last(key_segment(item));
+                    notify(&new_path);
+                    keys_changed = true;
+                }
+
+                if keys_changed {
+                    *self = new;
+                    new_path.replace_last(KEY_SET);
+                    notify(&new_path);
+                }
+            }
+        }
+    };
+}
+
+patch_set!(<T, S> HashSet<T, S> where T: Hash + Eq, S: BuildHasher);
+patch_set!(<T> BTreeSet<T> where T: Hash + Ord);
+
+#[cfg(feature = "indexmap")]
+impl<T, S> PatchField for indexmap::IndexSet<T, S>
+where
+    T: Hash + Eq,
+    S: BuildHasher,
+{
+    fn patch_field(
+        &mut self,
+        new: Self,
+        path: &StorePath,
+        notify: &mut dyn FnMut(&StorePath),
+    ) {
+        let mut new_path = path.to_owned();
+        new_path.push(KEY_SET);
+
+        let removed = self.iter().filter(|item| !new.contains(*item));
+        let added = new.iter().filter(|item| !self.contains(*item));
+        for item in removed.chain(added) {
+            new_path.replace_last(key_segment(item));
+            notify(&new_path);
+        }
+
+        // the order of the items is part of the key set, because they are iterated in order
+        if !self.iter().eq(new.iter()) {
+            *self = new;
+            new_path.replace_last(KEY_SET);
+            notify(&new_path);
+        }
+    }
+}
+
 macro_rules! patch_tuple {
 	($($ty:ident),*) => {
 		impl<$($ty),*> PatchField for ($($ty,)*)
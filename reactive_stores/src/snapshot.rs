@@ -0,0 +1,147 @@
+use crate::{Patch, PatchField, StoreField};
+use std::{ops::Deref, sync::Arc};
+
+/// A copy of the value of a store or field at some point in time, which can be
+/// [restored](StoreSnapshot::restore) later.
+///
+/// Snapshots are reference-counted, so they are cheap to clone and keep around.
+#[derive(Debug)]
+pub struct Snapshot<T>(Arc<T>);
+
+impl<T> Clone for Snapshot<T> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<T> Deref for Snapshot<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> PartialEq for Snapshot<T>
+where
+    T: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<T> Eq for Snapshot<T> where T: Eq {}
+
+/// Allows taking a [`Snapshot`] of a store or field, and restoring it later.
+pub trait StoreSnapshot {
+    /// The type of the value in the snapshot.
+    type Value;
+
+    /// Takes a snapshot of the current value, or returns `None` if the field no longer exists.
+    fn snapshot(&self) -> Option<Snapshot<Self::Value>>;
+
+    /// Sets the value back to a snapshot.
+    ///
+    /// The snapshot is applied with [`Patch::patch`], so only the fields that differ from the
+    /// snapshot are notified.
+    fn restore(&self, snapshot: &Snapshot<Self::Value>);
+}
+
+impl<T> StoreSnapshot for T
+where
+    T: StoreField,
+    T::Value: Clone + PatchField,
+{
+    type Value = T::Value;
+
+    fn snapshot(&self) -> Option<Snapshot<Self::Value>> {
+        self.reader().map(|value| Snapshot(Arc::new(value.clone())))
+    }
+
+    fn restore(&self, snapshot: &Snapshot<Self::Value>) {
+        self.patch(T::Value::clone(snapshot));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StoreSnapshot;
+    use crate::{self as reactive_stores, tests::tick, Patch, Store};
+    use reactive_graph::{
+        effect::Effect,
+        traits::{GetUntracked, Read, Set, Update},
+    };
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    #[derive(Debug, Store, Patch, Clone, Default, PartialEq)]
+    struct Settings {
+        theme: String,
+        font_size: u32,
+        recent: Vec<String>,
+    }
+
+    #[tokio::test]
+    async fn restoring_a_snapshot_notifies_only_changed_fields() {
+        _ = any_spawner::Executor::init_tokio();
+
+        let store = Store::new(Settings {
+            theme: "light".to_string(),
+            font_size: 12,
+            recent: vec!["a.txt".to_string()],
+        });
+        let snapshot = store.snapshot().unwrap();
+
+        let theme_runs = Arc::new(AtomicUsize::new(0));
+        let font_size_runs = Arc::new(AtomicUsize::new(0));
+        Effect::new_sync({
+            let theme_runs = Arc::clone(&theme_runs);
+            move |_| {
+                _ = store.theme().read();
+                theme_runs.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+        Effect::new_sync({
+            let font_size_runs = Arc::clone(&font_size_runs);
+            move |_| {
+                _ = store.font_size().read();
+                font_size_runs.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+        tick().await;
+
+        store.theme().set("dark".to_string());
+        store
+            .recent()
+            .update(|recent| recent.push("b.txt".to_string()));
+        tick().await;
+        assert_eq!(theme_runs.load(Ordering::Relaxed), 2);
+        assert_eq!(font_size_runs.load(Ordering::Relaxed), 1);
+
+        store.restore(&snapshot);
+        tick().await;
+        assert_eq!(store.get_untracked(), *snapshot);
+        assert_eq!(theme_runs.load(Ordering::Relaxed), 3);
+        assert_eq!(font_size_runs.load(Ordering::Relaxed), 1);
+    }
+
+    #[tokio::test]
+    async fn subfields_can_be_snapshotted() {
+        _ = any_spawner::Executor::init_tokio();
+
+        let store = Store::new(Settings::default());
+        let recent = store.recent().snapshot().unwrap();
+
+        store
+            .recent()
+            .update(|recent| recent.push("a.txt".to_string()));
+        store.font_size().set(14);
+        store.recent().restore(&recent);
+
+        assert!(store.recent().get_untracked().is_empty());
+        assert_eq!(store.font_size().get_untracked(), 14);
+    }
+}
@@ -132,7 +132,11 @@ where
     #[track_caller]
     fn get_trigger(&self, path: StorePath) -> StoreFieldTrigger {
         let triggers = &self.signals;
+        #[cfg(feature = "changefeed")]
+        let changed_path = path.clone();
         let trigger = triggers.write().or_poisoned().get_or_insert(path);
+        #[cfg(feature = "changefeed")]
+        self.changes.watch(&changed_path, &trigger);
         trigger
     }
 
@@ -26,8 +26,8 @@ use wasm_bindgen::JsValue;
 use {
     reactive_graph::owner::Storage,
     reactive_stores::{
-        ArcField, AtIndex, AtKeyed, DerefedField, Field, KeyedSubfield,
-        StoreField, Subfield,
+        ArcField, AtIndex, AtKeyed, DerefedField, Field, FormField,
+        KeyedSubfield, StoreField, Subfield,
     },
     std::ops::{Deref, DerefMut, IndexMut},
 };
@@ -458,6 +458,20 @@ where
     }
 }
 
+#[cfg(feature = "reactive_stores")]
+impl<F, T> IntoSplitSignal for FormField<F>
+where
+    Self: Get<Value = T> + Set<Value = T> + Clone,
+{
+    type Value = T;
+    type Read = Self;
+    type Write = Self;
+
+    fn into_split_signal(self) -> (Self::Read, Self::Write) {
+        (self.clone(), self.clone())
+    }
+}
+
 /// Returns self from an event target.
 pub trait FromEventTarget {
     /// Returns self from an event target.
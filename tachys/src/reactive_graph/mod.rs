@@ -23,12 +23,26 @@ mod inner_html;
 pub mod node_ref;
 mod owned;
 mod property;
+/// Renders lists from a [`SignalVec`](reactive_graph::signal::SignalVec) by applying its diffs.
+pub mod signal_vec;
 mod style;
 mod suspense;
 
 pub use owned::*;
 pub use suspense::*;
 
+/// Creates the span for one rebuild of a reactive view or attribute, tagged with the type being
+/// rebuilt and the owner it is rebuilt in.
+#[cfg(feature = "tracing")]
+fn rebuild_span<V>(_value: &V) -> tracing::Span {
+    tracing::trace_span!(
+        "rebuild",
+        view = std::any::type_name::<V>(),
+        owner = reactive_graph::owner::Owner::current()
+            .map(|owner| owner.debug_id())
+    )
+}
+
 impl<F, V> ToTemplate for F
 where
     F: ReactiveFunction<Output = V>,
@@ -65,6 +79,8 @@ where
                 .map(|h| throw_error::set_error_hook(Arc::clone(h)));
             let value = self.invoke();
             if let Some(mut state) = prev {
+                #[cfg(feature = "tracing")]
+                let _span = rebuild_span(&value).entered();
                 value.rebuild(&mut state);
                 state
             } else {
@@ -222,6 +238,8 @@ where
 
             let value = self.invoke();
             if let Some(mut state) = prev {
+                #[cfg(feature = "tracing")]
+                let _span = rebuild_span(&value).entered();
                 value.rebuild(&mut state);
                 state
             } else {
@@ -270,6 +288,8 @@ where
 
                     let value = fun.invoke();
                     if let Some(mut state) = prev {
+                        #[cfg(feature = "tracing")]
+                        let _span = rebuild_span(&value).entered();
                         value.rebuild(&mut state);
                         state
                     } else {
@@ -416,6 +436,8 @@ where
         RenderEffect::new(move |prev| {
             let value = self.invoke();
             if let Some(mut state) = prev {
+                #[cfg(feature = "tracing")]
+                let _span = rebuild_span(&value).entered();
                 value.rebuild(&key, &mut state);
                 state
             } else {
@@ -436,6 +458,8 @@ where
         RenderEffect::new(move |prev| {
             let value = self.invoke();
             if let Some(mut state) = prev {
+                #[cfg(feature = "tracing")]
+                let _span = rebuild_span(&value).entered();
                 value.rebuild(&key, &mut state);
                 state
             } else {
@@ -453,6 +477,8 @@ where
             move |prev| {
                 let value = self.invoke();
                 if let Some(mut state) = prev {
+                    #[cfg(feature = "tracing")]
+                    let _span = rebuild_span(&value).entered();
                     value.rebuild(&key, &mut state);
                     state
                 } else {
@@ -548,6 +574,8 @@ where
                 let value = self.inner.await;
                 let mut state = state.borrow_mut();
                 if let Some(state) = state.as_mut() {
+                    #[cfg(feature = "tracing")]
+                    let _span = rebuild_span(&value).entered();
                     value.rebuild(&key, state);
                 }
                 self.subscriber.forward();
@@ -0,0 +1,560 @@
+use super::RenderEffectState;
+use crate::{
+    html::attribute::{any_attribute::AnyAttribute, Attribute},
+    hydration::Cursor,
+    renderer::{CastFrom, Rndr},
+    ssr::StreamBuilder,
+    view::{
+        add_attr::AddAnyAttr, MarkBranch, Mountable, Position, PositionState,
+        Render, RenderHtml,
+    },
+};
+use reactive_graph::{
+    effect::RenderEffect,
+    signal::{ArcSignalVec, DiffStream, VecDiff},
+    traits::{GetUntracked, Track},
+};
+
+/// Creates a list of views from an [`ArcSignalVec`], which is updated by applying each change
+/// made to the list directly to the rendered views.
+///
+/// Unlike a [`keyed`](crate::view::keyed::keyed) list, this never compares the old and new
+/// lists: inserting, removing, or moving one item only creates, removes, or moves the view for
+/// that item. Updating the value at an index rebuilds the view at that index with the new value.
+pub fn signal_vec<T, VF, V>(
+    items: impl Into<ArcSignalVec<T>>,
+    view_fn: VF,
+) -> SignalVecView<T, VF>
+where
+    T: Clone + Send + Sync + 'static,
+    VF: Fn(T) -> V + Send + 'static,
+    V: Render,
+{
+    SignalVecView {
+        items: items.into(),
+        view_fn,
+    }
+}
+
+/// A list of views, rendered from an [`ArcSignalVec`].
+pub struct SignalVecView<T, VF> {
+    items: ArcSignalVec<T>,
+    view_fn: VF,
+}
+
+/// Retained view state for the items in a [`SignalVecView`].
+pub struct SignalVecState<S> {
+    // the element the list is mounted to, or `None` while it is not mounted, in which case new
+    // items are only mounted once the whole list is
+    parent: Option<crate::renderer::types::Element>,
+    marker: crate::renderer::types::Placeholder,
+    rendered_items: Vec<S>,
+}
+
+impl<S> SignalVecState<S>
+where
+    S: Mountable,
+{
+    fn apply_all<T, V>(
+        &mut self,
+        diffs: &mut DiffStream<VecDiff<T>>,
+        view_fn: &impl Fn(T) -> V,
+    ) where
+        V: Render<State = S>,
+    {
+        while let Some(diff) = diffs.try_next() {
+            self.apply(diff, view_fn);
+        }
+    }
+
+    fn apply<T, V>(&mut self, diff: VecDiff<T>, view_fn: &impl Fn(T) -> V)
+    where
+        V: Render<State = S>,
+    {
+        match diff {
+            VecDiff::Replace { values } => {
+                self.clear();
+                for value in values {
+                    let index = self.rendered_items.len();
+                    self.insert(index, view_fn(value).build());
+                }
+            }
+            VecDiff::InsertAt { index, value } => {
+                self.insert(index, view_fn(value).build());
+            }
+            VecDiff::UpdateAt { index, value } => {
+                view_fn(value).rebuild(&mut self.rendered_items[index]);
+            }
+            VecDiff::RemoveAt { index } => {
+                self.rendered_items.remove(index).unmount();
+            }
+            VecDiff::Move {
+                old_index,
+                new_index,
+            } => {
+                // the item is moved in the DOM by mounting it again, without unmounting it
+                let item = self.rendered_items.remove(old_index);
+                self.insert(new_index, item);
+            }
+            VecDiff::Push { value } => {
+                let index = self.rendered_items.len();
+                self.insert(index, view_fn(value).build());
+            }
+            VecDiff::Pop => {
+                if let Some(mut item) = self.rendered_items.pop() {
+                    item.unmount();
+                }
+            }
+            VecDiff::Clear => self.clear(),
+        }
+    }
+
+    fn insert(&mut self, index: usize, mut item: S) {
+        if let Some(parent) = &self.parent {
+            match self.rendered_items.get(index) {
+                Some(next) => next.insert_before_this_or_marker(
+                    parent,
+                    &mut item,
+                    Some(self.marker.as_ref()),
+                ),
+                None => {
+                    item.try_mount(parent, Some(self.marker.as_ref()));
+                }
+            }
+        }
+        self.rendered_items.insert(index, item);
+    }
+
+    fn clear(&mut self) {
+        for mut item in self.rendered_items.drain(..) {
+            item.unmount();
+        }
+    }
+}
+
+impl<T, VF, V> Render for SignalVecView<T, VF>
+where
+    T: Clone + Send + Sync + 'static,
+    VF: Fn(T) -> V + Send + 'static,
+    V: Render,
+    V::State: 'static,
+{
+    type State = RenderEffectState<SignalVecState<V::State>>;
+
+    fn build(self) -> Self::State {
+        let SignalVecView { items, view_fn } = self;
+        let mut diffs = items.diffs();
+        RenderEffect::new(move |prev| {
+            items.track();
+            // the first change in the stream is a snapshot of the whole list
+            let mut state = prev.unwrap_or_else(|| SignalVecState {
+                parent: None,
+                marker: Rndr::create_placeholder(),
+                rendered_items: Vec::new(),
+            });
+            state.apply_all(&mut diffs, &view_fn);
+            state
+        })
+        .into()
+    }
+
+    fn rebuild(self, state: &mut Self::State) {
+        let new = self.build();
+        let mut old = std::mem::replace(state, new);
+        old.insert_before_this(state);
+        old.unmount();
+    }
+}
+
+impl<T, VF, V> AddAnyAttr for SignalVecView<T, VF>
+where
+    T: Clone + Send + Sync + 'static,
+    VF: Fn(T) -> V + Send + 'static,
+    V: RenderHtml + 'static,
+{
+    type Output<SomeNewAttr: Attribute> = SignalVecView<
+        T,
+        Box<
+            dyn Fn(T) -> <V as AddAnyAttr>::Output<SomeNewAttr::CloneableOwned>
+                + Send,
+        >,
+    >;
+
+    fn add_any_attr<NewAttr: Attribute>(
+        self,
+        attr: NewAttr,
+    ) -> Self::Output<NewAttr>
+    where
+        Self::Output<NewAttr>: RenderHtml,
+    {
+        let SignalVecView { items, view_fn } = self;
+        let attr = attr.into_cloneable_owned();
+        SignalVecView {
+            items,
+            view_fn: Box::new(move |item| {
+                view_fn(item).add_any_attr(attr.clone())
+            }),
+        }
+    }
+}
+
+impl<T, VF, V> RenderHtml for SignalVecView<T, VF>
+where
+    T: Clone + Send + Sync + 'static,
+    VF: Fn(T) -> V + Send + 'static,
+    V: RenderHtml + 'static,
+    V::State: 'static,
+{
+    type AsyncOutput = Self;
+    type Owned = Self;
+
+    const MIN_LENGTH: usize = 0;
+
+    fn dry_resolve(&mut self) {
+        // the rows are only created when the list is rendered, so there is nothing to resolve
+    }
+
+    async fn resolve(self) -> Self::AsyncOutput {
+        self
+    }
+
+    fn to_html_with_buf(
+        self,
+        buf: &mut String,
+        position: &mut Position,
+        escape: bool,
+        mark_branches: bool,
+        extra_attrs: Vec<AnyAttribute>,
+    ) {
+        if mark_branches && escape {
+            buf.open_branch("for");
+        }
+        for value in self.items.get_untracked() {
+            if mark_branches && escape {
+                buf.open_branch("item");
+            }
+            (self.view_fn)(value).to_html_with_buf(
+                buf,
+                position,
+                escape,
+                mark_branches,
+                extra_attrs.clone(),
+            );
+            if mark_branches && escape {
+                buf.close_branch("item");
+            }
+            *position = Position::NextChild;
+        }
+        if mark_branches && escape {
+            buf.close_branch("for");
+        }
+        buf.push_str("<!>");
+    }
+
+    fn to_html_async_with_buf<const OUT_OF_ORDER: bool>(
+        self,
+        buf: &mut StreamBuilder,
+        position: &mut Position,
+        escape: bool,
+        mark_branches: bool,
+        extra_attrs: Vec<AnyAttribute>,
+    ) {
+        if mark_branches && escape {
+            buf.open_branch("for");
+        }
+        for value in self.items.get_untracked() {
+            if mark_branches && escape {
+                buf.open_branch("item");
+            }
+            (self.view_fn)(value).to_html_async_with_buf::<OUT_OF_ORDER>(
+                buf,
+                position,
+                escape,
+                mark_branches,
+                extra_attrs.clone(),
+            );
+            if mark_branches && escape {
+                buf.close_branch("item");
+            }
+            *position = Position::NextChild;
+        }
+        if mark_branches && escape {
+            buf.close_branch("for");
+        }
+        buf.push_sync("<!>");
+    }
+
+    fn hydrate<const FROM_SERVER: bool>(
+        self,
+        cursor: &Cursor,
+        position: &PositionState,
+    ) -> Self::State {
+        // get parent and position
+        let current = cursor.current();
+        let parent = if position.get() == Position::FirstChild {
+            current
+        } else {
+            Rndr::get_parent(&current)
+                .expect("first child of signal vec has no parent")
+        };
+        let parent = crate::renderer::types::Element::cast_from(parent)
+            .expect("parent of signal vec should be an element");
+
+        // hydrate the snapshot at the start of the stream
+        let SignalVecView { items, view_fn } = self;
+        let mut diffs = items.diffs();
+        let values = match diffs.try_next() {
+            Some(VecDiff::Replace { values }) => values,
+            _ => unreachable!("a diff stream always begins with a snapshot"),
+        };
+        let rendered_items = values
+            .into_iter()
+            .map(|value| {
+                view_fn(value).hydrate::<FROM_SERVER>(cursor, position)
+            })
+            .collect();
+        let marker = cursor.next_placeholder(position);
+        position.set(Position::NextChild);
+
+        let state = SignalVecState {
+            parent: Some(parent),
+            marker,
+            rendered_items,
+        };
+        RenderEffect::new_with_value(
+            move |prev| {
+                items.track();
+                let mut state =
+                    prev.expect("signal vec state should have been hydrated");
+                state.apply_all(&mut diffs, &view_fn);
+                state
+            },
+            Some(state),
+        )
+        .into()
+    }
+
+    fn into_owned(self) -> Self::Owned {
+        self
+    }
+}
+
+impl<S> Mountable for SignalVecState<S>
+where
+    S: Mountable,
+{
+    fn mount(
+        &mut self,
+        parent: &crate::renderer::types::Element,
+        marker: Option<&crate::renderer::types::Node>,
+    ) {
+        self.parent = Some(parent.clone());
+        for item in self.rendered_items.iter_mut() {
+            item.mount(parent, marker);
+        }
+        self.marker.mount(parent, marker);
+    }
+
+    fn unmount(&mut self) {
+        self.parent = None;
+        for item in self.rendered_items.iter_mut() {
+            item.unmount();
+        }
+        self.marker.unmount();
+    }
+
+    fn insert_before_this(&self, child: &mut dyn Mountable) -> bool {
+        self.rendered_items
+            .first()
+            .map(|item| item.insert_before_this(child))
+            .unwrap_or_else(|| self.marker.insert_before_this(child))
+    }
+
+    fn elements(&self) -> Vec<crate::renderer::types::Element> {
+        self.rendered_items
+            .iter()
+            .flat_map(|item| item.elements())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SignalVecState;
+    use crate::{
+        renderer::types::{Element, Node, Placeholder},
+        view::{Mountable, Render},
+    };
+    use reactive_graph::signal::{ArcSignalVec, DiffStream, VecDiff};
+    use std::cell::{Cell, RefCell};
+    use wasm_bindgen::{JsCast, JsValue};
+
+    thread_local! {
+        // the values of the rows that are currently mounted, in order
+        static MOUNTED: RefCell<Vec<i32>> = const { RefCell::new(Vec::new()) };
+        // the value of the row that the next row is mounted before, if any
+        static BEFORE: Cell<Option<i32>> = const { Cell::new(None) };
+    }
+
+    fn mounted() -> Vec<i32> {
+        MOUNTED.with_borrow(Clone::clone)
+    }
+
+    // a DOM node that is only passed to the mock rows, which never touch it
+    fn node<T: JsCast>() -> T {
+        JsValue::NULL.unchecked_into()
+    }
+
+    struct RowView(i32);
+
+    struct Row(i32);
+
+    impl Render for RowView {
+        type State = Row;
+
+        fn build(self) -> Self::State {
+            Row(self.0)
+        }
+
+        fn rebuild(self, state: &mut Self::State) {
+            MOUNTED.with_borrow_mut(|mounted| {
+                if let Some(row) =
+                    mounted.iter_mut().find(|row| **row == state.0)
+                {
+                    *row = self.0;
+                }
+            });
+            state.0 = self.0;
+        }
+    }
+
+    impl Mountable for Row {
+        fn unmount(&mut self) {
+            MOUNTED.with_borrow_mut(|mounted| {
+                mounted.retain(|row| *row != self.0)
+            });
+        }
+
+        fn mount(&mut self, _parent: &Element, _marker: Option<&Node>) {
+            MOUNTED.with_borrow_mut(|mounted| {
+                mounted.retain(|row| *row != self.0);
+                let index = BEFORE
+                    .take()
+                    .and_then(|before| {
+                        mounted.iter().position(|row| *row == before)
+                    })
+                    .unwrap_or(mounted.len());
+                mounted.insert(index, self.0);
+            });
+        }
+
+        fn insert_before_this(&self, child: &mut dyn Mountable) -> bool {
+            BEFORE.set(Some(self.0));
+            child.mount(&node(), None);
+            true
+        }
+
+        fn elements(&self) -> Vec<Element> {
+            vec![]
+        }
+    }
+
+    // a list of rows that is mounted, and has rendered the snapshot of `items`
+    fn mounted_list(
+        items: &ArcSignalVec<i32>,
+    ) -> (SignalVecState<Row>, DiffStream<VecDiff<i32>>) {
+        MOUNTED.with_borrow_mut(Vec::clear);
+        let mut state = SignalVecState {
+            parent: Some(node()),
+            marker: node::<Placeholder>(),
+            rendered_items: Vec::new(),
+        };
+        let mut diffs = items.diffs();
+        state.apply_all(&mut diffs, &RowView);
+        (state, diffs)
+    }
+
+    fn rendered(state: &SignalVecState<Row>) -> Vec<i32> {
+        state.rendered_items.iter().map(|row| row.0).collect()
+    }
+
+    #[test]
+    fn snapshot_mounts_every_row() {
+        let items = ArcSignalVec::new(vec![1, 2, 3]);
+        let (state, _diffs) = mounted_list(&items);
+        assert_eq!(rendered(&state), [1, 2, 3]);
+        assert_eq!(mounted(), [1, 2, 3]);
+    }
+
+    #[test]
+    fn push_and_insert_mount_rows_in_place() {
+        let items = ArcSignalVec::new(vec![1, 2]);
+        let (mut state, mut diffs) = mounted_list(&items);
+
+        items.push(4);
+        items.insert(2, 3);
+        items.insert(0, 0);
+        state.apply_all(&mut diffs, &RowView);
+        assert_eq!(rendered(&state), [0, 1, 2, 3, 4]);
+        assert_eq!(mounted(), [0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn remove_pop_and_clear_unmount_rows() {
+        let items = ArcSignalVec::new(vec![1, 2, 3, 4]);
+        let (mut state, mut diffs) = mounted_list(&items);
+
+        items.remove(1);
+        items.pop();
+        state.apply_all(&mut diffs, &RowView);
+        assert_eq!(rendered(&state), [1, 3]);
+        assert_eq!(mounted(), [1, 3]);
+
+        items.clear();
+        state.apply_all(&mut diffs, &RowView);
+        assert!(rendered(&state).is_empty());
+        assert!(mounted().is_empty());
+    }
+
+    #[test]
+    fn move_item_moves_the_existing_row() {
+        let items = ArcSignalVec::new(vec![1, 2, 3, 4]);
+        let (mut state, mut diffs) = mounted_list(&items);
+
+        items.move_item(0, 3);
+        state.apply_all(&mut diffs, &RowView);
+        assert_eq!(rendered(&state), [2, 3, 4, 1]);
+        assert_eq!(mounted(), [2, 3, 4, 1]);
+
+        items.move_item(2, 0);
+        state.apply_all(&mut diffs, &RowView);
+        assert_eq!(rendered(&state), [4, 2, 3, 1]);
+        assert_eq!(mounted(), [4, 2, 3, 1]);
+    }
+
+    #[test]
+    fn set_at_rebuilds_the_row_in_place() {
+        let items = ArcSignalVec::new(vec![1, 2, 3]);
+        let (mut state, mut diffs) = mounted_list(&items);
+
+        items.set_at(1, 5);
+        state.apply_all(&mut diffs, &RowView);
+        assert_eq!(rendered(&state), [1, 5, 3]);
+        assert_eq!(mounted(), [1, 5, 3]);
+    }
+
+    #[test]
+    fn rows_are_not_mounted_while_the_list_is_not() {
+        let items = ArcSignalVec::new(vec![1]);
+        let (mut state, mut diffs) = mounted_list(&items);
+
+        // this is what unmounting leaves behind, since the marker itself can only be unmounted
+        // in a browser
+        state.parent = None;
+        items.push(2);
+        items.insert(0, 0);
+        state.apply_all(&mut diffs, &RowView);
+        assert_eq!(rendered(&state), [0, 1, 2]);
+        assert_eq!(mounted(), [1]);
+    }
+}
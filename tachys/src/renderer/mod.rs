@@ -38,9 +38,13 @@ pub mod sledgehammer; */
 
 /// Implements the instructions necessary to render an interface on some platform.
 ///
-/// By default, this is implemented for the Document Object Model (DOM) in a Web
-/// browser, but implementing this trait for some other platform allows you to use
-/// the library to render any tree-based UI.
+/// This is implemented for the Document Object Model (DOM) in a Web browser.
+///
+/// ### Note
+/// View types are not generic over the renderer: they always render with [`Rndr`], and
+/// [`Mountable`] takes DOM nodes directly. Implementing this trait for some other platform
+/// (a terminal, for example) does not allow existing views to be rendered there. See [`Rndr`]
+/// for why.
 pub trait Renderer: Send + Sized + Debug + 'static {
     /// The basic type of node in the view tree.
     type Node: Mountable + Clone + 'static;